@@ -1,4 +1,8 @@
+extern crate rust_by_example;
+
+use std::io;
 
 fn main() {
-    println!("Hello, world")
-}
\ No newline at end of file
+    let stdout = io::stdout();
+    rust_by_example::hello::run(&mut stdout.lock()).unwrap();
+}
@@ -1,20 +1,16 @@
-// rust has 2 types of constants that can be declared in any scope.
-// const and static
+extern crate rust_by_example;
 
-// Globals are declared outside all other scopes.
-static LANGUAGE: &'static str = "Rust";
-const THRESHOLD: i32 = 10;
+use std::io;
+use std::process;
 
-fn is_big(n: i32) -> bool {
-    // Access constant in some function
-    n > THRESHOLD
-}
+use rust_by_example::custom_types::config;
 
 fn main() {
-    let n = 16;
-
-    // Access constant in the main thread
-    println!("This is {}", LANGUAGE);
-    println!("The threshold is {}", THRESHOLD);
-    println!("{} is {}", n, if is_big(n) { "big" } else { "small" });
-}
\ No newline at end of file
+    // the file named by $RBE_CONFIG and the $RBE_ variables
+    if let Err(e) = config::init() {
+        eprintln!("c3_constants: {}", e);
+        process::exit(1);
+    }
+    let stdout = io::stdout();
+    rust_by_example::custom_types::constants::run(&mut stdout.lock()).unwrap();
+}
@@ -0,0 +1,148 @@
+// rbe - a single launcher for every example in the library
+// rbe list                  list all examples with their chapter and summary
+// rbe run <chapter/topic>   run an example, e.g. `rbe run custom_types/enums` or `rbe run 3/enums`
+// rbe search <keyword>      list the examples whose path or leading comment mentions the keyword
+// rbe check [chapter/topic] compare the output of every example (or just one) against golden/
+// rbe bless [chapter/topic] record the current output of every example (or just one) in golden/
+//                           check and bless run the examples with the default config; run loads $RBE_CONFIG
+// rbe diff <left> <right> [tolerance ms]
+//                           compare two WebEvent sessions recorded as JSON Lines and print where they diverge
+
+extern crate rust_by_example;
+
+use std::env;
+use std::fs;
+use std::io;
+use std::process;
+use std::time::Duration;
+
+use rust_by_example::catalog::{self, Example};
+use rust_by_example::custom_types::Stamped;
+use rust_by_example::custom_types::config;
+use rust_by_example::custom_types::session;
+use rust_by_example::golden::{self, Outcome};
+
+const USAGE: &str = "usage: rbe list | rbe run <chapter/topic> | rbe search <keyword> | \
+                     rbe check [chapter/topic] | rbe bless [chapter/topic] | \
+                     rbe diff <left> <right> [tolerance ms]";
+
+fn print_examples(examples: &[&Example]) {
+    let width = examples.iter().map(|example| example.path.len()).max().unwrap_or(0);
+    for example in examples {
+        println!("{:>3}  {:<width$}  {}", example.chapter, example.path, example.summary(), width = width);
+    }
+}
+
+fn find_or_exit(name: &str) -> &'static Example {
+    catalog::find(name).unwrap_or_else(|| {
+        eprintln!("rbe: no example named `{}`; try `rbe list`", name);
+        process::exit(1);
+    })
+}
+
+// every example, or only the one named
+fn selection(name: Option<&str>) -> Vec<&'static Example> {
+    match name {
+        Some(name) => vec![find_or_exit(name)],
+        None => catalog::EXAMPLES.iter().collect(),
+    }
+}
+
+fn read_session_or_exit(path: &str) -> Vec<Stamped> {
+    let text = fs::read_to_string(path).unwrap_or_else(|e| {
+        eprintln!("rbe: could not read {}: {}", path, e);
+        process::exit(1);
+    });
+    session::decode(&text).unwrap_or_else(|e| {
+        eprintln!("rbe: {}: {}", path, e);
+        process::exit(1);
+    })
+}
+
+// prints one line per example and returns the number that didn't match
+fn check(examples: &[&Example]) -> usize {
+    let mut failures = 0;
+    for example in examples {
+        match golden::check(example) {
+            Ok(Outcome::Match) => println!("ok    {}", example.path),
+            Ok(outcome) => {
+                failures += 1;
+                println!("FAIL  {}: {}", example.path, outcome);
+            },
+            Err(e) => {
+                failures += 1;
+                println!("FAIL  {}: {}", example.path, e);
+            },
+        }
+    }
+    failures
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let args: Vec<&str> = args.iter().map(|arg| arg.as_str()).collect();
+
+    match args.as_slice() {
+        ["list"] => {
+            let examples: Vec<&Example> = catalog::EXAMPLES.iter().collect();
+            print_examples(&examples);
+        },
+        ["run", name] => {
+            let example = find_or_exit(name);
+            if let Err(e) = config::init() {
+                eprintln!("rbe: {}", e);
+                process::exit(1);
+            }
+            let stdout = io::stdout();
+            if let Err(e) = (example.run)(&mut stdout.lock()) {
+                eprintln!("rbe: {} failed: {}", example.path, e);
+                process::exit(1);
+            }
+        },
+        ["search", keyword] => {
+            let examples = catalog::search(keyword);
+            if examples.is_empty() {
+                eprintln!("rbe: nothing matches `{}`", keyword);
+                process::exit(1);
+            }
+            print_examples(&examples);
+        },
+        ["check"] | ["check", _] => {
+            let failures = check(&selection(args.get(1).cloned()));
+            if failures > 0 {
+                eprintln!("rbe: {} example(s) differ from their golden output", failures);
+                process::exit(1);
+            }
+        },
+        ["bless"] | ["bless", _] => {
+            for example in selection(args.get(1).cloned()) {
+                if let Err(e) = golden::bless(example) {
+                    eprintln!("rbe: could not record {}: {}", example.path, e);
+                    process::exit(1);
+                }
+                println!("recorded {}", golden::path(example).display());
+            }
+        },
+        ["diff", left, right] | ["diff", left, right, _] => {
+            let tolerance = match args.get(3) {
+                Some(millis) => Duration::from_millis(millis.parse().unwrap_or_else(|_| {
+                    eprintln!("rbe: `{}` is not a number of milliseconds", millis);
+                    process::exit(2);
+                })),
+                None => Duration::from_secs(0),
+            };
+            let (left, right) = (read_session_or_exit(left), read_session_or_exit(right));
+            match session::diverge(&left, &right, tolerance) {
+                Some(divergence) => {
+                    println!("{}", divergence);
+                    process::exit(1);
+                },
+                None => println!("the sessions match: {} events", left.len()),
+            }
+        },
+        _ => {
+            eprintln!("{}", USAGE);
+            process::exit(2);
+        },
+    }
+}
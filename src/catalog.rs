@@ -0,0 +1,166 @@
+// CATALOG
+// A table of every example in the library, used by the `rbe` launcher to list, search and run them in-process.
+// The summary of an example is taken from the leading comment block of its source file, so the text that
+// introduces an example is also what describes it in `rbe list`.
+
+use {conversion, custom_types, error, flow_control, formatted_print, functions, generics, geometry, hello, macros,
+     modules, primitives, scoping, traits, types, variable_bindings};
+
+use std::io::{self, Write};
+
+pub struct Example {
+    pub chapter: u32,
+    // module path of the example with `/` separators, e.g. "custom_types/enums"
+    pub path: &'static str,
+    pub source: &'static str,
+    // runs the example, writing its output to the given sink
+    pub run: fn(&mut dyn Write) -> io::Result<()>,
+}
+
+impl Example {
+    // the last segment of the path, e.g. "enums"
+    pub fn topic(&self) -> &'static str {
+        self.path.rsplit('/').next().unwrap_or(self.path)
+    }
+
+    // the leading `//` comment lines of the source file, without the comment markers. Inner attributes like
+    // `#![allow(..)]` can come first, and the comments right above them are about them rather than the example
+    pub fn comment_block(&self) -> Vec<&'static str> {
+        fn is_comment(line: &&str) -> bool {
+            line.trim_start().starts_with("//")
+        }
+
+        let mut lines: Vec<&'static str> = self.source.lines().collect();
+        loop {
+            let blank = lines.iter().take_while(|line| line.trim().is_empty()).count();
+            lines.drain(..blank);
+            let comments = lines.iter().take_while(|line| is_comment(line)).count();
+            match lines.get(comments) {
+                Some(line) if line.trim_start().starts_with("#!") => { lines.drain(..comments + 1); },
+                _ => break,
+            }
+        }
+        lines.into_iter()
+            .take_while(is_comment)
+            .map(|line| line.trim_start().trim_start_matches('/').trim())
+            .collect()
+    }
+
+    // the first line of the comment block that isn't an upper case heading like `// GENERICS`
+    pub fn summary(&self) -> &'static str {
+        let block = self.comment_block();
+        block.iter()
+            .find(|line| !is_heading(line))
+            .or_else(|| block.first())
+            .cloned()
+            .unwrap_or("")
+    }
+
+    // runs the example and captures everything it writes
+    pub fn output(&self) -> io::Result<String> {
+        let mut buffer = Vec::new();
+        (self.run)(&mut buffer)?;
+        Ok(String::from_utf8_lossy(&buffer).into_owned())
+    }
+
+    // true if `name` is either the full path or `<chapter>/<topic>`
+    pub fn matches_name(&self, name: &str) -> bool {
+        name == self.path || name == format!("{}/{}", self.chapter, self.topic())
+    }
+
+    // case insensitive search over the path and the leading comment block
+    pub fn matches_keyword(&self, keyword: &str) -> bool {
+        let keyword = keyword.to_lowercase();
+        self.path.to_lowercase().contains(&keyword) ||
+            self.comment_block().iter().any(|line| line.to_lowercase().contains(&keyword))
+    }
+}
+
+// upper case like `GENERICS`, or a single word title like `Derive`
+fn is_heading(line: &str) -> bool {
+    let upper_case = line.chars().any(|c| c.is_alphabetic()) && !line.chars().any(|c| c.is_lowercase());
+    let title = line.starts_with(char::is_uppercase) && line.chars().all(char::is_alphabetic);
+    upper_case || title
+}
+
+macro_rules! example {
+    ($chapter:expr, $path:expr, $file:expr, $run:path) => {
+        // the `run` functions are generic over the writer, so they are wrapped to take a trait object
+        Example {
+            chapter: $chapter,
+            path: $path,
+            source: include_str!($file),
+            run: |mut out: &mut dyn Write| $run(&mut out),
+        }
+    };
+}
+
+pub static EXAMPLES: &[Example] = &[
+    example!(1, "hello", "hello.rs", hello::run),
+    example!(1, "formatted_print", "formatted_print/mod.rs", formatted_print::run),
+    example!(2, "primitives", "primitives/mod.rs", primitives::run),
+    example!(3, "custom_types/structs", "custom_types/structs.rs", custom_types::structs::run),
+    example!(3, "custom_types/enums", "custom_types/enums.rs", custom_types::enums::run),
+    example!(3, "custom_types/events", "custom_types/events.rs", custom_types::events::run),
+    example!(3, "custom_types/session", "custom_types/session.rs", custom_types::session::run),
+    example!(3, "custom_types/keymap", "custom_types/keymap.rs", custom_types::keymap::run),
+    example!(3, "custom_types/widgets", "custom_types/widgets.rs", custom_types::widgets::run),
+    example!(3, "custom_types/registry", "custom_types/registry.rs", custom_types::registry::run),
+    example!(3, "custom_types/linked_list", "custom_types/linked_list.rs", custom_types::linked_list::run),
+    example!(3, "custom_types/constants", "custom_types/constants.rs", custom_types::constants::run),
+    example!(4, "variable_bindings", "variable_bindings.rs", variable_bindings::run),
+    example!(5, "types", "types/mod.rs", types::run),
+    example!(6, "conversion", "conversion.rs", conversion::run),
+    example!(8, "flow_control/loops", "flow_control/loops.rs", flow_control::loops::run),
+    example!(8, "flow_control/matching", "flow_control/matching.rs", flow_control::matching::run),
+    example!(9, "functions/methods", "functions/methods.rs", functions::methods::run),
+    example!(9, "functions/closures", "functions/closures.rs", functions::closures::run),
+    example!(9, "functions/higher_order", "functions/higher_order.rs", functions::higher_order::run),
+    example!(10, "modules/visibility", "modules/visibility.rs", modules::visibility::run),
+    example!(10, "modules/use_self_super", "modules/use_self_super.rs", modules::use_self_super::run),
+    example!(14, "generics/basics", "generics/basics.rs", generics::basics::run),
+    example!(14, "generics/bounds", "generics/bounds.rs", generics::bounds::run),
+    example!(14, "generics/new_types", "generics/new_types.rs", generics::new_types::run),
+    example!(14, "generics/associated_types", "generics/associated_types.rs", generics::associated_types::run),
+    example!(15, "scoping/ownership", "scoping/ownership.rs", scoping::ownership::run),
+    example!(15, "scoping/borrowing", "scoping/borrowing.rs", scoping::borrowing::run),
+    example!(15, "scoping/lifetimes", "scoping/lifetimes.rs", scoping::lifetimes::run),
+    example!(15, "scoping/lifetime_structs", "scoping/lifetime_structs.rs", scoping::lifetime_structs::run),
+    example!(15, "scoping/lifetime_static", "scoping/lifetime_static.rs", scoping::lifetime_static::run),
+    example!(16, "traits/animals", "traits/animals.rs", traits::animals::run),
+    example!(16, "traits/derive_ops_drop", "traits/derive_ops_drop.rs", traits::derive_ops_drop::run),
+    example!(16, "traits/iter_clone", "traits/iter_clone.rs", traits::iter_clone::run),
+    example!(17, "macros/rules_syntax", "macros/rules_syntax.rs", macros::rules_syntax::run),
+    example!(17, "macros/dry_dsl_variadics", "macros/dry_dsl_variadics.rs", macros::dry_dsl_variadics::run),
+    example!(18, "error/option_unwrap", "error/option_unwrap.rs", error::option_unwrap::run),
+    example!(18, "error/result", "error/result.rs", error::result::run),
+    // not a chapter of the book; after all of them
+    example!(19, "geometry", "geometry/mod.rs", geometry::run),
+];
+
+pub fn find(name: &str) -> Option<&'static Example> {
+    EXAMPLES.iter().find(|example| example.matches_name(name))
+}
+
+pub fn search(keyword: &str) -> Vec<&'static Example> {
+    EXAMPLES.iter().filter(|example| example.matches_keyword(keyword)).collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn summaries_skip_headings_and_attributes() {
+        let summary = |path| find(path).unwrap().summary();
+        // below `// Derive`
+        assert_eq!(summary("traits/derive_ops_drop"),
+                   "The compiler is capable of providing basic implementations for some traits via the #[derive] attribute.");
+        // below an `#![allow(..)]` and the comment that explains it
+        assert_eq!(summary("error/option_unwrap"), "panic is used for tests and dealing with unrecoverable errors.");
+        assert!(summary("flow_control/loops").starts_with("Branching with if-else"));
+        // every chapter in order, with anything that isn't one after them
+        assert!(EXAMPLES.windows(2).all(|pair| pair[0].chapter <= pair[1].chapter));
+        assert_eq!(EXAMPLES.last().unwrap().path, "geometry");
+    }
+}
@@ -0,0 +1,234 @@
+// Rust addresses conversion between types by using traits
+// From and Into are for generic conversions and there are more specific ones.
+
+use std::io::{self, Write};
+
+pub fn run(out: &mut impl Write) -> io::Result<()> {
+    let my_str = "hello";
+    // the from trait allows for a type to create itself from another type.
+    // my_str is a `str` and we convert it to a String
+    let _my_string = String::from(my_str); // prefixed with `_` as it isn't used in the program
+
+    let num = Number::from(30);
+    writeln!(out, "Number on using from: {:?}", num)?;
+
+    // Into is the reciprocal of From. Using into will require the specification of the type to convert to
+    // The compiler is unable to identify it most of the time
+
+    let int = 5;
+    let num: Number = int.into();
+    writeln!(out, "Number on using into: {:?}", num)?;
+
+    // TryFrom is the fallible version of From: a conversion that can fail returns a Result
+    writeln!(out, "Number from 7i64: {:?}", Number::try_from(7i64))?;
+    match Number::try_from(1e10) {
+        Ok(num) => writeln!(out, "Number from 1e10: {:?}", num)?,
+        Err(e) => writeln!(out, "Number from 1e10 fails: {}", e)?,
+    }
+
+    // ToString, which geometry's Circle gets from its Display impl below
+    let circle = Circle::new(Point::origin(), 6.0);
+    let circle_string: String = circle.to_string();
+    writeln!(out, "{}", circle_string)?;
+
+    // It's more common convert stings to a numeric type.
+    // we can use the parse function and provide the type for the function to parse the string value into
+    // this can be done either without type inference or with the turbofish syntax.
+    // A string will be converted to a type as long as the FromStr trait is implemented for the type.
+    // to have this functionality for a user defined type, simply define this trait for the type
+
+    let parsed: i32 = "5".parse().unwrap();
+    let turbo_parsed = "20".parse::<i32>().unwrap();
+
+    let sum = parsed + turbo_parsed;
+    writeln!(out, "parsed + turbo_parsed: {}", sum)?;
+
+    // Circle's FromStr reads back what its Display writes
+    let parsed_circle: Circle = circle_string.parse().unwrap();
+    writeln!(out, "parsed back: {:?}", parsed_circle)?;
+    writeln!(out, "parsing \"Square of side: 6\": {:?}", "Square of side: 6".parse::<Circle>())?;
+
+    Ok(())
+}
+
+// we can define a conversion for our own type
+
+use std::convert::{From, TryFrom};
+use std::error;
+use std::fmt;
+use std::num::{ParseFloatError, ParseIntError};
+use std::str::FromStr;
+
+use custom_types::Point;
+use geometry::Circle;
+use types::cast::{Cast, CastError};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Number {
+    pub value: i32,
+}
+
+impl From<i32> for Number {
+    fn from(item: i32) -> Self {
+        Number { value: item }
+    }
+}
+
+// TryFrom for the wider types: the value has to fit in an i32, which types::cast checks and explains
+impl TryFrom<i64> for Number {
+    type Error = CastError;
+
+    fn try_from(item: i64) -> Result<Self, CastError> {
+        item.checked_cast::<i32>().map(Number::from)
+    }
+}
+
+impl TryFrom<u64> for Number {
+    type Error = CastError;
+
+    fn try_from(item: u64) -> Result<Self, CastError> {
+        item.checked_cast::<i32>().map(Number::from)
+    }
+}
+
+// a float converts only if it is a whole number: not NaN, infinite or fractional
+impl TryFrom<f64> for Number {
+    type Error = CastError;
+
+    fn try_from(item: f64) -> Result<Self, CastError> {
+        item.checked_cast::<i32>().map(Number::from)
+    }
+}
+
+// just the value, like the i32 it wraps
+impl fmt::Display for Number {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.value)
+    }
+}
+
+impl FromStr for Number {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, ParseError> {
+        s.parse::<i32>().map(Number::from).map_err(ParseError::Value)
+    }
+}
+
+// To and From Strings
+// To convert any type to a string, implement the Display trait for it: ToString comes with Display, and the type
+// can be used in format strings too. FromStr is the way back, and should read what Display writes, so a Circle
+// writes its center as well as its radius.
+
+const CIRCLE_PREFIX: &str = "Circle of radius: ";
+
+impl fmt::Display for Circle {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}{} at ({}, {})", CIRCLE_PREFIX, self.radius, self.center.x, self.center.y)
+    }
+}
+
+impl FromStr for Circle {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, ParseError> {
+        let rest = s.strip_prefix(CIRCLE_PREFIX).ok_or(ParseError::Prefix { expected: CIRCLE_PREFIX })?;
+        let (radius, center) = rest.split_once(" at ").ok_or(ParseError::Center)?;
+        let (x, y) = center.strip_prefix('(')
+            .and_then(|center| center.strip_suffix(')'))
+            .and_then(|center| center.split_once(", "))
+            .ok_or(ParseError::Center)?;
+        let float = |s: &str| s.parse::<f64>().map_err(ParseError::Float);
+        let radius = float(radius)?;
+        if !(radius.is_finite() && radius >= 0.0) {
+            return Err(ParseError::Radius);
+        }
+        Ok(Circle::new(Point::new(float(x)?, float(y)?), radius))
+    }
+}
+
+// Why the text isn't the display form of a Number or Circle
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParseError {
+    // the text doesn't start the way the display form does
+    Prefix { expected: &'static str },
+    Value(ParseIntError),
+    Float(ParseFloatError),
+    // a Circle's radius is negative, infinite or NaN
+    Radius,
+    // a Circle's radius isn't followed by ` at (x, y)`
+    Center,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ParseError::Prefix { expected } => write!(f, "expected text starting with {:?}", expected),
+            ParseError::Value(ref e) => write!(f, "bad value: {}", e),
+            ParseError::Float(ref e) => write!(f, "bad value: {}", e),
+            ParseError::Center => write!(f, "expected the center as ` at (x, y)` after the radius"),
+            ParseError::Radius => write!(f, "a radius has to be finite and not negative"),
+        }
+    }
+}
+
+impl error::Error for ParseError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match *self {
+            ParseError::Value(ref e) => Some(e),
+            ParseError::Float(ref e) => Some(e),
+            ParseError::Prefix { .. } | ParseError::Center | ParseError::Radius => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use types::cast::Reason;
+
+    const VALUES: [i32; 9] = [i32::MIN, i32::MIN + 1, -1000, -1, 0, 1, 6, 1000, i32::MAX];
+
+    #[test]
+    fn round_trips() {
+        for &value in VALUES.iter() {
+            let number = Number::from(value);
+            assert_eq!(number.to_string().parse(), Ok(number));
+            let circle = Circle::new(Point::new(value as f64 / 3.0, -0.1), (value as f64).abs());
+            assert_eq!(circle.to_string().parse(), Ok(circle));
+        }
+        assert_eq!(Circle::new(Point::origin(), 6.0).to_string(), "Circle of radius: 6 at (0, 0)");
+        let tiny = Circle::new(Point::new(1e-300, f64::MAX), f64::MIN_POSITIVE);
+        assert_eq!(tiny.to_string().parse(), Ok(tiny));
+    }
+
+    #[test]
+    fn try_from() {
+        assert_eq!(Number::try_from(i32::MAX as i64), Ok(Number::from(i32::MAX)));
+        assert_eq!(Number::try_from(i32::MIN as i64), Ok(Number::from(i32::MIN)));
+        assert_eq!(Number::try_from(i32::MAX as i64 + 1).unwrap_err().reason, Reason::Overflow);
+        assert_eq!(Number::try_from(i32::MIN as i64 - 1).unwrap_err().reason, Reason::Underflow);
+        assert_eq!(Number::try_from(u64::MAX).unwrap_err().reason, Reason::Overflow);
+        assert_eq!(Number::try_from(30u64), Ok(Number::from(30)));
+        assert_eq!(Number::try_from(-2147483648.0), Ok(Number::from(i32::MIN)));
+        assert_eq!(Number::try_from(2.5).unwrap_err().reason, Reason::Fractional);
+        assert_eq!(Number::try_from(f64::NAN).unwrap_err().reason, Reason::NaN);
+        assert_eq!(Number::try_from(f64::INFINITY).unwrap_err().to_string(), "cannot cast inf_f64 to i32: it is infinite");
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!("Square of side: 6".parse::<Circle>(), Err(ParseError::Prefix { expected: "Circle of radius: " }));
+        assert!(matches!("Circle of radius: six at (0, 0)".parse::<Circle>(), Err(ParseError::Float(_))));
+        assert!(matches!("Circle of radius: 6 at (0, y)".parse::<Circle>(), Err(ParseError::Float(_))));
+        assert_eq!("Circle of radius: 6".parse::<Circle>(), Err(ParseError::Center));
+        assert_eq!("Circle of radius: 6 at (0, 0) ".parse::<Circle>(), Err(ParseError::Center));
+        assert_eq!("Circle of radius: 6 at 0, 0".parse::<Circle>(), Err(ParseError::Center));
+        for radius in &["-5", "NaN", "inf", "-inf"] {
+            let text = format!("Circle of radius: {} at (0, 0)", radius);
+            assert_eq!(text.parse::<Circle>(), Err(ParseError::Radius));
+        }
+        assert_eq!("Circle of radius: 0 at (0, 0)".parse::<Circle>(), Ok(Circle::new(Point::origin(), 0.0)));
+        assert!(matches!("2147483648".parse::<Number>(), Err(ParseError::Value(_))));
+    }
+}
@@ -0,0 +1,558 @@
+// CONFIG
+// THRESHOLD and LANGUAGE are compiled into the binary; a Config lets a deployment change them without a rebuild.
+// Settings are read from a small INI/TOML-like file:
+//
+//     # comments start with # or ;
+//     threshold = 20
+//     language = "Rust"      # strings may be quoted, with \" \\ \n \t \r and \u{..} escapes
+//
+//     [server]               # keys below a section are read as `server.port`
+//     port = 8080
+//     verbose = true
+//
+// Values are booleans, integers (which may contain `_`), floats or strings. A setting is then overridden by an
+// environment variable named after it: `RBE_THRESHOLD`, `RBE_SERVER_PORT`. Anything not set anywhere falls back to
+// the compiled constant.
+//
+// `global()` is a handle to the config for the whole program, which `set_global` or `init` can replace from any
+// thread.
+
+use std::collections::BTreeMap;
+use std::convert::TryFrom;
+use std::env;
+use std::error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, PoisonError, RwLock};
+
+use custom_types::constants::{LANGUAGE, THRESHOLD};
+
+// environment variables start with this and `_`
+pub const ENV_PREFIX: &str = "RBE";
+// the environment variable `init` reads the path of the config file from
+pub const ENV_FILE: &str = "RBE_CONFIG";
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    Str(String),
+}
+
+impl Value {
+    pub fn type_name(&self) -> &'static str {
+        match *self {
+            Value::Bool(_) => "a boolean",
+            Value::Int(_) => "an integer",
+            Value::Float(_) => "a float",
+            Value::Str(_) => "a string",
+        }
+    }
+
+    // an unquoted value: a boolean or a number if it looks like one, otherwise the text as it is
+    fn infer(text: &str) -> Result<Value, ParseErrorKind> {
+        match text {
+            "true" => return Ok(Value::Bool(true)),
+            "false" => return Ok(Value::Bool(false)),
+            _ => {},
+        }
+        let digits = text.trim_start_matches(['+', '-']);
+        if !digits.starts_with(|c: char| c.is_ascii_digit() || c == '.') {
+            return Ok(Value::Str(text.to_owned()));
+        }
+        let number = text.replace('_', "");
+        if digits.chars().all(|c| c.is_ascii_digit() || c == '_') {
+            return number.parse().map(Value::Int).map_err(|_| ParseErrorKind::IntegerOutOfRange(text.to_owned()));
+        }
+        Ok(number.parse().map(Value::Float).unwrap_or_else(|_| Value::Str(text.to_owned())))
+    }
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Value::Bool(b) => write!(f, "{}", b),
+            Value::Int(i) => write!(f, "{}", i),
+            Value::Float(x) => write!(f, "{:?}", x),
+            Value::Str(ref s) => write!(f, "{:?}", s),
+        }
+    }
+}
+
+// Where a setting came from, for error messages
+#[derive(Debug, Clone, PartialEq)]
+pub enum Origin {
+    Default,
+    Line(usize),
+    Env(String),
+}
+
+impl fmt::Display for Origin {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Origin::Default => write!(f, "the default"),
+            Origin::Line(line) => write!(f, "line {}", line),
+            Origin::Env(ref var) => write!(f, "${}", var),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Entry {
+    pub value: Value,
+    pub origin: Origin,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParseErrorKind {
+    MissingEquals,
+    EmptyKey,
+    EmptyValue,
+    // keys and section names are letters, digits, `_`, `-` and `.`
+    BadKey(String),
+    UnclosedSection,
+    UnclosedString,
+    BadEscape(String),
+    // anything but a comment after a quoted string or a section header
+    TrailingCharacters(String),
+    IntegerOutOfRange(String),
+    DuplicateKey { key: String, first: usize },
+}
+
+impl fmt::Display for ParseErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ParseErrorKind::MissingEquals => write!(f, "expected `key = value`"),
+            ParseErrorKind::EmptyKey => write!(f, "missing key before `=`"),
+            ParseErrorKind::EmptyValue => write!(f, "missing value after `=`"),
+            ParseErrorKind::BadKey(ref key) => write!(f, "`{}` is not a valid name", key),
+            ParseErrorKind::UnclosedSection => write!(f, "section header is missing its `]`"),
+            ParseErrorKind::UnclosedString => write!(f, "string is missing its closing `\"`"),
+            ParseErrorKind::BadEscape(ref escape) => write!(f, "unknown escape `{}`", escape),
+            ParseErrorKind::TrailingCharacters(ref rest) => write!(f, "unexpected `{}`", rest),
+            ParseErrorKind::IntegerOutOfRange(ref n) => write!(f, "{} does not fit in a 64-bit integer", n),
+            ParseErrorKind::DuplicateKey { ref key, first } => write!(f, "`{}` was already set on line {}", key, first),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError {
+    // counting from 1
+    pub line: usize,
+    pub kind: ParseErrorKind,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.kind)
+    }
+}
+
+impl error::Error for ParseError {}
+
+#[derive(Debug)]
+pub enum ConfigError {
+    Io { path: PathBuf, error: io::Error },
+    Parse { path: Option<PathBuf>, error: ParseError },
+    Missing(String),
+    WrongType { key: String, expected: &'static str, found: &'static str, origin: Origin },
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ConfigError::Io { ref path, ref error } => write!(f, "{}: {}", path.display(), error),
+            ConfigError::Parse { path: Some(ref path), ref error } => write!(f, "{}: {}", path.display(), error),
+            ConfigError::Parse { path: None, ref error } => write!(f, "{}", error),
+            ConfigError::Missing(ref key) => write!(f, "`{}` is not set", key),
+            ConfigError::WrongType { ref key, expected, found, ref origin } =>
+                write!(f, "`{}` should be {} but {} sets it to {}", key, expected, origin, found),
+        }
+    }
+}
+
+impl error::Error for ConfigError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match *self {
+            ConfigError::Io { ref error, .. } => Some(error),
+            ConfigError::Parse { ref error, .. } => Some(error),
+            _ => None,
+        }
+    }
+}
+
+// The Rust types a Value can be read as
+pub trait FromValue: Sized {
+    // e.g. "an integer", for WrongType
+    const EXPECTED: &'static str;
+    fn from_value(value: &Value) -> Option<Self>;
+}
+
+impl FromValue for bool {
+    const EXPECTED: &'static str = "a boolean";
+    fn from_value(value: &Value) -> Option<bool> {
+        match *value { Value::Bool(b) => Some(b), _ => None }
+    }
+}
+
+impl FromValue for String {
+    const EXPECTED: &'static str = "a string";
+    fn from_value(value: &Value) -> Option<String> {
+        match *value { Value::Str(ref s) => Some(s.clone()), _ => None }
+    }
+}
+
+// an integer is fine where a float is expected
+impl FromValue for f64 {
+    const EXPECTED: &'static str = "a number";
+    fn from_value(value: &Value) -> Option<f64> {
+        match *value { Value::Float(x) => Some(x), Value::Int(i) => Some(i as f64), _ => None }
+    }
+}
+
+macro_rules! from_int_value {
+    ($($t:ty => $expected:expr),*) => {
+        $(
+            impl FromValue for $t {
+                const EXPECTED: &'static str = $expected;
+                fn from_value(value: &Value) -> Option<$t> {
+                    match *value { Value::Int(i) => <$t>::try_from(i).ok(), _ => None }
+                }
+            }
+        )*
+    };
+}
+
+from_int_value!(i64 => "an integer", i32 => "a 32-bit integer", u16 => "an integer from 0 to 65535",
+                u32 => "a non-negative 32-bit integer", usize => "a non-negative integer");
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Config {
+    // keys inside a section are `section.key`
+    entries: BTreeMap<String, Entry>,
+}
+
+impl Config {
+    pub fn empty() -> Config {
+        Config::default()
+    }
+
+    // the compiled constants
+    pub fn defaults() -> Config {
+        let mut config = Config::empty();
+        config.set("threshold", Value::Int(THRESHOLD as i64), Origin::Default);
+        config.set("language", Value::Str(LANGUAGE.to_owned()), Origin::Default);
+        config
+    }
+
+    // Only the settings in the text, without the defaults
+    pub fn parse(text: &str) -> Result<Config, ParseError> {
+        let mut config = Config::empty();
+        let mut section = String::new();
+        for (index, line) in text.lines().enumerate() {
+            let line_number = index + 1;
+            let error = |kind| ParseError { line: line_number, kind };
+            let line = line.trim();
+            if line.is_empty() || line.starts_with(['#', ';']) {
+                continue;
+            }
+
+            if let Some(header) = line.strip_prefix('[') {
+                let end = header.find(']').ok_or_else(|| error(ParseErrorKind::UnclosedSection))?;
+                check_comment(&header[end + 1..]).map_err(error)?;
+                section = header[..end].trim().to_owned();
+                check_name(&section).map_err(error)?;
+                continue;
+            }
+
+            let equals = line.find('=').ok_or_else(|| error(ParseErrorKind::MissingEquals))?;
+            let key = line[..equals].trim();
+            if key.is_empty() {
+                return Err(error(ParseErrorKind::EmptyKey));
+            }
+            check_name(key).map_err(error)?;
+            let value = parse_value(line[equals + 1..].trim()).map_err(error)?;
+
+            let key = if section.is_empty() { key.to_owned() } else { format!("{}.{}", section, key) };
+            if let Some(&Entry { origin: Origin::Line(first), .. }) = config.entries.get(&key) {
+                return Err(error(ParseErrorKind::DuplicateKey { key, first }));
+            }
+            config.set(&key, value, Origin::Line(line_number));
+        }
+        Ok(config)
+    }
+
+    // The defaults, then the file, then the environment
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Config, ConfigError> {
+        let path = path.as_ref();
+        let text = fs::read_to_string(path).map_err(|error| ConfigError::Io { path: path.to_owned(), error })?;
+        let file = Config::parse(&text).map_err(|error| ConfigError::Parse { path: Some(path.to_owned()), error })?;
+        let mut config = Config::defaults();
+        config.merge(file);
+        config.apply_env(ENV_PREFIX, env::vars());
+        config.validate()?;
+        Ok(config)
+    }
+
+    // The defaults and the environment, for when there is no file
+    pub fn from_env() -> Result<Config, ConfigError> {
+        let mut config = Config::defaults();
+        config.apply_env(ENV_PREFIX, env::vars());
+        config.validate()?;
+        Ok(config)
+    }
+
+    pub fn set(&mut self, key: &str, value: Value, origin: Origin) {
+        self.entries.insert(key.to_owned(), Entry { value, origin });
+    }
+
+    // the other config's settings replace these
+    pub fn merge(&mut self, other: Config) {
+        self.entries.extend(other.entries);
+    }
+
+    // Overrides every setting whose variable is among `vars`: `prefix_KEY`, upper case, with `.` and `-` as `_`.
+    // Only settings that already exist can be overridden, since a variable name doesn't say where the sections end.
+    pub fn apply_env<I: IntoIterator<Item = (String, String)>>(&mut self, prefix: &str, vars: I) {
+        let vars: BTreeMap<String, String> = vars.into_iter().collect();
+        for (key, entry) in self.entries.iter_mut() {
+            let var = env_var(prefix, key);
+            if let Some(text) = vars.get(&var) {
+                // a variable has no quotes to tell a string from a number, so anything that isn't one is a string
+                let value = Value::infer(text.trim()).unwrap_or_else(|_| Value::Str(text.clone()));
+                *entry = Entry { value, origin: Origin::Env(var) };
+            }
+        }
+    }
+
+    pub fn get(&self, key: &str) -> Option<&Entry> {
+        self.entries.get(key)
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = &str> {
+        self.entries.keys().map(|key| key.as_str())
+    }
+
+    pub fn value<T: FromValue>(&self, key: &str) -> Result<T, ConfigError> {
+        let entry = self.get(key).ok_or_else(|| ConfigError::Missing(key.to_owned()))?;
+        T::from_value(&entry.value).ok_or_else(|| ConfigError::WrongType {
+            key: key.to_owned(),
+            expected: T::EXPECTED,
+            found: entry.value.type_name(),
+            origin: entry.origin.clone(),
+        })
+    }
+
+    // Checks the settings the program itself reads, so that a typo is reported once, up front
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        self.value::<i32>("threshold")?;
+        self.value::<String>("language")?;
+        Ok(())
+    }
+
+    // the threshold for `is_big`, or THRESHOLD if it isn't a valid setting
+    pub fn threshold(&self) -> i32 {
+        self.value("threshold").unwrap_or(THRESHOLD)
+    }
+
+    pub fn language(&self) -> String {
+        self.value("language").unwrap_or_else(|_| LANGUAGE.to_owned())
+    }
+}
+
+fn env_var(prefix: &str, key: &str) -> String {
+    format!("{}_{}", prefix, key.to_uppercase().replace(['.', '-'], "_"))
+}
+
+fn check_name(name: &str) -> Result<(), ParseErrorKind> {
+    if !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || "_-.".contains(c)) {
+        Ok(())
+    } else {
+        Err(ParseErrorKind::BadKey(name.to_owned()))
+    }
+}
+
+// what's left of a line may only be a comment
+fn check_comment(rest: &str) -> Result<(), ParseErrorKind> {
+    let rest = rest.trim();
+    if rest.is_empty() || rest.starts_with(['#', ';']) {
+        Ok(())
+    } else {
+        Err(ParseErrorKind::TrailingCharacters(rest.to_owned()))
+    }
+}
+
+fn parse_value(text: &str) -> Result<Value, ParseErrorKind> {
+    if let Some(quoted) = text.strip_prefix('"') {
+        let (value, rest) = parse_string(quoted)?;
+        check_comment(rest)?;
+        return Ok(Value::Str(value));
+    }
+    // an unquoted value ends where a comment starts, at a `#` or `;` that starts a word
+    let end = text.char_indices()
+        .find(|&(i, c)| (c == '#' || c == ';') && (i == 0 || text[..i].ends_with(char::is_whitespace)))
+        .map_or(text.len(), |(i, _)| i);
+    let text = text[..end].trim();
+    if text.is_empty() {
+        return Err(ParseErrorKind::EmptyValue);
+    }
+    Value::infer(text)
+}
+
+// the string up to the closing quote, and the rest of the line after it
+fn parse_string(text: &str) -> Result<(String, &str), ParseErrorKind> {
+    let mut value = String::new();
+    let mut chars = text.char_indices();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '"' => return Ok((value, &text[i + 1..])),
+            '\\' => {
+                let escaped = match chars.next() {
+                    Some((_, '"')) => '"',
+                    Some((_, '\\')) => '\\',
+                    Some((_, 'n')) => '\n',
+                    Some((_, 't')) => '\t',
+                    Some((_, 'r')) => '\r',
+                    Some((start, 'u')) => {
+                        // `\u{1F980}`
+                        let hex = text[start + 1..].strip_prefix('{')
+                            .and_then(|rest| rest.find('}').map(|end| &rest[..end]));
+                        let code = hex.and_then(|hex| u32::from_str_radix(hex, 16).ok()).and_then(char::from_u32);
+                        match (hex, code) {
+                            (Some(hex), Some(code)) => {
+                                // skip past the `}`
+                                chars.nth(hex.len() + 1);
+                                code
+                            },
+                            _ => return Err(ParseErrorKind::BadEscape(text[start - 1..].chars().take(10).collect())),
+                        }
+                    },
+                    Some((_, other)) => return Err(ParseErrorKind::BadEscape(format!("\\{}", other))),
+                    None => return Err(ParseErrorKind::UnclosedString),
+                };
+                value.push(escaped);
+            },
+            c => value.push(c),
+        }
+    }
+    Err(ParseErrorKind::UnclosedString)
+}
+
+// GLOBAL
+// None until something sets it; readers then get the defaults and environment
+static GLOBAL: RwLock<Option<Arc<Config>>> = RwLock::new(None);
+
+// The current config; cheap to call, and the Arc stays valid even if another thread replaces the config
+pub fn global() -> Arc<Config> {
+    // a panic while holding the lock can't leave a half-written Option behind, so a poisoned lock is still fine
+    if let Some(ref config) = *GLOBAL.read().unwrap_or_else(PoisonError::into_inner) {
+        return Arc::clone(config);
+    }
+    let mut global = GLOBAL.write().unwrap_or_else(PoisonError::into_inner);
+    // an invalid environment can't be reported from here, so it falls back to the constants; `init` reports it
+    let config = global.get_or_insert_with(|| Arc::new(Config::from_env().unwrap_or_else(|_| Config::defaults())));
+    Arc::clone(config)
+}
+
+pub fn set_global(config: Config) {
+    *GLOBAL.write().unwrap_or_else(PoisonError::into_inner) = Some(Arc::new(config));
+}
+
+// Loads the file named by $RBE_CONFIG, or just the defaults and environment without it, and makes it the global
+// config
+pub fn init() -> Result<Arc<Config>, ConfigError> {
+    let config = match env::var_os(ENV_FILE) {
+        Some(path) => Config::load(path)?,
+        None => Config::from_env()?,
+    };
+    set_global(config);
+    Ok(global())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parses_settings() {
+        let config = Config::parse(r#"
+# deployment settings
+threshold = 1_000
+ratio = 0.5
+language = "Ru\"st\" \u{1F980}"   # with a comment
+name = plain text ; and a comment
+[server.http]
+port = 8080
+verbose = true
+"#).unwrap();
+        assert_eq!(config.keys().collect::<Vec<_>>(),
+                   vec!["language", "name", "ratio", "server.http.port", "server.http.verbose", "threshold"]);
+        assert_eq!(config.value::<i32>("threshold").unwrap(), 1000);
+        assert_eq!(config.value::<f64>("ratio").unwrap(), 0.5);
+        assert_eq!(config.value::<String>("language").unwrap(), "Ru\"st\" \u{1F980}");
+        assert_eq!(config.value::<String>("name").unwrap(), "plain text");
+        assert_eq!(config.value::<u16>("server.http.port").unwrap(), 8080);
+        assert!(config.value::<bool>("server.http.verbose").unwrap());
+        assert_eq!(config.get("server.http.port").unwrap().origin, Origin::Line(8));
+    }
+
+    #[test]
+    fn reports_line_numbers() {
+        let kind = |text: &str| Config::parse(text).unwrap_err();
+        assert_eq!(kind("a = 1\nb\n"), ParseError { line: 2, kind: ParseErrorKind::MissingEquals });
+        assert_eq!(kind("\n\n= 1").kind, ParseErrorKind::EmptyKey);
+        assert_eq!(kind("a =   # nothing").kind, ParseErrorKind::EmptyValue);
+        assert_eq!(kind("a b = 1").kind, ParseErrorKind::BadKey("a b".to_owned()));
+        assert_eq!(kind("[server").kind, ParseErrorKind::UnclosedSection);
+        assert_eq!(kind("a = \"open").kind, ParseErrorKind::UnclosedString);
+        assert_eq!(kind("a = \"\\q\"").kind, ParseErrorKind::BadEscape("\\q".to_owned()));
+        assert_eq!(kind("a = \"x\" y").kind, ParseErrorKind::TrailingCharacters("y".to_owned()));
+        assert_eq!(kind("a = 99999999999999999999").kind,
+                   ParseErrorKind::IntegerOutOfRange("99999999999999999999".to_owned()));
+        assert_eq!(kind("a = 1\n\na = 2"), ParseError {
+            line: 3,
+            kind: ParseErrorKind::DuplicateKey { key: "a".to_owned(), first: 1 },
+        });
+        assert_eq!(kind("a = 1\nb\n").to_string(), "line 2: expected `key = value`");
+    }
+
+    #[test]
+    fn layers_and_types() {
+        let mut config = Config::defaults();
+        assert_eq!((config.threshold(), config.language()), (THRESHOLD, LANGUAGE.to_owned()));
+
+        config.merge(Config::parse("threshold = 20\n[server]\nport = 80").unwrap());
+        config.apply_env("APP", vec![
+            ("APP_SERVER_PORT".to_owned(), "8080".to_owned()),
+            ("APP_LANGUAGE".to_owned(), "Rust 2015".to_owned()),
+            ("APP_UNKNOWN".to_owned(), "1".to_owned()),
+        ]);
+        assert_eq!(config.threshold(), 20);
+        assert_eq!(config.language(), "Rust 2015");
+        assert_eq!(config.get("server.port").unwrap(),
+                   &Entry { value: Value::Int(8080), origin: Origin::Env("APP_SERVER_PORT".to_owned()) });
+        assert!(config.get("unknown").is_none());
+        assert!(config.validate().is_ok());
+
+        config.apply_env("APP", vec![("APP_THRESHOLD".to_owned(), "ten".to_owned())]);
+        assert_eq!(config.threshold(), THRESHOLD);
+        assert_eq!(config.validate().unwrap_err().to_string(),
+                   "`threshold` should be a 32-bit integer but $APP_THRESHOLD sets it to a string");
+        assert!(matches!(config.value::<bool>("nope"), Err(ConfigError::Missing(_))));
+    }
+
+    #[test]
+    fn global_is_shared_across_threads() {
+        // the same config golden::check sets, so the tests running alongside this one don't change it
+        set_global(Config::defaults());
+        let threshold = global().threshold();
+        let handles: Vec<_> = (0..4).map(|_| ::std::thread::spawn(|| global().threshold())).collect();
+        for handle in handles {
+            assert_eq!(handle.join().unwrap(), threshold);
+        }
+    }
+}
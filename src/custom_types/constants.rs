@@ -0,0 +1,37 @@
+// rust has 2 types of constants that can be declared in any scope.
+// const and static
+
+use std::io::{self, Write};
+
+use custom_types::config::{self, Config};
+
+// Globals are declared outside all other scopes.
+pub static LANGUAGE: &str = "Rust";
+pub const THRESHOLD: i32 = 10;
+
+pub fn is_big(n: i32) -> bool {
+    // the threshold comes from the deployment's config (see custom_types::config), which falls back to THRESHOLD
+    n > config::global().threshold()
+}
+
+pub fn run(out: &mut impl Write) -> io::Result<()> {
+    let n = 16;
+
+    // Access constant in the main thread, through the config, which falls back to LANGUAGE
+    writeln!(out, "This is {}", config::global().language())?;
+    // what is_big compares with: THRESHOLD, unless the config overrides it
+    writeln!(out, "The threshold is {}", config::global().threshold())?;
+    writeln!(out, "{} is {}", n, if is_big(n) { "big" } else { "small" })?;
+
+    // CONFIG
+    // a constant can only change with a rebuild; a config file or $RBE_THRESHOLD can change a setting
+    let config = Config::parse("threshold = 20\nlanguage = \"Rust\"\n").unwrap();
+    writeln!(out, "The configured threshold is {} (from {})", config.threshold(),
+             config.get("threshold").unwrap().origin)?;
+    match Config::parse("threshold = 20\nlanguage Rust\n") {
+        Ok(_) => writeln!(out, "the broken config parsed")?,
+        Err(e) => writeln!(out, "The broken config fails at {}", e)?,
+    }
+
+    Ok(())
+}
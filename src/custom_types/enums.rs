@@ -0,0 +1,65 @@
+// An enum keyword allows the creation of a type that may be one of a few different variants.
+// Any variant valid as a struct is also valid as an enum
+
+use std::io::{self, Write};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum WebEvent {
+    // an enum can be unit like
+    PageLoad,
+    PageUnload,
+    // like tuple structs
+    KeyPress(char),
+    Paste(String),
+    // or like structures
+    Click { x: i64, y: i64 },
+}
+
+// A function that takes a WebEvent enum and returns nothing
+pub fn inspect(out: &mut impl Write, event: WebEvent) -> io::Result<()> {
+    match event {
+        WebEvent::PageLoad => writeln!(out, "page loaded")?,
+        WebEvent::PageUnload => writeln!(out, "page unloaded")?,
+
+        // Destructure c from inside the enum
+        WebEvent::KeyPress(c) => writeln!(out, "pressed '{}'.", c)?,
+        WebEvent::Paste(s) => writeln!(out, "pasted \"{}\".", s)?,
+
+        // Destructure click into `x` and `y`
+        WebEvent::Click { x, y } => {
+            writeln!(out, "clicked at x={}, y={}", x, y)?;
+        },
+    }
+
+    Ok(())
+}
+
+// Plain C-like enums, used by the `use` declaration example in linked_list
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Status {
+    Rich,
+    Poor,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Work {
+    Civilian,
+    Soldier,
+}
+
+pub fn run(out: &mut impl Write) -> io::Result<()> {
+    let pressed = WebEvent::KeyPress('x');
+    // `to_owned()` creates an owned string from a string slice.
+    let pasted = WebEvent::Paste("my text".to_owned());
+    let click = WebEvent::Click { x: 20, y: 80 };
+    let load = WebEvent::PageLoad;
+    let unload = WebEvent::PageUnload;
+
+    // Based on the type of argument in inspect, the function prints a different line
+
+    inspect(out, pressed)?;
+    inspect(out, pasted)?;
+    inspect(out, click)?;
+    inspect(out, load)?;
+    inspect(out, unload)
+}
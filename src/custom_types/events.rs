@@ -0,0 +1,540 @@
+// EVENTS
+// `inspect` handles one WebEvent by printing it. A Pipeline handles a stream of them: every event goes through the
+// stages in the order they were added, where a filter can drop it and a transformer can change it, and whatever
+// comes out the other end is counted in the pipeline's Stats and handed to the handlers subscribed to its Kind.
+//
+// Events can also be read from a text log, one per line, optionally after a time in seconds:
+//
+//     # comment
+//     0.0 page_load
+//     1.5 click 20 80
+//     key_press x
+//     2.25 paste my text
+//     page_unload
+//
+// An event without a time happened at the time of the event before it.
+
+use std::collections::BTreeMap;
+use std::error;
+use std::fmt;
+use std::io::{self, Write};
+use std::time::Duration;
+
+use custom_types::WebEvent;
+use primitives::stats::Running;
+
+// The variant of a WebEvent without its data, for subscribing
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Kind {
+    PageLoad,
+    PageUnload,
+    KeyPress,
+    Paste,
+    Click,
+}
+
+impl Kind {
+    pub const ALL: [Kind; 5] = [Kind::PageLoad, Kind::PageUnload, Kind::KeyPress, Kind::Paste, Kind::Click];
+
+    // the name in a text log
+    pub fn name(self) -> &'static str {
+        match self {
+            Kind::PageLoad => "page_load",
+            Kind::PageUnload => "page_unload",
+            Kind::KeyPress => "key_press",
+            Kind::Paste => "paste",
+            Kind::Click => "click",
+        }
+    }
+}
+
+impl WebEvent {
+    pub fn kind(&self) -> Kind {
+        match *self {
+            WebEvent::PageLoad => Kind::PageLoad,
+            WebEvent::PageUnload => Kind::PageUnload,
+            WebEvent::KeyPress(_) => Kind::KeyPress,
+            WebEvent::Paste(_) => Kind::Paste,
+            WebEvent::Click { .. } => Kind::Click,
+        }
+    }
+}
+
+// An event and when it happened, from the start of the session
+#[derive(Debug, Clone, PartialEq)]
+pub struct Stamped {
+    pub at: Duration,
+    pub event: WebEvent,
+}
+
+// A line of the text log, which parse_log reads back
+impl fmt::Display for Stamped {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?} {}", self.at.as_secs_f64(), self.event.kind().name())?;
+        match self.event {
+            WebEvent::KeyPress(c) => write!(f, " {}", c),
+            WebEvent::Paste(ref s) => write!(f, " {}", s),
+            WebEvent::Click { x, y } => write!(f, " {} {}", x, y),
+            WebEvent::PageLoad | WebEvent::PageUnload => Ok(()),
+        }
+    }
+}
+
+// TEXT LOG
+#[derive(Debug, Clone, PartialEq)]
+pub enum LogErrorKind {
+    UnknownEvent(String),
+    MissingArgument { event: &'static str, argument: &'static str },
+    // e.g. a click coordinate that isn't an integer, or a key_press of more than one char
+    BadArgument { event: &'static str, argument: &'static str, found: String },
+    TrailingArguments(String),
+    BadTime(String),
+}
+
+impl fmt::Display for LogErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            LogErrorKind::UnknownEvent(ref name) => write!(f, "unknown event `{}`", name),
+            LogErrorKind::MissingArgument { event, argument } => write!(f, "{} is missing its {}", event, argument),
+            LogErrorKind::BadArgument { event, argument, ref found } =>
+                write!(f, "`{}` is not a valid {} for {}", found, argument, event),
+            LogErrorKind::TrailingArguments(ref rest) => write!(f, "unexpected `{}` after the event", rest),
+            LogErrorKind::BadTime(ref time) => write!(f, "`{}` is not a time in seconds", time),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct LogError {
+    // counting from 1
+    pub line: usize,
+    pub kind: LogErrorKind,
+}
+
+impl fmt::Display for LogError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.kind)
+    }
+}
+
+impl error::Error for LogError {}
+
+// A line without its time; None for a blank line or a comment
+pub fn parse_event(line: &str) -> Result<Option<WebEvent>, LogErrorKind> {
+    parse_line(line, Duration::from_secs(0)).map(|stamped| stamped.map(|stamped| stamped.event))
+}
+
+// `previous` is the time an event without one gets
+fn parse_line(line: &str, previous: Duration) -> Result<Option<Stamped>, LogErrorKind> {
+    let line = line.trim_start();
+    if line.trim().is_empty() || line.starts_with('#') {
+        return Ok(None);
+    }
+
+    let (mut name, mut rest) = split_word(line);
+    let mut at = previous;
+    if name.starts_with(|c: char| c.is_ascii_digit()) {
+        at = name.parse::<f64>().ok()
+            .and_then(|secs| Duration::try_from_secs_f64(secs).ok())
+            .ok_or_else(|| LogErrorKind::BadTime(name.to_owned()))?;
+        let (next, after) = split_word(rest);
+        name = next;
+        rest = after;
+    }
+
+    let event = match name {
+        "page_load" | "page_unload" => {
+            if !rest.trim().is_empty() {
+                return Err(LogErrorKind::TrailingArguments(rest.trim().to_owned()));
+            }
+            if name == "page_load" { WebEvent::PageLoad } else { WebEvent::PageUnload }
+        },
+        // the rest of the line is the char, so `key_press  ` presses the space bar
+        "key_press" => {
+            let mut chars = rest.chars();
+            match (chars.next(), chars.next()) {
+                (Some(c), None) => WebEvent::KeyPress(c),
+                (None, _) => return Err(LogErrorKind::MissingArgument { event: "key_press", argument: "key" }),
+                (Some(_), Some(_)) => return Err(LogErrorKind::BadArgument {
+                    event: "key_press",
+                    argument: "key",
+                    found: rest.to_owned(),
+                }),
+            }
+        },
+        // the text is everything after the space, spaces included
+        "paste" => WebEvent::Paste(rest.to_owned()),
+        "click" => {
+            let (x, rest) = split_word(rest);
+            let (y, rest) = split_word(rest);
+            if !rest.trim().is_empty() {
+                return Err(LogErrorKind::TrailingArguments(rest.trim().to_owned()));
+            }
+            WebEvent::Click { x: coordinate(x, "x")?, y: coordinate(y, "y")? }
+        },
+        _ => return Err(LogErrorKind::UnknownEvent(name.to_owned())),
+    };
+    Ok(Some(Stamped { at, event }))
+}
+
+// the first word and what's after the one space that ends it
+fn split_word(text: &str) -> (&str, &str) {
+    let text = text.trim_start_matches(' ');
+    match text.find(' ') {
+        Some(end) => (&text[..end], &text[end + 1..]),
+        None => (text, ""),
+    }
+}
+
+fn coordinate(text: &str, argument: &'static str) -> Result<i64, LogErrorKind> {
+    if text.is_empty() {
+        return Err(LogErrorKind::MissingArgument { event: "click", argument });
+    }
+    text.parse().map_err(|_| LogErrorKind::BadArgument { event: "click", argument, found: text.to_owned() })
+}
+
+pub fn parse_log(text: &str) -> Result<Vec<Stamped>, LogError> {
+    let mut events = Vec::new();
+    let mut previous = Duration::from_secs(0);
+    for (index, line) in text.lines().enumerate() {
+        let stamped = parse_line(line, previous).map_err(|kind| LogError { line: index + 1, kind })?;
+        if let Some(stamped) = stamped {
+            previous = stamped.at;
+            events.push(stamped);
+        }
+    }
+    Ok(events)
+}
+
+// STATS
+#[derive(Debug, Clone, PartialEq)]
+pub struct Stats {
+    pub counts: BTreeMap<Kind, usize>,
+    // clicks are counted in squares of this many pixels
+    pub bucket_size: i64,
+    // the number of clicks in each square, keyed by the square's column and row: a click at (25, 80) is in (2, 8)
+    // with a bucket size of 10
+    pub heatmap: BTreeMap<(i64, i64), usize>,
+    // when the first and last key presses happened
+    pub keys: Option<(Duration, Duration)>,
+    // the length of every paste, in chars
+    pub paste_sizes: Running<usize>,
+}
+
+impl Stats {
+    // panics unless the bucket size is at least 1, since clicks are divided by it
+    pub fn new(bucket_size: i64) -> Stats {
+        assert!(bucket_size >= 1, "the bucket size has to be at least 1 pixel, not {}", bucket_size);
+        Stats {
+            counts: BTreeMap::new(),
+            bucket_size,
+            heatmap: BTreeMap::new(),
+            keys: None,
+            paste_sizes: Running::new(),
+        }
+    }
+
+    pub fn count(&self, kind: Kind) -> usize {
+        self.counts.get(&kind).cloned().unwrap_or(0)
+    }
+
+    pub fn record(&mut self, stamped: &Stamped) {
+        *self.counts.entry(stamped.event.kind()).or_insert(0) += 1;
+        match stamped.event {
+            WebEvent::Click { x, y } => {
+                let bucket = (x.div_euclid(self.bucket_size), y.div_euclid(self.bucket_size));
+                *self.heatmap.entry(bucket).or_insert(0) += 1;
+            },
+            WebEvent::KeyPress(_) => {
+                let first = self.keys.map_or(stamped.at, |(first, _)| first);
+                self.keys = Some((first, stamped.at));
+            },
+            WebEvent::Paste(ref s) => self.paste_sizes.push(s.chars().count()),
+            WebEvent::PageLoad | WebEvent::PageUnload => {},
+        }
+    }
+
+    // the presses after the first one over the time from the first to the last; None until two presses with time
+    // between them
+    pub fn keys_per_second(&self) -> Option<f64> {
+        let (first, last) = self.keys?;
+        let span = last.checked_sub(first)?.as_secs_f64();
+        if span > 0.0 { Some((self.count(Kind::KeyPress) - 1) as f64 / span) } else { None }
+    }
+
+    // the n squares with the most clicks, most first
+    pub fn hottest(&self, n: usize) -> Vec<((i64, i64), usize)> {
+        let mut buckets: Vec<_> = self.heatmap.iter().map(|(&bucket, &count)| (bucket, count)).collect();
+        // a stable sort keeps equally hot squares in column and row order
+        buckets.sort_by_key(|&(_, count)| ::std::cmp::Reverse(count));
+        buckets.truncate(n);
+        buckets
+    }
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let counts: Vec<String> = Kind::ALL.iter().map(|&kind| format!("{} {}", kind.name(), self.count(kind))).collect();
+        writeln!(f, "events: {}", counts.join(", "))?;
+        let hottest: Vec<String> = self.hottest(3).iter()
+            .map(|&((column, row), count)| {
+                // a square's edges can be past the i64 range for clicks near its ends
+                let size = self.bucket_size as i128;
+                let (x, y) = (column as i128 * size, row as i128 * size);
+                format!("({}..{}, {}..{}) {}", x, x + size, y, y + size, count)
+            })
+            .collect();
+        writeln!(f, "hottest click squares: {}", if hottest.is_empty() { "none".to_owned() } else { hottest.join(", ") })?;
+        match self.keys_per_second() {
+            Some(rate) => writeln!(f, "keys per second: {:.2}", rate)?,
+            None => writeln!(f, "keys per second: n/a")?,
+        }
+        match (self.paste_sizes.mean(), self.paste_sizes.max()) {
+            (Ok(mean), Some(max)) => write!(f, "paste sizes: mean {:.1}, max {}", mean, max),
+            _ => write!(f, "paste sizes: n/a"),
+        }
+    }
+}
+
+// PIPELINE
+// a filter or transformer; None drops the event
+type Stage<'a> = Box<dyn FnMut(Stamped) -> Option<Stamped> + 'a>;
+type Handler<'a> = Box<dyn FnMut(&Stamped) + 'a>;
+
+pub struct Pipeline<'a> {
+    // in the order they were added
+    stages: Vec<Stage<'a>>,
+    // None subscribes to every kind
+    handlers: Vec<(Option<Kind>, Handler<'a>)>,
+    pub stats: Stats,
+}
+
+impl<'a> Default for Pipeline<'a> {
+    fn default() -> Pipeline<'a> {
+        Pipeline::new()
+    }
+}
+
+impl<'a> Pipeline<'a> {
+    // clicks go in squares of 10 by 10 pixels
+    pub fn new() -> Pipeline<'a> {
+        Pipeline { stages: Vec::new(), handlers: Vec::new(), stats: Stats::new(10) }
+    }
+
+    // panics unless it's at least 1, like Stats::new
+    pub fn bucket_size(mut self, bucket_size: i64) -> Pipeline<'a> {
+        self.stats = Stats::new(bucket_size);
+        self
+    }
+
+    // keeps only the events the predicate is true for
+    pub fn filter<F: FnMut(&Stamped) -> bool + 'a>(mut self, mut predicate: F) -> Pipeline<'a> {
+        self.stages.push(Box::new(move |stamped| if predicate(&stamped) { Some(stamped) } else { None }));
+        self
+    }
+
+    // replaces each event with what the function makes of it
+    pub fn map<F: FnMut(Stamped) -> Stamped + 'a>(mut self, mut transform: F) -> Pipeline<'a> {
+        self.stages.push(Box::new(move |stamped| Some(transform(stamped))));
+        self
+    }
+
+    // a transformer that can also drop an event by returning None
+    pub fn filter_map<F: FnMut(Stamped) -> Option<Stamped> + 'a>(mut self, transform: F) -> Pipeline<'a> {
+        self.stages.push(Box::new(transform));
+        self
+    }
+
+    pub fn subscribe<F: FnMut(&Stamped) + 'a>(mut self, kind: Kind, handler: F) -> Pipeline<'a> {
+        self.handlers.push((Some(kind), Box::new(handler)));
+        self
+    }
+
+    pub fn subscribe_all<F: FnMut(&Stamped) + 'a>(mut self, handler: F) -> Pipeline<'a> {
+        self.handlers.push((None, Box::new(handler)));
+        self
+    }
+
+    // returns whether the event made it through the stages
+    pub fn push(&mut self, stamped: Stamped) -> bool {
+        let mut stamped = stamped;
+        for stage in self.stages.iter_mut() {
+            match stage(stamped) {
+                Some(next) => stamped = next,
+                None => return false,
+            }
+        }
+        self.stats.record(&stamped);
+        let kind = stamped.event.kind();
+        for (subscribed, handler) in &mut self.handlers {
+            if subscribed.is_none_or(|subscribed| subscribed == kind) {
+                handler(&stamped);
+            }
+        }
+        true
+    }
+
+    // Pushes every event of a text log, stopping at the first bad line; returns how many made it through
+    pub fn ingest(&mut self, log: &str) -> Result<usize, LogError> {
+        let mut delivered = 0;
+        let mut previous = Duration::from_secs(0);
+        for (index, line) in log.lines().enumerate() {
+            let stamped = parse_line(line, previous).map_err(|kind| LogError { line: index + 1, kind })?;
+            if let Some(stamped) = stamped {
+                previous = stamped.at;
+                if self.push(stamped) {
+                    delivered += 1;
+                }
+            }
+        }
+        Ok(delivered)
+    }
+}
+
+// A pipeline reading a text log: page unloads are dropped, clicks also go to a subscriber
+pub fn run(out: &mut impl Write) -> io::Result<()> {
+    let mut clicks = Vec::new();
+    let stats = {
+        let mut pipeline = Pipeline::new()
+            .filter(|stamped| stamped.event != WebEvent::PageUnload)
+            .subscribe(Kind::Click, |stamped| clicks.push(stamped.event.clone()));
+        if let Err(e) = pipeline.ingest("0 page_load\n0.5 click 20 80\n1 key_press x\n2 key_press y\npaste my text\nclick 25 81\n") {
+            writeln!(out, "bad log: {}", e)?;
+        }
+        pipeline.stats
+    };
+    writeln!(out, "subscribed to clicks: {:?}", clicks)?;
+    writeln!(out, "{}", stats)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::cell::RefCell;
+
+    fn at(millis: u64, event: WebEvent) -> Stamped {
+        Stamped { at: Duration::from_millis(millis), event }
+    }
+
+    #[test]
+    fn reads_a_log() {
+        let log = "# a session\n0 page_load\n1.5 click 20 80\nkey_press x\nkey_press  \n2.25 paste my  text\npage_unload\n";
+        assert_eq!(parse_log(log).unwrap(), vec![
+            at(0, WebEvent::PageLoad),
+            at(1500, WebEvent::Click { x: 20, y: 80 }),
+            at(1500, WebEvent::KeyPress('x')),
+            at(1500, WebEvent::KeyPress(' ')),
+            at(2250, WebEvent::Paste("my  text".to_owned())),
+            at(2250, WebEvent::PageUnload),
+        ]);
+        for stamped in parse_log(log).unwrap() {
+            assert_eq!(parse_log(&stamped.to_string()).unwrap(), vec![stamped.clone()], "{}", stamped);
+        }
+    }
+
+    #[test]
+    fn log_errors() {
+        let error = |log: &str| parse_log(log).unwrap_err();
+        assert_eq!(error("page_load\nscroll 5"), LogError { line: 2, kind: LogErrorKind::UnknownEvent("scroll".to_owned()) });
+        assert_eq!(error("click 20").kind, LogErrorKind::MissingArgument { event: "click", argument: "y" });
+        assert_eq!(error("click 20 eighty").kind,
+                   LogErrorKind::BadArgument { event: "click", argument: "y", found: "eighty".to_owned() });
+        assert_eq!(error("click 1 2 3").kind, LogErrorKind::TrailingArguments("3".to_owned()));
+        assert_eq!(error("key_press").kind, LogErrorKind::MissingArgument { event: "key_press", argument: "key" });
+        assert_eq!(error("key_press xy").kind,
+                   LogErrorKind::BadArgument { event: "key_press", argument: "key", found: "xy".to_owned() });
+        assert_eq!(error("page_load now").kind, LogErrorKind::TrailingArguments("now".to_owned()));
+        assert_eq!(error("1.2.3 page_load").to_string(), "line 1: `1.2.3` is not a time in seconds");
+        assert_eq!(parse_event("key_press q"), Ok(Some(WebEvent::KeyPress('q'))));
+        assert_eq!(parse_event("   "), Ok(None));
+    }
+
+    #[test]
+    fn stages_and_handlers() {
+        let clicks = RefCell::new(Vec::new());
+        let everything = RefCell::new(0);
+        let mut pipeline = Pipeline::new()
+            // drop clicks outside a 100 by 100 page
+            .filter(|stamped| match stamped.event {
+                WebEvent::Click { x, y } => (0..100).contains(&x) && (0..100).contains(&y),
+                _ => true,
+            })
+            // upper-case every key
+            .map(|stamped| match stamped.event {
+                WebEvent::KeyPress(c) => Stamped { event: WebEvent::KeyPress(c.to_ascii_uppercase()), ..stamped },
+                _ => stamped,
+            })
+            .filter_map(|stamped| if stamped.event == WebEvent::PageUnload { None } else { Some(stamped) })
+            .subscribe(Kind::Click, |stamped| clicks.borrow_mut().push(stamped.event.clone()))
+            .subscribe_all(|_| *everything.borrow_mut() += 1);
+
+        assert!(pipeline.push(at(0, WebEvent::Click { x: 5, y: 5 })));
+        assert!(!pipeline.push(at(0, WebEvent::Click { x: 500, y: 5 })));
+        assert!(pipeline.push(at(0, WebEvent::KeyPress('a'))));
+        assert_eq!(pipeline.ingest("page_load\nclick 1 2\npage_unload\n"), Ok(2));
+        assert_eq!(pipeline.stats.count(Kind::KeyPress), 1);
+        assert_eq!(pipeline.stats.count(Kind::PageUnload), 0);
+        drop(pipeline);
+
+        assert_eq!(*clicks.borrow(), vec![WebEvent::Click { x: 5, y: 5 }, WebEvent::Click { x: 1, y: 2 }]);
+        assert_eq!(*everything.borrow(), 4);
+    }
+
+    #[test]
+    fn stats() {
+        let mut pipeline = Pipeline::new().bucket_size(50);
+        pipeline.ingest("\
+0 click 10 10
+click 49 0
+click 50 0
+click -1 -1
+1 key_press a
+key_press b
+3 key_press c
+paste hello
+paste héllo, world
+").unwrap();
+        let stats = &pipeline.stats;
+        assert_eq!(stats.heatmap.get(&(0, 0)), Some(&2));
+        assert_eq!(stats.heatmap.get(&(1, 0)), Some(&1));
+        assert_eq!(stats.heatmap.get(&(-1, -1)), Some(&1));
+        assert_eq!(stats.hottest(1), vec![((0, 0), 2)]);
+        assert_eq!(stats.keys_per_second(), Some(1.0));
+        assert_eq!(stats.paste_sizes.count(), 2);
+        assert_eq!(stats.paste_sizes.max(), Some(12));
+        assert_eq!(stats.to_string(), "\
+events: page_load 0, page_unload 0, key_press 3, paste 2, click 4
+hottest click squares: (0..50, 0..50) 2, (-50..0, -50..0) 1, (50..100, 0..50) 1
+keys per second: 1.00
+paste sizes: mean 8.5, max 12");
+        assert_eq!(Stats::new(10).keys_per_second(), None);
+    }
+
+    #[test]
+    fn extreme_clicks() {
+        let mut pipeline = Pipeline::new().bucket_size(10);
+        pipeline.ingest("click 9223372036854775807 0
+click -9223372036854775808 -9223372036854775808
+").unwrap();
+        assert_eq!(pipeline.stats.to_string().lines().nth(1), Some("hottest click squares: \
+(-9223372036854775810..-9223372036854775800, -9223372036854775810..-9223372036854775800) 1, \
+(9223372036854775800..9223372036854775810, 0..10) 1"));
+
+        let mut stats = Stats::new(i64::MAX);
+        stats.record(&Stamped { at: Duration::default(), event: WebEvent::Click { x: i64::MAX, y: i64::MIN } });
+        assert!(stats.to_string().contains("(9223372036854775807..18446744073709551614, \
+-18446744073709551614..-9223372036854775807) 1"));
+    }
+
+    #[test]
+    #[should_panic(expected = "the bucket size has to be at least 1 pixel, not 0")]
+    fn zero_bucket_size() {
+        Pipeline::new().bucket_size(0);
+    }
+
+    #[test]
+    #[should_panic(expected = "not -10")]
+    fn negative_bucket_size() {
+        Stats::new(-10);
+    }
+}
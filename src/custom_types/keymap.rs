@@ -0,0 +1,571 @@
+// KEYMAPS
+// A KeyPress is one key with no notion of what came before it. A Keymap binds sequences of keys to commands, the
+// way vim binds `gg` and `dd` and emacs binds `C-x C-s`, and a Recognizer turns a stream of key presses into the
+// commands they spell.
+//
+// Keys are written as plain chars, `C-x` for control and x (the control char a terminal sends for it), or one of
+// SPC TAB RET ESC DEL. Runs of plain chars are one key per char, so `gg` is two keys and `C-x b` is two keys.
+//
+// A keymap file binds keys to commands for each mode:
+//
+//     # comments start with #, or ` #` after a binding
+//     [normal]
+//     gg = goto_top
+//     g = goto_line          # a prefix of gg: fires when nothing follows the g within the timeout
+//     dd = delete_line
+//     C-x C-s = save
+//     i = insert_mode
+//
+//     [insert]
+//     ESC = normal_mode
+//
+// Binding the same keys twice, or binding a prefix of another binding, isn't an error, but `Keymaps::load` reports
+// both as conflicts.
+
+use std::collections::BTreeMap;
+use std::error;
+use std::fmt;
+use std::io::{self, Write};
+use std::time::Duration;
+
+use custom_types::WebEvent;
+use custom_types::events::{Kind, Pipeline, Stamped};
+
+// KEYS
+const NAMED: [(&str, char); 5] = [("SPC", ' '), ("TAB", '\t'), ("RET", '\r'), ("ESC", '\u{1b}'), ("DEL", '\u{7f}')];
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum KeyError {
+    Empty,
+    // a `C-` that isn't followed by a char with a control code
+    UnknownKey(String),
+}
+
+impl fmt::Display for KeyError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            KeyError::Empty => write!(f, "no keys"),
+            KeyError::UnknownKey(ref key) => write!(f, "`{}` is not a key", key),
+        }
+    }
+}
+
+impl error::Error for KeyError {}
+
+// `"C-x C-s"` is `['\u{18}', '\u{13}']`
+pub fn parse_keys(notation: &str) -> Result<Vec<char>, KeyError> {
+    let mut keys = Vec::new();
+    for word in notation.split_whitespace() {
+        if let Some(&(_, key)) = NAMED.iter().find(|&&(name, _)| name == word) {
+            keys.push(key);
+        } else if let Some(rest) = word.strip_prefix("C-") {
+            let mut chars = rest.chars();
+            match (chars.next().map(|c| c.to_ascii_uppercase()), chars.next()) {
+                // `C-@` is 0 and `C-a` is 1, up to `C-_`
+                (Some(c @ '@'..='_'), None) => keys.push((c as u8 - b'@') as char),
+                _ => return Err(KeyError::UnknownKey(word.to_owned())),
+            }
+        } else {
+            keys.extend(word.chars());
+        }
+    }
+    if keys.is_empty() { Err(KeyError::Empty) } else { Ok(keys) }
+}
+
+// what parse_keys reads back
+pub fn key_names(keys: &[char]) -> String {
+    let mut names = String::new();
+    let mut plain = false;
+    for &key in keys {
+        let name = match NAMED.iter().find(|&&(_, named)| named == key) {
+            Some(&(name, _)) => Some(name.to_owned()),
+            None if (key as u32) < 0x20 => Some(format!("C-{}", ((key as u8 + b'@') as char).to_ascii_lowercase())),
+            None => None,
+        };
+        // plain keys run together, anything else is a word of its own
+        let run_together = plain && name.is_none();
+        if !run_together && !names.is_empty() {
+            names.push(' ');
+        }
+        plain = name.is_none();
+        match name {
+            Some(name) => names.push_str(&name),
+            None => names.push(key),
+        }
+    }
+    names
+}
+
+// KEYMAP
+#[derive(Debug, Clone, PartialEq)]
+struct Node<C> {
+    command: Option<C>,
+    children: BTreeMap<char, Node<C>>,
+}
+
+impl<C> Node<C> {
+    fn new() -> Node<C> {
+        Node { command: None, children: BTreeMap::new() }
+    }
+
+    fn find(&self, keys: &[char]) -> Option<&Node<C>> {
+        keys.iter().try_fold(self, |node, key| node.children.get(key))
+    }
+
+    // every binding at or below this node, each before the longer ones it's a prefix of
+    fn collect<'a>(&'a self, path: &mut Vec<char>, bindings: &mut Vec<(Vec<char>, &'a C)>) {
+        if let Some(ref command) = self.command {
+            bindings.push((path.clone(), command));
+        }
+        for (&key, child) in &self.children {
+            path.push(key);
+            child.collect(path, bindings);
+            path.pop();
+        }
+    }
+}
+
+// A trie of key sequences, each node being the keys typed so far
+#[derive(Debug, Clone, PartialEq)]
+pub struct Keymap<C> {
+    root: Node<C>,
+}
+
+impl<C> Default for Keymap<C> {
+    fn default() -> Keymap<C> {
+        Keymap::new()
+    }
+}
+
+impl<C> Keymap<C> {
+    pub fn new() -> Keymap<C> {
+        Keymap { root: Node::new() }
+    }
+
+    // Binds the keys in notation to the command, returning the command they were bound to before
+    pub fn bind(&mut self, keys: &str, command: C) -> Result<Option<C>, KeyError> {
+        Ok(self.insert(&parse_keys(keys)?, command))
+    }
+
+    fn insert(&mut self, keys: &[char], command: C) -> Option<C> {
+        let node = keys.iter().fold(&mut self.root, |node, &key| node.children.entry(key).or_insert_with(Node::new));
+        node.command.replace(command)
+    }
+
+    // the command bound to exactly these keys
+    pub fn get(&self, keys: &[char]) -> Option<&C> {
+        self.root.find(keys)?.command.as_ref()
+    }
+
+    // whether a longer binding starts with these keys
+    pub fn is_prefix(&self, keys: &[char]) -> bool {
+        self.root.find(keys).is_some_and(|node| !node.children.is_empty())
+    }
+
+    // every binding, in key order
+    pub fn bindings(&self) -> Vec<(Vec<char>, &C)> {
+        let mut bindings = Vec::new();
+        self.root.collect(&mut Vec::new(), &mut bindings);
+        bindings
+    }
+
+    // Each binding that's a prefix of a longer one, with the first of those in key order
+    pub fn prefixes(&self) -> Vec<(Vec<char>, Vec<char>)> {
+        // a binding's longer ones come right after it in `bindings`
+        self.bindings().windows(2)
+            .filter(|pair| pair[1].0.starts_with(&pair[0].0))
+            .map(|pair| (pair[0].0.clone(), pair[1].0.clone()))
+            .collect()
+    }
+}
+
+// CONFLICTS
+#[derive(Debug, Clone, PartialEq)]
+pub enum Conflict {
+    // the same keys bound twice in a mode; the later binding wins
+    Rebound { mode: String, keys: String, first: usize, second: usize },
+    // keys bound to a command that are also the start of a longer binding, so the command only fires after the
+    // timeout or when a key that continues neither is typed
+    Prefix { mode: String, keys: String, longer: String },
+}
+
+impl fmt::Display for Conflict {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Conflict::Rebound { ref mode, ref keys, first, second } =>
+                write!(f, "[{}] `{}` is bound on line {} and again on line {}, which wins", mode, keys, first, second),
+            Conflict::Prefix { ref mode, ref keys, ref longer } =>
+                write!(f, "[{}] `{}` is a prefix of `{}`, so it waits for the timeout", mode, keys, longer),
+        }
+    }
+}
+
+// LOADING
+#[derive(Debug, Clone, PartialEq)]
+pub enum LoadErrorKind {
+    MissingEquals,
+    EmptyCommand,
+    UnclosedSection,
+    // a binding before the first [mode]
+    NoMode,
+    BadKeys(KeyError),
+}
+
+impl fmt::Display for LoadErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            LoadErrorKind::MissingEquals => write!(f, "expected `keys = command`"),
+            LoadErrorKind::EmptyCommand => write!(f, "the command is empty"),
+            LoadErrorKind::UnclosedSection => write!(f, "the mode is missing its `]`"),
+            LoadErrorKind::NoMode => write!(f, "a binding before any [mode]"),
+            LoadErrorKind::BadKeys(ref e) => write!(f, "{}", e),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct LoadError {
+    // counting from 1
+    pub line: usize,
+    pub kind: LoadErrorKind,
+}
+
+impl fmt::Display for LoadError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.kind)
+    }
+}
+
+impl error::Error for LoadError {}
+
+// A Keymap for each mode, by name
+#[derive(Debug, Clone, PartialEq)]
+pub struct Keymaps<C> {
+    pub modes: BTreeMap<String, Keymap<C>>,
+}
+
+impl<C> Default for Keymaps<C> {
+    fn default() -> Keymaps<C> {
+        Keymaps::new()
+    }
+}
+
+impl<C> Keymaps<C> {
+    pub fn new() -> Keymaps<C> {
+        Keymaps { modes: BTreeMap::new() }
+    }
+
+    pub fn mode(&self, mode: &str) -> Option<&Keymap<C>> {
+        self.modes.get(mode)
+    }
+
+    // the mode's keymap, made empty if there isn't one yet
+    pub fn mode_mut(&mut self, mode: &str) -> &mut Keymap<C> {
+        self.modes.entry(mode.to_owned()).or_default()
+    }
+
+    // the prefix conflicts of every mode
+    pub fn conflicts(&self) -> Vec<Conflict> {
+        self.modes.iter()
+            .flat_map(|(mode, keymap)| keymap.prefixes().into_iter().map(move |(keys, longer)| Conflict::Prefix {
+                mode: mode.clone(),
+                keys: key_names(&keys),
+                longer: key_names(&longer),
+            }))
+            .collect()
+    }
+}
+
+impl Keymaps<String> {
+    // Reads a keymap file, with the conflicts in it: every rebinding in the order they come, then every prefix
+    pub fn load(text: &str) -> Result<(Keymaps<String>, Vec<Conflict>), LoadError> {
+        let mut keymaps = Keymaps::new();
+        let mut conflicts = Vec::new();
+        let mut mode: Option<String> = None;
+        // the line each mode's keys were last bound on
+        let mut lines: BTreeMap<(String, Vec<char>), usize> = BTreeMap::new();
+        for (index, line) in text.lines().enumerate() {
+            let line_number = index + 1;
+            let error = |kind| LoadError { line: line_number, kind };
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            if let Some(header) = line.strip_prefix('[') {
+                let end = header.find(']').ok_or_else(|| error(LoadErrorKind::UnclosedSection))?;
+                mode = Some(header[..end].trim().to_owned());
+                continue;
+            }
+
+            let mode = mode.as_ref().ok_or_else(|| error(LoadErrorKind::NoMode))?;
+            // a command never has an `=` in it, but the keys can
+            let line = line.split(" #").next().unwrap_or(line);
+            let equals = line.rfind('=').ok_or_else(|| error(LoadErrorKind::MissingEquals))?;
+            let command = line[equals + 1..].trim();
+            if command.is_empty() {
+                return Err(error(LoadErrorKind::EmptyCommand));
+            }
+            let keys = parse_keys(&line[..equals]).map_err(|e| error(LoadErrorKind::BadKeys(e)))?;
+            keymaps.mode_mut(mode).insert(&keys, command.to_owned());
+            if let Some(first) = lines.insert((mode.clone(), keys.clone()), line_number) {
+                conflicts.push(Conflict::Rebound { mode: mode.clone(), keys: key_names(&keys), first, second: line_number });
+            }
+        }
+        conflicts.extend(keymaps.conflicts());
+        Ok((keymaps, conflicts))
+    }
+}
+
+// RECOGNIZING
+#[derive(Debug, Clone, PartialEq)]
+pub enum Action<C> {
+    Command(C),
+    // a key that starts no binding, for the caller to insert as text, say
+    Unbound(char),
+}
+
+pub struct Recognizer<'a, C: 'a> {
+    keymaps: &'a Keymaps<C>,
+    mode: String,
+    // how long to wait for the next key of a sequence that could go on
+    timeout: Duration,
+    // the keys typed so far that don't make a command yet
+    pending: Vec<char>,
+    // when the last of them was typed
+    last: Option<Duration>,
+}
+
+impl<'a, C: Clone> Recognizer<'a, C> {
+    // starts in the mode, with vim's default timeout of a second
+    pub fn new(keymaps: &'a Keymaps<C>, mode: &str) -> Recognizer<'a, C> {
+        Recognizer { keymaps, mode: mode.to_owned(), timeout: Duration::from_secs(1), pending: Vec::new(), last: None }
+    }
+
+    pub fn timeout(mut self, timeout: Duration) -> Recognizer<'a, C> {
+        self.timeout = timeout;
+        self
+    }
+
+    pub fn mode(&self) -> &str {
+        &self.mode
+    }
+
+    // Switches to the mode, forgetting any pending keys
+    pub fn set_mode(&mut self, mode: &str) {
+        self.mode = mode.to_owned();
+        self.pending.clear();
+        self.last = None;
+    }
+
+    pub fn pending(&self) -> &[char] {
+        &self.pending
+    }
+
+    // Feeds a key press, first timing out the pending keys if it came too late for them. Every other event is
+    // ignored.
+    pub fn push(&mut self, stamped: &Stamped) -> Vec<Action<C>> {
+        let key = match stamped.event {
+            WebEvent::KeyPress(key) => key,
+            _ => return Vec::new(),
+        };
+        let mut actions = self.tick(stamped.at);
+        self.pending.push(key);
+        self.last = Some(stamped.at);
+        self.resolve(false, &mut actions);
+        actions
+    }
+
+    // Times out the pending keys if the last one was typed at least the timeout before `now`
+    pub fn tick(&mut self, now: Duration) -> Vec<Action<C>> {
+        let mut actions = Vec::new();
+        if self.last.is_some_and(|last| now.saturating_sub(last) >= self.timeout) {
+            self.resolve(true, &mut actions);
+        }
+        actions
+    }
+
+    // Resolves the pending keys as if the timeout had passed
+    pub fn flush(&mut self) -> Vec<Action<C>> {
+        let mut actions = Vec::new();
+        self.resolve(true, &mut actions);
+        actions
+    }
+
+    // Turns as many pending keys into actions as it can; `done` means no more keys are coming for them. When the
+    // keys start no binding, the longest bound prefix of them fires (or the first key is unbound) and the rest
+    // are tried again.
+    fn resolve(&mut self, done: bool, actions: &mut Vec<Action<C>>) {
+        let keymap = match self.keymaps.mode(&self.mode) {
+            Some(keymap) => keymap,
+            None => {
+                actions.extend(self.pending.drain(..).map(Action::Unbound));
+                self.last = None;
+                return;
+            },
+        };
+        while !self.pending.is_empty() {
+            if !done && keymap.is_prefix(&self.pending) {
+                return;
+            }
+            match (1..=self.pending.len()).rev().find_map(|n| keymap.get(&self.pending[..n]).map(|command| (n, command))) {
+                Some((n, command)) => {
+                    actions.push(Action::Command(command.clone()));
+                    self.pending.drain(..n);
+                },
+                None => actions.push(Action::Unbound(self.pending.remove(0))),
+            }
+        }
+        self.last = None;
+    }
+}
+
+// Key presses from a text log, through a Pipeline, spelling out the commands of a vim and emacs style keymap
+pub fn run(out: &mut impl Write) -> io::Result<()> {
+    let (keymaps, conflicts) = match Keymaps::load("[normal]\ngg = goto_top\ng = goto_line\ndd = delete_line\nC-x C-s = save\n") {
+        Ok(loaded) => loaded,
+        Err(e) => return writeln!(out, "bad keymap: {}", e),
+    };
+    for conflict in &conflicts {
+        writeln!(out, "conflict: {}", conflict)?;
+    }
+    let mut recognizer = Recognizer::new(&keymaps, "normal");
+    let mut actions = Vec::new();
+    {
+        let mut pipeline = Pipeline::new().subscribe(Kind::KeyPress, |stamped| actions.extend(recognizer.push(stamped)));
+        let keys = "0 key_press g\n0.1 key_press g\n0.2 key_press d\n0.3 key_press d\n0.4 key_press x\n0.5 key_press g\n\
+                    2 key_press \u{18}\n2.1 key_press \u{13}\n";
+        if let Err(e) = pipeline.ingest(keys) {
+            writeln!(out, "bad log: {}", e)?;
+        }
+    }
+    actions.extend(recognizer.flush());
+    let actions: Vec<String> = actions.iter()
+        .map(|action| match *action {
+            Action::Command(ref command) => command.clone(),
+            Action::Unbound(key) => format!("{:?}", key),
+        })
+        .collect();
+    writeln!(out, "gg dd x g, a pause, then C-x C-s: {}", actions.join(", "))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn press(millis: u64, key: char) -> Stamped {
+        Stamped { at: Duration::from_millis(millis), event: WebEvent::KeyPress(key) }
+    }
+
+    fn command(name: &str) -> Action<String> {
+        Action::Command(name.to_owned())
+    }
+
+    const KEYMAP: &str = "\
+# vim and emacs, together at last
+[normal]
+gg = goto_top
+g = goto_line   # waits for a second g
+dd = delete_line
+C-x C-s = save
+C-x C-c = quit
+i = insert_mode
+dd = cut_line
+
+[insert]
+ESC = normal_mode
+C-w = delete_word
+";
+
+    #[test]
+    fn notation() {
+        assert_eq!(parse_keys("gg"), Ok(vec!['g', 'g']));
+        assert_eq!(parse_keys(" C-x  C-s "), Ok(vec!['\u{18}', '\u{13}']));
+        assert_eq!(parse_keys("C-x b SPC ESC C-@ C-_"), Ok(vec!['\u{18}', 'b', ' ', '\u{1b}', '\u{0}', '\u{1f}']));
+        assert_eq!(parse_keys("C-é"), Err(KeyError::UnknownKey("C-é".to_owned())));
+        assert_eq!(parse_keys("C-xy"), Err(KeyError::UnknownKey("C-xy".to_owned())));
+        assert_eq!(parse_keys("  "), Err(KeyError::Empty));
+        for notation in &["gg", "C-x C-s", "C-x b", "dd C-w ESC", "a SPC b TAB RET DEL", "C-@ C-\\ C-_"] {
+            assert_eq!(key_names(&parse_keys(notation).unwrap()), *notation);
+        }
+    }
+
+    #[test]
+    fn load() {
+        let (keymaps, conflicts) = Keymaps::load(KEYMAP).unwrap();
+        let normal = keymaps.mode("normal").unwrap();
+        assert_eq!(normal.get(&['g', 'g']), Some(&"goto_top".to_owned()));
+        assert_eq!(normal.get(&['d', 'd']), Some(&"cut_line".to_owned()));
+        assert_eq!(normal.get(&['\u{18}']), None);
+        assert!(normal.is_prefix(&['\u{18}']));
+        assert_eq!(normal.bindings().len(), 6);
+        assert_eq!(conflicts.iter().map(|c| c.to_string()).collect::<Vec<_>>(), vec![
+            "[normal] `dd` is bound on line 5 and again on line 9, which wins",
+            "[normal] `g` is a prefix of `gg`, so it waits for the timeout",
+        ]);
+
+        let error = |text: &str| Keymaps::load(text).unwrap_err();
+        assert_eq!(error("gg = top"), LoadError { line: 1, kind: LoadErrorKind::NoMode });
+        assert_eq!(error("[normal\ngg = top").kind, LoadErrorKind::UnclosedSection);
+        assert_eq!(error("[normal]\n\ngg top").to_string(), "line 3: expected `keys = command`");
+        assert_eq!(error("[normal]\ngg =").kind, LoadErrorKind::EmptyCommand);
+        assert_eq!(error("[normal]\nC-1 = x").kind, LoadErrorKind::BadKeys(KeyError::UnknownKey("C-1".to_owned())));
+        // `=` can be bound, as the command comes after the last one
+        assert_eq!(Keymaps::load("[normal]\n== = indent").unwrap().0.mode("normal").unwrap().get(&['=', '=']),
+                   Some(&"indent".to_owned()));
+    }
+
+    #[test]
+    fn sequences() {
+        let (keymaps, _) = Keymaps::load(KEYMAP).unwrap();
+        let mut recognizer = Recognizer::new(&keymaps, "normal").timeout(Duration::from_millis(500));
+        let mut typed = |millis, key| recognizer.push(&press(millis, key));
+        assert_eq!(typed(0, 'd'), vec![]);
+        assert_eq!(typed(100, 'd'), vec![command("cut_line")]);
+        assert_eq!(typed(200, '\u{18}'), vec![]);
+        assert_eq!(typed(300, '\u{13}'), vec![command("save")]);
+        assert_eq!(typed(400, 'g'), vec![]);
+        assert_eq!(typed(500, 'g'), vec![command("goto_top")]);
+        // a g on its own waits, then fires once the next key is too late to make a gg...
+        assert_eq!(typed(1000, 'g'), vec![]);
+        assert_eq!(typed(1600, 'g'), vec![command("goto_line")]);
+        // ...or is one that can't follow it, which is then tried on its own
+        assert_eq!(typed(1700, 'i'), vec![command("goto_line"), command("insert_mode")]);
+        // unbound keys come back one by one, even after a prefix of a binding
+        assert_eq!(typed(1800, 'x'), vec![Action::Unbound('x')]);
+        assert_eq!(typed(1900, '\u{18}'), vec![]);
+        assert_eq!(typed(2000, 'q'), vec![Action::Unbound('\u{18}'), Action::Unbound('q')]);
+        assert_eq!(typed(2100, 'd'), vec![]);
+        assert_eq!(recognizer.pending(), &['d']);
+        assert_eq!(recognizer.tick(Duration::from_millis(2599)), vec![]);
+        assert_eq!(recognizer.tick(Duration::from_millis(2600)), vec![Action::Unbound('d')]);
+        assert_eq!(recognizer.push(&press(2700, 'g')), vec![]);
+        assert_eq!(recognizer.flush(), vec![command("goto_line")]);
+        assert_eq!(recognizer.push(&Stamped { at: Duration::from_secs(3), event: WebEvent::PageUnload }), vec![]);
+    }
+
+    #[test]
+    fn modes() {
+        let (keymaps, _) = Keymaps::load(KEYMAP).unwrap();
+        let mut recognizer = Recognizer::new(&keymaps, "normal");
+        let mut typed = String::new();
+        for (index, key) in "iab\u{17}c\u{1b}dd".chars().enumerate() {
+            for action in recognizer.push(&press(index as u64, key)) {
+                match action {
+                    Action::Command(command) => match command.as_str() {
+                        "insert_mode" => recognizer.set_mode("insert"),
+                        "normal_mode" => recognizer.set_mode("normal"),
+                        command => typed.push_str(&format!("<{}>", command)),
+                    },
+                    Action::Unbound(key) => typed.push(key),
+                }
+            }
+        }
+        assert_eq!(typed, "ab<delete_word>c<cut_line>");
+        assert_eq!(recognizer.mode(), "normal");
+
+        // a mode without a keymap binds nothing
+        recognizer.set_mode("visual");
+        assert_eq!(recognizer.push(&press(10, 'g')), vec![Action::Unbound('g')]);
+    }
+}
@@ -0,0 +1,478 @@
+// The use declaration can be used so that manual scoping isn't necessary
+
+use std::fmt;
+use std::io::{self, Write};
+use std::iter::FromIterator;
+use std::mem;
+
+use custom_types::PList;
+use custom_types::enums::{Status, Work};
+
+// A common use for enums is to create a linked list - https://doc.rust-lang.org/1.21.0/std/collections/struct.LinkedList.html
+// A linked list allows pushing and popping elements at either end at constant time
+// It is however, almost always better to use Vec or VecDeque instead of a linked list as
+// array based containers are faster, more memory efficient and make better use of the CPU cache
+use self::Link::*;
+enum Link<T> {
+    // Cons is a tuple struct that wraps an element and a pointer to the next node
+    Cons(T, Box<Link<T>>),
+    // Nil is a node that signifies the end of a linked list
+    Nil,
+}
+
+// The links, with their count kept alongside so `len` doesn't have to walk them.
+// Every walk over the links is a loop rather than a recursion, dropping included: the drop the compiler writes for
+// `Link` would recurse once per element and overflow the stack on a long enough list.
+pub struct List<T> {
+    head: Link<T>,
+    len: usize,
+}
+
+impl<T> List<T> {
+    // create an empty list
+    pub fn new() -> List<T> {
+        // `Nil` has a type Link
+        List { head: Nil, len: 0 }
+    }
+
+    // consume a list, and return the same list with a new element in its front
+    pub fn prepend(mut self, elem: T) -> List<T> {
+        self.push_front(elem);
+        self
+    }
+
+    pub fn push_front(&mut self, elem: T) {
+        // `Cons` also has a type Link
+        let tail = mem::replace(&mut self.head, Nil);
+        self.head = Cons(elem, Box::new(tail));
+        self.len += 1;
+    }
+
+    pub fn pop_front(&mut self) -> Option<T> {
+        // the head is moved out and Nil left in its place for as long as it takes to put the tail there
+        match mem::replace(&mut self.head, Nil) {
+            Cons(elem, tail) => {
+                self.head = *tail;
+                self.len -= 1;
+                Some(elem)
+            },
+            Nil => None,
+        }
+    }
+
+    pub fn front(&self) -> Option<&T> {
+        // `self.head` is matched by reference, so `elem` borrows the element instead of moving it
+        match self.head {
+            Cons(ref elem, _) => Some(elem),
+            Nil => None,
+        }
+    }
+
+    pub fn front_mut(&mut self) -> Option<&mut T> {
+        match self.head {
+            Cons(ref mut elem, _) => Some(elem),
+            Nil => None,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn clear(&mut self) {
+        // the old list is dropped here
+        *self = List::new();
+    }
+
+    // Reverses the list in place, reusing every node
+    pub fn reverse(&mut self) {
+        let mut reversed = Nil;
+        let mut link = mem::replace(&mut self.head, Nil);
+        while let Cons(elem, mut tail) = link {
+            link = mem::replace(&mut *tail, reversed);
+            reversed = Cons(elem, tail);
+        }
+        self.head = reversed;
+    }
+
+    // Moves every element of `other` to the end of this list, leaving `other` empty. It takes a walk to the end of
+    // this list, but none of `other`.
+    pub fn append(&mut self, other: &mut List<T>) {
+        *last(&mut self.head) = mem::replace(&mut other.head, Nil);
+        self.len += mem::replace(&mut other.len, 0);
+    }
+
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter { link: &self.head, len: self.len }
+    }
+
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut { link: Some(&mut self.head), len: self.len }
+    }
+}
+
+// the Nil at the end of the links
+fn last<T>(mut link: &mut Link<T>) -> &mut Link<T> {
+    // matching in the loop's condition would keep `link` borrowed after the loop; `is_nil` borrows it only for the
+    // check
+    while !link.is_nil() {
+        link = match *link {
+            Cons(_, ref mut tail) => tail,
+            Nil => unreachable!(),
+        };
+    }
+    link
+}
+
+impl<T> Link<T> {
+    fn is_nil(&self) -> bool {
+        match *self {
+            Cons(..) => false,
+            Nil => true,
+        }
+    }
+}
+
+impl<T> Default for List<T> {
+    fn default() -> List<T> {
+        List::new()
+    }
+}
+
+impl<T> Drop for List<T> {
+    fn drop(&mut self) {
+        let mut link = mem::replace(&mut self.head, Nil);
+        // each node is dropped once its tail has been moved out of it, so no drop goes deeper than one node
+        while let Cons(_, tail) = link {
+            link = *tail;
+        }
+    }
+}
+
+// ITERATORS
+pub struct Iter<'a, T: 'a> {
+    link: &'a Link<T>,
+    // the elements left
+    len: usize,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        match *self.link {
+            Cons(ref elem, ref tail) => {
+                self.link = tail;
+                self.len -= 1;
+                Some(elem)
+            },
+            Nil => None,
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<'a, T> ExactSizeIterator for Iter<'a, T> {}
+
+// Cloning an Iter clones the position, not the list
+impl<'a, T> Clone for Iter<'a, T> {
+    fn clone(&self) -> Iter<'a, T> {
+        Iter { link: self.link, len: self.len }
+    }
+}
+
+pub struct IterMut<'a, T: 'a> {
+    // an Option so `next` can take the reference out, where a `&mut` can't be copied
+    link: Option<&'a mut Link<T>>,
+    len: usize,
+}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<&'a mut T> {
+        match *self.link.take()? {
+            Cons(ref mut elem, ref mut tail) => {
+                self.link = Some(tail);
+                self.len -= 1;
+                Some(elem)
+            },
+            Nil => None,
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<'a, T> ExactSizeIterator for IterMut<'a, T> {}
+
+// Pops the elements off the front of the list
+pub struct IntoIter<T>(List<T>);
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.0.pop_front()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.0.len, Some(self.0.len))
+    }
+}
+
+impl<T> ExactSizeIterator for IntoIter<T> {}
+
+impl<T> IntoIterator for List<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> IntoIter<T> {
+        IntoIter(self)
+    }
+}
+
+impl<'a, T> IntoIterator for &'a List<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut List<T> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> IterMut<'a, T> {
+        self.iter_mut()
+    }
+}
+
+// Adds the elements at the end, in order
+impl<T> Extend<T> for List<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        let mut link = last(&mut self.head);
+        for elem in iter {
+            *link = Cons(elem, Box::new(Nil));
+            self.len += 1;
+            link = match *link {
+                Cons(_, ref mut tail) => tail,
+                Nil => unreachable!(),
+            };
+        }
+    }
+}
+
+// The list has the elements in the order the iterator gives them
+impl<T> FromIterator<T> for List<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> List<T> {
+        let mut list = List::new();
+        list.extend(iter);
+        list
+    }
+}
+
+// TRAITS
+// The derived impls would recurse through the links like the derived drop, so these go through `iter` instead
+impl<T: Clone> Clone for List<T> {
+    fn clone(&self) -> List<T> {
+        self.iter().cloned().collect()
+    }
+}
+
+impl<T: PartialEq> PartialEq for List<T> {
+    fn eq(&self, other: &List<T>) -> bool {
+        self.len == other.len && self.iter().eq(other.iter())
+    }
+}
+
+impl<T: Eq> Eq for List<T> {}
+
+// like a slice: `[3, 2, 1]`
+impl<T: fmt::Debug> fmt::Debug for List<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+// each element followed by a comma, then Nil: `3, 2, 1, Nil`
+impl<T: fmt::Display> fmt::Display for List<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for elem in self {
+            write!(f, "{}, ", elem)?;
+        }
+        write!(f, "Nil")
+    }
+}
+
+pub fn run(out: &mut impl Write) -> io::Result<()> {
+    // USE
+    // explicitly use each name so they are available without manual scoping
+
+    use self::Status::{Poor, Rich};
+    // automatically `use` each name inside work
+    use self::Work::*;
+
+    let status = Poor; // equivalent to Status::Poor
+    let _work = Civilian; // equivalent to Work::Civilian
+
+    match status {
+        // we don't need scoping due to the explicit `use` above
+        Rich => writeln!(out, "The Rich have lots of money")?,
+        Poor => writeln!(out, "The Poor have no money...")?,
+    }
+    // we can use work the same way
+
+    // TESTCASE: LINKED-LIST
+    // create an empty linked list
+    let mut list = List::new();
+
+    list = list.prepend(1);
+    list = list.prepend(2);
+    list = list.prepend(3);
+
+    writeln!(out, "linked list has length: {}", list.len())?;
+    writeln!(out, "{}", list)?;
+
+    // the list holds any type, and works with iterators like the std collections
+    let mut words: List<String> = "the list is generic".split(' ').map(str::to_owned).collect();
+    for word in &mut words {
+        *word = word.to_uppercase();
+    }
+    words.extend(vec!["NOW".to_owned()]);
+    writeln!(out, "{:?} has {} words", words, words.len())?;
+
+    let mut more: List<u32> = (4..7).collect();
+    list.append(&mut more);
+    list.reverse();
+    writeln!(out, "appended and reversed: {}", list)?;
+    let front = list.pop_front();
+    writeln!(out, "popped {:?}, and the rest sum to {}", front, list.iter().sum::<u32>())?;
+
+    // a million elements, dropped without recursing
+    let long: List<u64> = (0..1_000_000).collect();
+    writeln!(out, "a list of {} elements ending in {:?}", long.len(), long.iter().last())?;
+
+    // custom_types::persistent_list has a list that `prepend` doesn't consume: each version shares the one before
+    let v1 = PList::new().prepend(1).prepend(2);
+    let v2 = v1.prepend(3);
+    let v3 = v1.prepend(4);
+    writeln!(out, "v1 is {}, v2 is {} and v3 is {}", v1, v2, v3)?;
+    writeln!(out, "v2 and v3 share v1 as their tail: {}", v2.tail().is_some_and(|tail| tail.ptr_eq(&v1))
+             && v3.tail().is_some_and(|tail| tail.ptr_eq(&v1)))?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::cell::Cell;
+
+    #[test]
+    fn front_operations() {
+        let mut list = List::new().prepend(1).prepend(2);
+        list.push_front(3);
+        assert_eq!(list.len(), 3);
+        assert_eq!(list.front(), Some(&3));
+        *list.front_mut().unwrap() = 30;
+        assert_eq!(list.pop_front(), Some(30));
+        assert_eq!(list.pop_front(), Some(2));
+        assert_eq!(list.pop_front(), Some(1));
+        assert_eq!(list.pop_front(), None);
+        assert!(list.is_empty());
+        assert_eq!(list.to_string(), "Nil");
+    }
+
+    #[test]
+    fn iterators() {
+        let mut list: List<i32> = vec![1, 2, 3].into_iter().collect();
+        assert_eq!(format!("{:?}", list), "[1, 2, 3]");
+        assert_eq!(list.to_string(), "1, 2, 3, Nil");
+        assert_eq!(list.iter().len(), 3);
+        for x in &mut list {
+            *x *= 10;
+        }
+        assert_eq!((&list).into_iter().cloned().collect::<Vec<_>>(), vec![10, 20, 30]);
+        list.extend(4..6);
+        assert_eq!(list.len(), 5);
+        assert_eq!(list.clone().into_iter().collect::<Vec<_>>(), vec![10, 20, 30, 4, 5]);
+        assert_eq!(list.iter_mut().size_hint(), (5, Some(5)));
+
+        let mut empty = List::new();
+        empty.extend(vec!['a']);
+        assert_eq!(empty.front(), Some(&'a'));
+    }
+
+    #[test]
+    fn reverse_and_append() {
+        let mut list: List<i32> = (1..4).collect();
+        list.reverse();
+        assert_eq!(list, (1..4).rev().collect());
+        let mut other: List<i32> = (7..9).collect();
+        list.append(&mut other);
+        assert!(other.is_empty());
+        assert_eq!(list, vec![3, 2, 1, 7, 8].into_iter().collect());
+        assert_eq!(list.len(), 5);
+        // appending to an empty list, and appending an empty one
+        let mut empty = List::new();
+        empty.append(&mut list);
+        empty.append(&mut List::new());
+        assert_eq!(empty.len(), 5);
+        assert_ne!(empty, (1..6).collect());
+        List::<i32>::new().reverse();
+    }
+
+    #[test]
+    fn drops_every_element() {
+        struct Counted<'a>(&'a Cell<usize>);
+        impl<'a> Drop for Counted<'a> {
+            fn drop(&mut self) {
+                self.0.set(self.0.get() + 1);
+            }
+        }
+
+        let drops = Cell::new(0);
+        let mut list: List<Counted> = (0..10).map(|_| Counted(&drops)).collect();
+        drop(list.pop_front());
+        assert_eq!(drops.get(), 1);
+        let mut rest = list.into_iter();
+        rest.next();
+        drop(rest);
+        assert_eq!(drops.get(), 10);
+    }
+
+    // every one of these recursed once per element before, which the default 2MiB stack of a test thread can't
+    // take for ten million of them
+    #[test]
+    fn ten_million_elements() {
+        const N: u64 = 10_000_000;
+        let mut list: List<u64> = (0..N).collect();
+        assert_eq!(list.len(), N as usize);
+        assert_eq!(list.iter().sum::<u64>(), N * (N - 1) / 2);
+        list.reverse();
+        assert_eq!(list.front(), Some(&(N - 1)));
+        let copy = list.clone();
+        assert!(copy == list);
+        drop(copy);
+        list.clear();
+        assert!(list.is_empty());
+
+        let mut list = List::new();
+        for i in 0..N {
+            list = list.prepend(i);
+        }
+        drop(list);
+    }
+}
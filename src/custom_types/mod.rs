@@ -0,0 +1,23 @@
+// CUSTOM TYPES
+// Rust custom data types are formed mainly through the two keywords struct and enum.
+// Constants can be created via the const and static keywords.
+
+pub mod structs;
+pub mod enums;
+pub mod events;
+pub mod keymap;
+pub mod session;
+pub mod widgets;
+pub mod linked_list;
+pub mod persistent_list;
+pub mod registry;
+pub mod constants;
+pub mod config;
+
+pub use self::structs::{Nil, Pair, Person, Point, Rectangle};
+pub use self::enums::{Status, WebEvent, Work};
+pub use self::events::{Pipeline, Stamped};
+pub use self::linked_list::List;
+pub use self::persistent_list::{ArcPList, PList};
+pub use self::config::Config;
+pub use self::registry::Registry;
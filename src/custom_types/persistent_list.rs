@@ -0,0 +1,295 @@
+// PERSISTENT LISTS
+// `List::prepend` consumes the list, so keeping the old version around means cloning every element. A PList never
+// changes once it's made: `prepend` makes a new list whose tail is the old one, shared rather than copied, and
+// `tail` hands back the shared rest of a list. Every version stays valid, which makes them cheap snapshots, say for
+// undo or for a history of configurations:
+//
+//     let v1 = PList::new().prepend(1);   // [1]
+//     let v2 = v1.prepend(2);             // [2, 1], the 1 being v1's node
+//     let v3 = v1.prepend(3);             // [3, 1], sharing it too
+//
+// Cloning a list copies one pointer, and comparing two lists stops as soon as they reach a node they share.
+// PList counts its references with Rc; ArcPList is the same list with Arc, for sharing between threads.
+
+use std::fmt;
+use std::iter::FromIterator;
+use std::rc::Rc;
+use std::sync::Arc;
+
+macro_rules! persistent_list {
+    ($List:ident, $Node:ident, $Iter:ident, $Rc:ident) => {
+        pub struct $List<T> {
+            head: Option<$Rc<$Node<T>>>,
+        }
+
+        struct $Node<T> {
+            elem: T,
+            // of the list starting here, so every tail knows its length too
+            len: usize,
+            next: Option<$Rc<$Node<T>>>,
+        }
+
+        impl<T> $List<T> {
+            pub fn new() -> $List<T> {
+                $List { head: None }
+            }
+
+            // a new list of the element followed by this one
+            pub fn prepend(&self, elem: T) -> $List<T> {
+                let node = $Node { elem, len: self.len() + 1, next: self.head.clone() };
+                $List { head: Some($Rc::new(node)) }
+            }
+
+            pub fn head(&self) -> Option<&T> {
+                self.head.as_ref().map(|node| &node.elem)
+            }
+
+            // everything after the head, sharing this list's nodes; None for an empty list
+            pub fn tail(&self) -> Option<$List<T>> {
+                self.head.as_ref().map(|node| $List { head: node.next.clone() })
+            }
+
+            pub fn len(&self) -> usize {
+                self.head.as_ref().map_or(0, |node| node.len)
+            }
+
+            pub fn is_empty(&self) -> bool {
+                self.head.is_none()
+            }
+
+            // whether the two are the same nodes, not only equal elements
+            pub fn ptr_eq(&self, other: &$List<T>) -> bool {
+                match (&self.head, &other.head) {
+                    (Some(a), Some(b)) => $Rc::ptr_eq(a, b),
+                    (None, None) => true,
+                    _ => false,
+                }
+            }
+
+            pub fn iter(&self) -> $Iter<'_, T> {
+                $Iter { node: self.head.as_deref() }
+            }
+        }
+
+        impl<T> Default for $List<T> {
+            fn default() -> $List<T> {
+                $List::new()
+            }
+        }
+
+        // one more reference to the same nodes
+        impl<T> Clone for $List<T> {
+            fn clone(&self) -> $List<T> {
+                $List { head: self.head.clone() }
+            }
+        }
+
+        // Drops the nodes no other list holds, stopping at the first shared one, without recursing on either.
+        // into_inner rather than try_unwrap: when two lists sharing a node drop at once, one of them always gets it
+        impl<T> Drop for $List<T> {
+            fn drop(&mut self) {
+                let mut next = self.head.take();
+                while let Some(node) = next {
+                    next = $Rc::into_inner(node).and_then(|mut node| node.next.take());
+                }
+            }
+        }
+
+        pub struct $Iter<'a, T: 'a> {
+            node: Option<&'a $Node<T>>,
+        }
+
+        impl<'a, T> Iterator for $Iter<'a, T> {
+            type Item = &'a T;
+
+            fn next(&mut self) -> Option<&'a T> {
+                let node = self.node?;
+                self.node = node.next.as_deref();
+                Some(&node.elem)
+            }
+
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                let len = self.node.map_or(0, |node| node.len);
+                (len, Some(len))
+            }
+        }
+
+        impl<'a, T> ExactSizeIterator for $Iter<'a, T> {}
+
+        impl<'a, T> IntoIterator for &'a $List<T> {
+            type Item = &'a T;
+            type IntoIter = $Iter<'a, T>;
+
+            fn into_iter(self) -> $Iter<'a, T> {
+                self.iter()
+            }
+        }
+
+        // in the iterator's order, which takes building from the back
+        impl<T> FromIterator<T> for $List<T> {
+            fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> $List<T> {
+                let elems: Vec<T> = iter.into_iter().collect();
+                let mut list = $List::new();
+                for elem in elems.into_iter().rev() {
+                    list = list.prepend(elem);
+                }
+                list
+            }
+        }
+
+        // Element by element until the lists reach the same node, after which the rest is the same too
+        impl<T: PartialEq> PartialEq for $List<T> {
+            fn eq(&self, other: &$List<T>) -> bool {
+                if self.len() != other.len() {
+                    return false;
+                }
+                let (mut a, mut b) = (&self.head, &other.head);
+                while let (Some(x), Some(y)) = (a, b) {
+                    if $Rc::ptr_eq(x, y) {
+                        return true;
+                    }
+                    if x.elem != y.elem {
+                        return false;
+                    }
+                    a = &x.next;
+                    b = &y.next;
+                }
+                true
+            }
+        }
+
+        impl<T: Eq> Eq for $List<T> {}
+
+        impl<T: fmt::Debug> fmt::Debug for $List<T> {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.debug_list().entries(self.iter()).finish()
+            }
+        }
+
+        // like List: `3, 2, 1, Nil`
+        impl<T: fmt::Display> fmt::Display for $List<T> {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                for elem in self {
+                    write!(f, "{}, ", elem)?;
+                }
+                write!(f, "Nil")
+            }
+        }
+    };
+}
+
+persistent_list!(PList, Node, Iter, Rc);
+persistent_list!(ArcPList, ArcNode, ArcIter, Arc);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::sync::Barrier;
+    use std::thread;
+
+    #[test]
+    fn sharing() {
+        let v1 = PList::new().prepend(1);
+        let v2 = v1.prepend(2);
+        let v3 = v1.prepend(3);
+        assert_eq!(v2.iter().cloned().collect::<Vec<_>>(), vec![2, 1]);
+        assert_eq!(v3.to_string(), "3, 1, Nil");
+        assert_eq!(format!("{:?}", v1), "[1]");
+        assert_eq!((v1.len(), v2.len(), v3.len()), (1, 2, 2));
+        assert!(v2.tail().unwrap().ptr_eq(&v1) && v3.tail().unwrap().ptr_eq(&v1));
+        assert_eq!(v2.head(), Some(&2));
+        assert_eq!(PList::<i32>::new().tail(), None);
+        assert_eq!(PList::<i32>::new().head(), None);
+
+        // the 1 is held by v1's list and by the tails of v2 and v3
+        let shared = v1.head.as_ref().unwrap();
+        assert_eq!(Rc::strong_count(shared), 3);
+        drop(v3);
+        assert_eq!(Rc::strong_count(v1.head.as_ref().unwrap()), 2);
+        let clone = v2.clone();
+        assert!(clone.ptr_eq(&v2));
+    }
+
+    #[test]
+    fn equality() {
+        let a: PList<i32> = (1..4).collect();
+        let b: PList<i32> = (1..4).collect();
+        assert!(!a.ptr_eq(&b));
+        assert_eq!(a, b);
+        assert_ne!(a, b.tail().unwrap());
+        assert_ne!(a, b.tail().unwrap().prepend(0));
+        assert_eq!(PList::<i32>::new(), PList::new());
+
+        // a NaN isn't equal to itself, so only the shared node stops these two comparing unequal
+        let nan = PList::new().prepend(f64::NAN);
+        assert_eq!(nan.prepend(1.0), nan.prepend(1.0));
+        assert_ne!(PList::new().prepend(f64::NAN), PList::new().prepend(f64::NAN));
+    }
+
+    #[test]
+    fn undo_history() {
+        let mut history = vec![PList::new()];
+        for word in "the quick brown fox".split(' ') {
+            let next = history.last().unwrap().prepend(word);
+            history.push(next);
+        }
+        // undoing twice is going back two versions, which are still intact
+        let undone = &history[history.len() - 3];
+        assert_eq!(undone.iter().cloned().collect::<Vec<_>>(), vec!["quick", "the"]);
+        assert_eq!(history[4].iter().len(), 4);
+        assert!(history[4].tail().unwrap().tail().unwrap().ptr_eq(undone));
+    }
+
+    #[test]
+    fn threads() {
+        let base: ArcPList<u64> = (0..3).collect();
+        let handles: Vec<_> = (10..14)
+            .map(|n| {
+                let base = base.clone();
+                thread::spawn(move || base.prepend(n))
+            })
+            .collect();
+        for handle in handles {
+            let list = handle.join().unwrap();
+            assert!(list.tail().unwrap().ptr_eq(&base));
+            assert_eq!(list.len(), 4);
+        }
+        assert_eq!(Arc::strong_count(base.head.as_ref().unwrap()), 1);
+    }
+
+    #[test]
+    fn long_lists() {
+        let long: PList<u32> = (0..1_000_000).collect();
+        let longer = long.prepend(7);
+        drop(long);
+        assert_eq!(longer.len(), 1_000_001);
+        assert_eq!(longer.tail().unwrap(), longer.tail().unwrap());
+        let copy: PList<u32> = longer.iter().cloned().collect();
+        assert!(copy == longer);
+        drop(longer);
+        let _arc: ArcPList<u32> = (0..1_000_000).collect();
+    }
+
+    #[test]
+    fn racing_drops() {
+        // with try_unwrap, threads dropping at once could all see the other's reference and leave the long tail
+        // to be dropped by recursion
+        for _ in 0..4 {
+            let long: ArcPList<u32> = (0..1_000_000).collect();
+            let barrier = Arc::new(Barrier::new(4));
+            let handles: Vec<_> = (0..4)
+                .map(|_| {
+                    let (list, barrier) = (long.clone(), barrier.clone());
+                    thread::spawn(move || {
+                        barrier.wait();
+                        drop(list);
+                    })
+                })
+                .collect();
+            drop(long);
+            for handle in handles {
+                handle.join().unwrap();
+            }
+        }
+    }
+}
@@ -0,0 +1,535 @@
+// REGISTRY
+// A Person borrows its name, which suits a struct literal but not a customer record that has to outlive the text it
+// was read from. A Registry owns its records, each a person with their Status and Work, and can:
+// - filter them with predicates, which the functions here make and combine: `and(works_as(Soldier), aged(18..30))`
+// - group them by any key, counting each group and averaging its ages
+// - sort them by several keys at once, keeping records that tie on all of them in the order they were in
+// - read and write them as CSV, with a header and a record per line:
+//
+//     name,age,status,work
+//     Peter,27,Rich,Civilian
+//     "Smith, Jane",41,Poor,Soldier
+//     "The ""Baron""",63,Rich,Civilian
+//
+// A field is quoted when it holds a comma, a quote, a line break or space at either end, and a quote inside one is
+// written twice. A quoted field can run over several lines; a malformed one is reported by line and column.
+
+use std::collections::BTreeMap;
+use std::error;
+use std::fmt;
+use std::io::{self, Write};
+use std::iter::Peekable;
+use std::ops::RangeBounds;
+use std::str::Chars;
+
+use custom_types::{Person, Status, Work};
+use primitives::stats::Running;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Record {
+    pub name: String,
+    pub age: u8,
+    pub status: Status,
+    pub work: Work,
+}
+
+impl Record {
+    pub fn new(person: Person, status: Status, work: Work) -> Record {
+        Record { name: person.name.to_owned(), age: person.age, status, work }
+    }
+
+    pub fn person(&self) -> Person<'_> {
+        Person { name: &self.name, age: self.age }
+    }
+}
+
+// PREDICATES
+pub fn has_status(status: Status) -> impl Fn(&Record) -> bool {
+    move |record| record.status == status
+}
+
+pub fn works_as(work: Work) -> impl Fn(&Record) -> bool {
+    move |record| record.work == work
+}
+
+pub fn aged<R: RangeBounds<u8>>(ages: R) -> impl Fn(&Record) -> bool {
+    move |record| ages.contains(&record.age)
+}
+
+pub fn named(part: &str) -> impl Fn(&Record) -> bool {
+    let part = part.to_lowercase();
+    move |record| record.name.to_lowercase().contains(&part)
+}
+
+pub fn and<P: Fn(&Record) -> bool, Q: Fn(&Record) -> bool>(p: P, q: Q) -> impl Fn(&Record) -> bool {
+    move |record| p(record) && q(record)
+}
+
+pub fn or<P: Fn(&Record) -> bool, Q: Fn(&Record) -> bool>(p: P, q: Q) -> impl Fn(&Record) -> bool {
+    move |record| p(record) || q(record)
+}
+
+pub fn not<P: Fn(&Record) -> bool>(p: P) -> impl Fn(&Record) -> bool {
+    move |record| !p(record)
+}
+
+// GROUPS
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Group {
+    pub count: usize,
+    pub average_age: f64,
+    pub youngest: u8,
+    pub oldest: u8,
+}
+
+impl fmt::Display for Group {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} aged {:.1} on average, {} to {}", self.count, self.average_age, self.youngest, self.oldest)
+    }
+}
+
+// SORTING
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Field {
+    Name,
+    Age,
+    Status,
+    Work,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SortKey {
+    pub field: Field,
+    pub descending: bool,
+}
+
+impl SortKey {
+    pub fn asc(field: Field) -> SortKey {
+        SortKey { field, descending: false }
+    }
+
+    pub fn desc(field: Field) -> SortKey {
+        SortKey { field, descending: true }
+    }
+}
+
+impl Field {
+    fn compare(self, a: &Record, b: &Record) -> ::std::cmp::Ordering {
+        match self {
+            Field::Name => a.name.cmp(&b.name),
+            Field::Age => a.age.cmp(&b.age),
+            Field::Status => a.status.cmp(&b.status),
+            Field::Work => a.work.cmp(&b.work),
+        }
+    }
+}
+
+// REGISTRY
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Registry {
+    pub records: Vec<Record>,
+}
+
+impl Registry {
+    pub fn new() -> Registry {
+        Registry { records: Vec::new() }
+    }
+
+    pub fn add(&mut self, person: Person, status: Status, work: Work) {
+        self.records.push(Record::new(person, status, work));
+    }
+
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+
+    // the records the predicate is true for, in order
+    pub fn filter<P: Fn(&Record) -> bool>(&self, predicate: P) -> Vec<&Record> {
+        self.records.iter().filter(|record| predicate(record)).collect()
+    }
+
+    pub fn group_by<K: Ord, F: Fn(&Record) -> K>(&self, key: F) -> BTreeMap<K, Group> {
+        let mut ages: BTreeMap<K, Running<u8>> = BTreeMap::new();
+        for record in &self.records {
+            ages.entry(key(record)).or_default().push(record.age);
+        }
+        ages.into_iter()
+            .map(|(key, ages)| {
+                // every group has at least one record, so none of these are empty
+                let group = Group {
+                    count: ages.count() as usize,
+                    average_age: ages.mean().unwrap(),
+                    youngest: ages.min().unwrap(),
+                    oldest: ages.max().unwrap(),
+                };
+                (key, group)
+            })
+            .collect()
+    }
+
+    // Sorts by the first key, then the second among records equal on the first, and so on. The sort is stable, so
+    // records equal on every key stay in the order they were in.
+    pub fn sort_by(&mut self, keys: &[SortKey]) {
+        self.records.sort_by(|a, b| {
+            keys.iter()
+                .map(|key| if key.descending { key.field.compare(b, a) } else { key.field.compare(a, b) })
+                .find(|ordering| ordering.is_ne())
+                .unwrap_or(::std::cmp::Ordering::Equal)
+        });
+    }
+
+    pub fn to_csv(&self) -> String {
+        let mut csv = HEADER.join(",") + "\n";
+        for record in &self.records {
+            push_field(&mut csv, &record.name);
+            csv.push_str(&format!(",{},{},{}\n", record.age, status_name(record.status), work_name(record.work)));
+        }
+        csv
+    }
+
+    pub fn from_csv(text: &str) -> Result<Registry, CsvError> {
+        let mut rows = parse_csv(text)?.into_iter();
+        match rows.next() {
+            Some(ref header) if header.iter().map(|field| field.text.as_str()).eq(HEADER.iter().cloned()) => {},
+            header => {
+                let header = header.unwrap_or_default();
+                let found = header.iter().map(|field| field.text.as_str()).collect::<Vec<_>>().join(",");
+                let line = header.first().map_or(1, |field| field.line);
+                return Err(CsvError { line, column: 1, kind: CsvErrorKind::BadHeader(found) });
+            },
+        }
+
+        let mut registry = Registry::new();
+        for row in rows {
+            if row.len() != HEADER.len() {
+                let (line, column) = row.last().map_or((0, 0), |field| (field.line, field.column));
+                return Err(CsvError { line, column, kind: CsvErrorKind::FieldCount { expected: HEADER.len(), found: row.len() } });
+            }
+            let age = row[1].text.trim().parse().map_err(|_| row[1].bad("age"))?;
+            let status = match row[2].text.trim() {
+                "Rich" => Status::Rich,
+                "Poor" => Status::Poor,
+                _ => return Err(row[2].bad("status")),
+            };
+            let work = match row[3].text.trim() {
+                "Civilian" => Work::Civilian,
+                "Soldier" => Work::Soldier,
+                _ => return Err(row[3].bad("work")),
+            };
+            registry.records.push(Record { name: row[0].text.clone(), age, status, work });
+        }
+        Ok(registry)
+    }
+}
+
+// CSV
+const HEADER: [&str; 4] = ["name", "age", "status", "work"];
+
+fn status_name(status: Status) -> &'static str {
+    match status {
+        Status::Rich => "Rich",
+        Status::Poor => "Poor",
+    }
+}
+
+fn work_name(work: Work) -> &'static str {
+    match work {
+        Work::Civilian => "Civilian",
+        Work::Soldier => "Soldier",
+    }
+}
+
+// the field, quoted if it has to be
+fn push_field(csv: &mut String, field: &str) {
+    let quote = field.contains([',', '"', '\n', '\r']) || field.starts_with(' ') || field.ends_with(' ');
+    if quote {
+        csv.push('"');
+        csv.push_str(&field.replace('"', "\"\""));
+        csv.push('"');
+    } else {
+        csv.push_str(field);
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum CsvErrorKind {
+    // a quoted field still open at the end of the text
+    UnterminatedQuote,
+    // a `"` in a field that didn't start with one
+    QuoteInField,
+    // something other than a comma or the end of the line after a quoted field
+    AfterQuote(char),
+    BadHeader(String),
+    FieldCount { expected: usize, found: usize },
+    BadValue { column: &'static str, found: String },
+}
+
+impl fmt::Display for CsvErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            CsvErrorKind::UnterminatedQuote => write!(f, "the quoted field that starts here never ends"),
+            CsvErrorKind::QuoteInField => write!(f, "a quote in a field that isn't quoted"),
+            CsvErrorKind::AfterQuote(c) => write!(f, "expected `,` or the end of the line after a quoted field, found `{}`", c),
+            CsvErrorKind::BadHeader(ref header) => write!(f, "expected the header `{}`, found `{}`", HEADER.join(","), header),
+            CsvErrorKind::FieldCount { expected, found } => write!(f, "expected {} fields, found {}", expected, found),
+            CsvErrorKind::BadValue { column, ref found } => write!(f, "`{}` is not a valid {}", found, column),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CsvError {
+    // both counting from 1; the column is in chars
+    pub line: usize,
+    pub column: usize,
+    pub kind: CsvErrorKind,
+}
+
+impl fmt::Display for CsvError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}, column {}: {}", self.line, self.column, self.kind)
+    }
+}
+
+impl error::Error for CsvError {}
+
+// A field and where it starts
+struct CsvField {
+    text: String,
+    line: usize,
+    column: usize,
+}
+
+impl CsvField {
+    fn bad(&self, column: &'static str) -> CsvError {
+        CsvError { line: self.line, column: self.column, kind: CsvErrorKind::BadValue { column, found: self.text.clone() } }
+    }
+}
+
+struct Cursor<'a> {
+    chars: Peekable<Chars<'a>>,
+    line: usize,
+    column: usize,
+}
+
+impl<'a> Cursor<'a> {
+    fn bump(&mut self) -> Option<char> {
+        let c = self.chars.next()?;
+        if c == '\n' {
+            self.line += 1;
+            self.column = 1;
+        } else {
+            self.column += 1;
+        }
+        Some(c)
+    }
+
+    fn error(&self, kind: CsvErrorKind) -> CsvError {
+        CsvError { line: self.line, column: self.column, kind }
+    }
+
+    // the end of a line, `\n` or `\r\n`
+    fn at_line_end(&mut self) -> bool {
+        match self.chars.peek() {
+            Some('\n') => true,
+            Some('\r') => self.chars.clone().nth(1) == Some('\n'),
+            _ => false,
+        }
+    }
+
+    // Reads the separator after a field, returning whether another field follows on the same record
+    fn separator(&mut self) -> bool {
+        if self.chars.peek() == Some(&',') {
+            self.bump();
+            return true;
+        }
+        if self.chars.peek() == Some(&'\r') {
+            self.bump();
+        }
+        self.bump();
+        false
+    }
+}
+
+// Every record of the text, each a list of fields; blank lines are skipped
+fn parse_csv(text: &str) -> Result<Vec<Vec<CsvField>>, CsvError> {
+    let mut cursor = Cursor { chars: text.chars().peekable(), line: 1, column: 1 };
+    let mut records = Vec::new();
+    while cursor.chars.peek().is_some() {
+        if cursor.at_line_end() {
+            cursor.separator();
+            continue;
+        }
+        let mut record = Vec::new();
+        loop {
+            let (line, column) = (cursor.line, cursor.column);
+            let mut text = String::new();
+            if cursor.chars.peek() == Some(&'"') {
+                cursor.bump();
+                loop {
+                    match cursor.bump() {
+                        None => return Err(CsvError { line, column, kind: CsvErrorKind::UnterminatedQuote }),
+                        Some('"') if cursor.chars.peek() == Some(&'"') => {
+                            cursor.bump();
+                            text.push('"');
+                        },
+                        Some('"') => break,
+                        Some(c) => text.push(c),
+                    }
+                }
+                let next = cursor.chars.peek().cloned();
+                match next {
+                    None | Some(',') => {},
+                    Some(_) if cursor.at_line_end() => {},
+                    Some(c) => return Err(cursor.error(CsvErrorKind::AfterQuote(c))),
+                }
+            } else {
+                while let Some(&c) = cursor.chars.peek() {
+                    if c == ',' || cursor.at_line_end() {
+                        break;
+                    }
+                    if c == '"' {
+                        return Err(cursor.error(CsvErrorKind::QuoteInField));
+                    }
+                    text.push(c);
+                    cursor.bump();
+                }
+            }
+            record.push(CsvField { text, line, column });
+            if !cursor.separator() {
+                break;
+            }
+        }
+        records.push(record);
+    }
+    Ok(records)
+}
+
+// People filtered by their work, grouped by their status, sorted by age and name, and written out and read back as CSV
+pub fn run(out: &mut impl Write) -> io::Result<()> {
+    let mut people = Registry::new();
+    people.add(Person { name: "Peter", age: 27 }, Status::Rich, Work::Civilian);
+    people.add(Person { name: "Smith, Jane", age: 41 }, Status::Poor, Work::Soldier);
+    people.add(Person { name: "Bob", age: 19 }, Status::Poor, Work::Soldier);
+    people.add(Person { name: "Ann", age: 27 }, Status::Poor, Work::Civilian);
+    let soldiers = people.filter(works_as(Work::Soldier));
+    writeln!(out, "soldiers: {:?}", soldiers.iter().map(|record| record.person()).collect::<Vec<_>>())?;
+    for (status, group) in people.group_by(|record| record.status) {
+        writeln!(out, "{:?}: {}", status, group)?;
+    }
+    people.sort_by(&[SortKey::asc(Field::Age), SortKey::asc(Field::Name)]);
+    let csv = people.to_csv();
+    write!(out, "{}", csv)?;
+    writeln!(out, "read back the same: {}", Registry::from_csv(&csv).as_ref() == Ok(&people))?;
+    if let Err(e) = Registry::from_csv("name,age,status,work\n\"Ann,27,Poor,Civilian\n") {
+        writeln!(out, "bad CSV: {}", e)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use custom_types::Status::*;
+    use custom_types::Work::*;
+
+    fn registry() -> Registry {
+        let mut registry = Registry::new();
+        for &(name, age, status, work) in &[
+            ("Peter", 27, Rich, Civilian),
+            ("Smith, Jane", 41, Poor, Soldier),
+            ("The \"Baron\"", 63, Rich, Civilian),
+            ("Ann", 27, Poor, Civilian),
+            ("Bob", 19, Poor, Soldier),
+            (" Zed ", 41, Rich, Soldier),
+        ] {
+            registry.add(Person { name, age }, status, work);
+        }
+        registry
+    }
+
+    fn names(records: &[&Record]) -> Vec<String> {
+        records.iter().map(|record| record.name.clone()).collect()
+    }
+
+    #[test]
+    fn filters() {
+        let registry = registry();
+        assert_eq!(names(&registry.filter(has_status(Rich))), vec!["Peter", "The \"Baron\"", " Zed "]);
+        assert_eq!(names(&registry.filter(and(works_as(Soldier), aged(..30)))), vec!["Bob"]);
+        assert_eq!(names(&registry.filter(or(named("b"), aged(60..)))), vec!["The \"Baron\"", "Bob"]);
+        assert_eq!(registry.filter(not(has_status(Rich))).len(), 3);
+        assert_eq!(registry.records[0].person(), Person { name: "Peter", age: 27 });
+    }
+
+    #[test]
+    fn groups() {
+        let registry = registry();
+        let by_work = registry.group_by(|record| record.work);
+        assert_eq!(by_work[&Civilian], Group { count: 3, average_age: 39.0, youngest: 27, oldest: 63 });
+        assert_eq!(by_work[&Soldier].to_string(), "3 aged 33.7 on average, 19 to 41");
+        let by_both = registry.group_by(|record| (record.status, record.work));
+        assert_eq!(by_both.keys().cloned().collect::<Vec<_>>(),
+                   vec![(Rich, Civilian), (Rich, Soldier), (Poor, Civilian), (Poor, Soldier)]);
+        assert!(Registry::new().group_by(|record| record.age).is_empty());
+    }
+
+    #[test]
+    fn sorting() {
+        let mut registry = registry();
+        registry.sort_by(&[SortKey::desc(Field::Age)]);
+        // ties keep their order: Jane was before Zed and Peter before Ann
+        let all: Vec<&Record> = registry.records.iter().collect();
+        assert_eq!(names(&all), vec!["The \"Baron\"", "Smith, Jane", " Zed ", "Peter", "Ann", "Bob"]);
+
+        registry.sort_by(&[SortKey::asc(Field::Status), SortKey::asc(Field::Work), SortKey::desc(Field::Name)]);
+        let all: Vec<&Record> = registry.records.iter().collect();
+        assert_eq!(names(&all), vec!["The \"Baron\"", "Peter", " Zed ", "Ann", "Smith, Jane", "Bob"]);
+    }
+
+    #[test]
+    fn csv_round_trip() {
+        let registry = registry();
+        let csv = registry.to_csv();
+        assert_eq!(csv, "\
+name,age,status,work
+Peter,27,Rich,Civilian
+\"Smith, Jane\",41,Poor,Soldier
+\"The \"\"Baron\"\"\",63,Rich,Civilian
+Ann,27,Poor,Civilian
+Bob,19,Poor,Soldier
+\" Zed \",41,Rich,Soldier
+");
+        assert_eq!(Registry::from_csv(&csv), Ok(registry));
+
+        // line breaks inside quotes, CRLF line endings, blank lines and no final line break
+        let registry = Registry::from_csv("name,age,status,work\r\n\r\n\"two\r\nlines\",5,Rich,Soldier\r\n,7, Poor ,Civilian").unwrap();
+        assert_eq!(registry.records[0].name, "two\r\nlines");
+        assert_eq!(registry.records[1], Record { name: String::new(), age: 7, status: Poor, work: Civilian });
+        assert_eq!(Registry::from_csv(&registry.to_csv()), Ok(registry));
+    }
+
+    #[test]
+    fn csv_errors() {
+        let error = |csv: &str| {
+            let e = Registry::from_csv(&format!("name,age,status,work\n{}", csv)).unwrap_err();
+            (e.line, e.column, e.kind)
+        };
+        assert_eq!(error("Ann,27,Poor,Civilian\n\"Bob,19,Poor,Soldier\n"), (3, 1, CsvErrorKind::UnterminatedQuote));
+        assert_eq!(error("Ann \"the\",27,Poor,Civilian"), (2, 5, CsvErrorKind::QuoteInField));
+        assert_eq!(error("\"Ann\" Lee,27,Poor,Civilian"), (2, 6, CsvErrorKind::AfterQuote(' ')));
+        assert_eq!(error("\"Ann\nLee\",27,Poor"), (3, 9, CsvErrorKind::FieldCount { expected: 4, found: 3 }));
+        assert_eq!(error("Ann,twenty,Poor,Civilian"),
+                   (2, 5, CsvErrorKind::BadValue { column: "age", found: "twenty".to_owned() }));
+        assert_eq!(error("Ann,300,Poor,Civilian").2, CsvErrorKind::BadValue { column: "age", found: "300".to_owned() });
+        assert_eq!(error("\"Ann\nLee\",27,Poor,Clerk"), (3, 14, CsvErrorKind::BadValue { column: "work", found: "Clerk".to_owned() }));
+        assert_eq!(Registry::from_csv("name,age\nAnn,27").unwrap_err().to_string(),
+                   "line 1, column 1: expected the header `name,age,status,work`, found `name,age`");
+        assert_eq!(Registry::from_csv("").unwrap_err().kind, CsvErrorKind::BadHeader(String::new()));
+        assert_eq!(Registry::from_csv("name,age,status,work\n"), Ok(Registry::new()));
+    }
+}
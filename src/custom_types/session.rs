@@ -0,0 +1,914 @@
+// SESSIONS
+// A session is a recorded sequence of Stamped events, kept for replaying in regression tests. `encode` writes one
+// as JSON Lines, an object per event:
+//
+//     {"at_us":0,"event":"page_load"}
+//     {"at_us":1500000,"event":"click","x":20,"y":80}
+//     {"at_us":1500000,"event":"key_press","key":"é"}
+//     {"at_us":2250000,"event":"paste","text":"two\nlines, \"quoted\""}
+//
+// and `decode` reads it back. Times are whole microseconds from the start of the session, so anything finer is
+// lost on the way out. Strings escape quotes, backslashes and control characters and write everything else as is;
+// the decoder also takes `\uXXXX` escapes, surrogate pairs included, as any JSON writer may use them. Fields it
+// doesn't know are skipped, so a recording with extra fields still loads.
+//
+// A Recorder stamps events as they happen, a Replayer plays a session back at its recorded pace, faster or slower,
+// or one event at a time, and `diverge` finds the first place two sessions stop agreeing.
+
+use std::error;
+use std::fmt;
+use std::io::{self, Write};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use custom_types::WebEvent;
+use custom_types::events::{parse_log, Kind, Stamped};
+
+// ENCODING
+pub fn encode_line(stamped: &Stamped) -> String {
+    let mut line = format!("{{\"at_us\":{},\"event\":\"{}\"", stamped.at.as_micros(), stamped.event.kind().name());
+    match stamped.event {
+        WebEvent::KeyPress(c) => {
+            line.push_str(",\"key\":");
+            push_string(&mut line, c.encode_utf8(&mut [0; 4]));
+        },
+        WebEvent::Paste(ref text) => {
+            line.push_str(",\"text\":");
+            push_string(&mut line, text);
+        },
+        WebEvent::Click { x, y } => line.push_str(&format!(",\"x\":{},\"y\":{}", x, y)),
+        WebEvent::PageLoad | WebEvent::PageUnload => {},
+    }
+    line.push('}');
+    line
+}
+
+// every event on its own line, each line ending in a newline
+pub fn encode(session: &[Stamped]) -> String {
+    session.iter().map(|stamped| encode_line(stamped) + "\n").collect()
+}
+
+// `text` in quotes, escaped
+fn push_string(out: &mut String, text: &str) {
+    out.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            '\u{8}' => out.push_str("\\b"),
+            '\u{c}' => out.push_str("\\f"),
+            // every control character is in the basic plane, so one escape is enough
+            c if c.is_control() => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+// DECODING
+#[derive(Debug, Clone, PartialEq)]
+pub enum DecodeErrorKind {
+    // None when the line ended first
+    Expected { expected: &'static str, found: Option<char> },
+    BadEscape(String),
+    // a raw control character inside a string
+    ControlCharacter(char),
+    BadNumber(String),
+    DuplicateField(String),
+    MissingField(&'static str),
+    // `found` is the value as it was written for numbers and strings, or what it is for anything else
+    WrongType { field: &'static str, expected: &'static str, found: String },
+    UnknownEvent(String),
+    TrailingCharacters(String),
+    // arrays and objects inside each other more than MAX_NESTING deep
+    TooDeep,
+}
+
+impl fmt::Display for DecodeErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            DecodeErrorKind::Expected { expected, found: Some(c) } => write!(f, "expected {} but found `{}`", expected, c),
+            DecodeErrorKind::Expected { expected, found: None } => write!(f, "expected {} but the line ended", expected),
+            DecodeErrorKind::BadEscape(ref escape) => write!(f, "`{}` is not a valid escape", escape),
+            DecodeErrorKind::ControlCharacter(c) =>
+                write!(f, "control character U+{:04X} in a string needs escaping", c as u32),
+            DecodeErrorKind::BadNumber(ref number) => write!(f, "`{}` is not a number", number),
+            DecodeErrorKind::DuplicateField(ref field) => write!(f, "the field `{}` appears twice", field),
+            DecodeErrorKind::MissingField(field) => write!(f, "missing the field `{}`", field),
+            DecodeErrorKind::WrongType { field, expected, ref found } =>
+                write!(f, "{} should be {}, not {}", field, expected, found),
+            DecodeErrorKind::UnknownEvent(ref name) => write!(f, "unknown event `{}`", name),
+            DecodeErrorKind::TrailingCharacters(ref rest) => write!(f, "unexpected `{}` after the object", rest),
+            DecodeErrorKind::TooDeep => write!(f, "values nested more than {} deep", MAX_NESTING),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DecodeError {
+    // both counting from 1; the column is in chars
+    pub line: usize,
+    pub column: usize,
+    pub kind: DecodeErrorKind,
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}, column {}: {}", self.line, self.column, self.kind)
+    }
+}
+
+impl error::Error for DecodeError {}
+
+// an error and the byte offset in the line where it is
+type Located = (usize, DecodeErrorKind);
+
+// Any JSON value, though only the scalars ever make it into an event
+#[derive(Debug, Clone, PartialEq)]
+enum Json {
+    Null,
+    Bool(bool),
+    // as written, for the field to parse as the type it needs
+    Number(String),
+    Str(String),
+    Array(Vec<Json>),
+    // each field with the offset of its value
+    Object(Vec<(String, usize, Json)>),
+}
+
+impl Json {
+    // for WrongType
+    fn describe(&self) -> String {
+        match *self {
+            Json::Null => "null".to_owned(),
+            Json::Bool(b) => b.to_string(),
+            Json::Number(ref number) => number.clone(),
+            Json::Str(ref s) => format!("{:?}", s),
+            Json::Array(_) => "an array".to_owned(),
+            Json::Object(_) => "an object".to_owned(),
+        }
+    }
+}
+
+// Unknown fields can hold anything and are read like any other value, recursively; this keeps a line of brackets
+// from running the reader out of stack
+const MAX_NESTING: usize = 128;
+
+struct Reader<'a> {
+    line: &'a str,
+    // in bytes
+    position: usize,
+    // how many arrays and objects the reader is inside, not counting the event itself
+    depth: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn peek(&self) -> Option<char> {
+        self.line[self.position..].chars().next()
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.position += c.len_utf8();
+        Some(c)
+    }
+
+    fn skip_whitespace(&mut self) {
+        while let Some(' ') | Some('\t') | Some('\r') | Some('\n') = self.peek() {
+            self.position += 1;
+        }
+    }
+
+    fn expected(&self, expected: &'static str) -> Located {
+        (self.position, DecodeErrorKind::Expected { expected, found: self.peek() })
+    }
+
+    fn expect(&mut self, c: char, expected: &'static str) -> Result<(), Located> {
+        if self.peek() == Some(c) {
+            self.position += 1;
+            Ok(())
+        } else {
+            Err(self.expected(expected))
+        }
+    }
+
+    fn value(&mut self) -> Result<Json, Located> {
+        match self.peek() {
+            Some(c @ '{') | Some(c @ '[') => {
+                if self.depth == MAX_NESTING {
+                    return Err((self.position, DecodeErrorKind::TooDeep));
+                }
+                self.depth += 1;
+                let value = if c == '{' { self.object() } else { self.array() };
+                self.depth -= 1;
+                value
+            },
+            Some('"') => self.string().map(Json::Str),
+            Some('-') | Some('0'..='9') => self.number(),
+            _ => {
+                for &(word, ref value) in &[("null", Json::Null), ("true", Json::Bool(true)), ("false", Json::Bool(false))] {
+                    if self.line[self.position..].starts_with(word) {
+                        self.position += word.len();
+                        return Ok(value.clone());
+                    }
+                }
+                Err(self.expected("a value"))
+            },
+        }
+    }
+
+    fn object(&mut self) -> Result<Json, Located> {
+        self.expect('{', "`{`")?;
+        let mut fields = Vec::new();
+        self.skip_whitespace();
+        if self.peek() == Some('}') {
+            self.position += 1;
+            return Ok(Json::Object(fields));
+        }
+        loop {
+            self.skip_whitespace();
+            if self.peek() != Some('"') {
+                return Err(self.expected("a field name"));
+            }
+            let name = self.string()?;
+            self.skip_whitespace();
+            self.expect(':', "`:`")?;
+            self.skip_whitespace();
+            let start = self.position;
+            fields.push((name, start, self.value()?));
+            self.skip_whitespace();
+            match self.peek() {
+                Some(',') => self.position += 1,
+                Some('}') => {
+                    self.position += 1;
+                    return Ok(Json::Object(fields));
+                },
+                _ => return Err(self.expected("`,` or `}`")),
+            }
+        }
+    }
+
+    fn array(&mut self) -> Result<Json, Located> {
+        self.expect('[', "`[`")?;
+        let mut values = Vec::new();
+        self.skip_whitespace();
+        if self.peek() == Some(']') {
+            self.position += 1;
+            return Ok(Json::Array(values));
+        }
+        loop {
+            self.skip_whitespace();
+            values.push(self.value()?);
+            self.skip_whitespace();
+            match self.peek() {
+                Some(',') => self.position += 1,
+                Some(']') => {
+                    self.position += 1;
+                    return Ok(Json::Array(values));
+                },
+                _ => return Err(self.expected("`,` or `]`")),
+            }
+        }
+    }
+
+    // JSON is stricter about numbers than Rust's parsers, which would take `+1` or `1.`
+    fn number(&mut self) -> Result<Json, Located> {
+        let start = self.position;
+        while let Some('-') | Some('+') | Some('.') | Some('e') | Some('E') | Some('0'..='9') = self.peek() {
+            self.position += 1;
+        }
+        let number = &self.line[start..self.position];
+        let digits = number.trim_start_matches('-');
+        // a leading zero only on its own, and digits on both sides of a point
+        let leading_zero = digits.starts_with('0') && digits[1..].starts_with(|c: char| c.is_ascii_digit());
+        let bare_point = number.ends_with('.') || number.contains(".e") || number.contains(".E");
+        let valid = digits.starts_with(|c: char| c.is_ascii_digit()) && !leading_zero && !bare_point
+            && number.parse::<f64>().is_ok();
+        if valid {
+            Ok(Json::Number(number.to_owned()))
+        } else {
+            Err((start, DecodeErrorKind::BadNumber(number.to_owned())))
+        }
+    }
+
+    fn string(&mut self) -> Result<String, Located> {
+        self.expect('"', "`\"`")?;
+        let mut s = String::new();
+        loop {
+            let start = self.position;
+            match self.bump() {
+                None => return Err(self.expected("`\"`")),
+                Some('"') => return Ok(s),
+                Some('\\') => s.push(self.escape(start)?),
+                Some(c) if c < ' ' => return Err((start, DecodeErrorKind::ControlCharacter(c))),
+                Some(c) => s.push(c),
+            }
+        }
+    }
+
+    // after the backslash at `start`
+    fn escape(&mut self, start: usize) -> Result<char, Located> {
+        let c = match self.bump() {
+            Some('"') => '"',
+            Some('\\') => '\\',
+            Some('/') => '/',
+            Some('b') => '\u{8}',
+            Some('f') => '\u{c}',
+            Some('n') => '\n',
+            Some('r') => '\r',
+            Some('t') => '\t',
+            Some('u') => {
+                let unit = self.hex(start)?;
+                let code = match unit {
+                    // a high surrogate, which a low one has to follow
+                    0xd800..=0xdbff => {
+                        if !self.line[self.position..].starts_with("\\u") {
+                            return Err(self.bad_escape(start));
+                        }
+                        self.position += 2;
+                        let low = self.hex(start)?;
+                        if !(0xdc00..=0xdfff).contains(&low) {
+                            return Err(self.bad_escape(start));
+                        }
+                        0x10000 + ((unit - 0xd800) << 10) + (low - 0xdc00)
+                    },
+                    0xdc00..=0xdfff => return Err(self.bad_escape(start)),
+                    unit => unit,
+                };
+                // everything outside the surrogates is a char
+                return Ok(char::from_u32(code).unwrap());
+            },
+            _ => return Err(self.bad_escape(start)),
+        };
+        Ok(c)
+    }
+
+    // the four hex digits of a `\u` escape
+    fn hex(&mut self, start: usize) -> Result<u32, Located> {
+        let mut unit = 0;
+        for _ in 0..4 {
+            match self.peek().and_then(|c| c.to_digit(16)) {
+                Some(digit) => {
+                    unit = unit * 16 + digit;
+                    self.position += 1;
+                },
+                None => {
+                    self.bump();
+                    return Err(self.bad_escape(start));
+                },
+            }
+        }
+        Ok(unit)
+    }
+
+    // the escape from its backslash up to the char that made it bad
+    fn bad_escape(&self, start: usize) -> Located {
+        (start, DecodeErrorKind::BadEscape(self.line[start..self.position].to_owned()))
+    }
+}
+
+// The fields of an event's object; `end` is the offset after it, where missing fields are reported
+struct Fields<'a> {
+    fields: &'a [(String, usize, Json)],
+    end: usize,
+}
+
+impl<'a> Fields<'a> {
+    fn get(&self, field: &'static str) -> Result<(&'a Json, usize), Located> {
+        self.fields.iter()
+            .find(|&(name, _, _)| name == field)
+            .map(|&(_, position, ref value)| (value, position))
+            .ok_or((self.end, DecodeErrorKind::MissingField(field)))
+    }
+
+    fn string(&self, field: &'static str) -> Result<(&'a str, usize), Located> {
+        match self.get(field)? {
+            (Json::Str(s), position) => Ok((s, position)),
+            (value, position) => Err(wrong_type(position, field, "a string", value)),
+        }
+    }
+
+    fn integer<T: ::std::str::FromStr>(&self, field: &'static str, expected: &'static str) -> Result<T, Located> {
+        let (value, position) = self.get(field)?;
+        let parsed = match *value {
+            Json::Number(ref number) => number.parse().ok(),
+            _ => None,
+        };
+        parsed.ok_or_else(|| wrong_type(position, field, expected, value))
+    }
+}
+
+fn wrong_type(position: usize, field: &'static str, expected: &'static str, value: &Json) -> Located {
+    (position, DecodeErrorKind::WrongType { field, expected, found: value.describe() })
+}
+
+// One line of a session; the error is on line 1
+pub fn decode_line(line: &str) -> Result<Stamped, DecodeError> {
+    read_line(line).map_err(|(position, kind)| DecodeError {
+        line: 1,
+        column: line[..position].chars().count() + 1,
+        kind,
+    })
+}
+
+fn read_line(line: &str) -> Result<Stamped, Located> {
+    let mut reader = Reader { line, position: 0, depth: 0 };
+    reader.skip_whitespace();
+    if reader.peek() != Some('{') {
+        return Err(reader.expected("`{`"));
+    }
+    let fields = match reader.object()? {
+        Json::Object(fields) => fields,
+        _ => unreachable!(),
+    };
+    let end = reader.position;
+    reader.skip_whitespace();
+    if reader.position < line.len() {
+        return Err((reader.position, DecodeErrorKind::TrailingCharacters(line[reader.position..].to_owned())));
+    }
+    for (index, &(ref name, position, _)) in fields.iter().enumerate() {
+        if fields[..index].iter().any(|field| field.0 == *name) {
+            return Err((position, DecodeErrorKind::DuplicateField(name.clone())));
+        }
+    }
+
+    let fields = Fields { fields: &fields, end };
+    let at = Duration::from_micros(fields.integer("at_us", "a whole number of microseconds")?);
+    let (name, position) = fields.string("event")?;
+    let kind = Kind::ALL.iter().cloned().find(|kind| kind.name() == name)
+        .ok_or_else(|| (position, DecodeErrorKind::UnknownEvent(name.to_owned())))?;
+    let event = match kind {
+        Kind::PageLoad => WebEvent::PageLoad,
+        Kind::PageUnload => WebEvent::PageUnload,
+        Kind::KeyPress => {
+            let (key, position) = fields.string("key")?;
+            let mut chars = key.chars();
+            match (chars.next(), chars.next()) {
+                (Some(c), None) => WebEvent::KeyPress(c),
+                _ => return Err(wrong_type(position, "key", "a single char", &Json::Str(key.to_owned()))),
+            }
+        },
+        Kind::Paste => WebEvent::Paste(fields.string("text")?.0.to_owned()),
+        Kind::Click => WebEvent::Click {
+            x: fields.integer("x", "a 64-bit integer")?,
+            y: fields.integer("y", "a 64-bit integer")?,
+        },
+    };
+    Ok(Stamped { at, event })
+}
+
+// A whole session, skipping blank lines; stops at the first bad line
+pub fn decode(text: &str) -> Result<Vec<Stamped>, DecodeError> {
+    let mut session = Vec::new();
+    for (index, line) in text.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        session.push(decode_line(line).map_err(|e| DecodeError { line: index + 1, ..e })?);
+    }
+    Ok(session)
+}
+
+// CLOCKS
+// Where recording and replaying get the time from, and how replaying waits
+pub trait Clock {
+    // the time since some fixed start
+    fn now(&self) -> Duration;
+    fn sleep(&mut self, duration: Duration);
+}
+
+// The real time, since the clock was made
+#[derive(Debug, Clone, Copy)]
+pub struct SystemClock {
+    start: Instant,
+}
+
+impl SystemClock {
+    pub fn new() -> SystemClock {
+        SystemClock { start: Instant::now() }
+    }
+}
+
+impl Default for SystemClock {
+    fn default() -> SystemClock {
+        SystemClock::new()
+    }
+}
+
+impl Clock for SystemClock {
+    fn now(&self) -> Duration {
+        self.start.elapsed()
+    }
+
+    fn sleep(&mut self, duration: Duration) {
+        thread::sleep(duration)
+    }
+}
+
+// A clock that only moves when it's slept or set, for replaying without waiting while keeping track of how long it
+// would have taken
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ManualClock {
+    pub now: Duration,
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> Duration {
+        self.now
+    }
+
+    fn sleep(&mut self, duration: Duration) {
+        self.now += duration;
+    }
+}
+
+// RECORDING
+pub struct Recorder<C = SystemClock> {
+    clock: C,
+    // the clock's time when recording started
+    start: Duration,
+    pub session: Vec<Stamped>,
+}
+
+impl Recorder {
+    pub fn new() -> Recorder {
+        Recorder::with_clock(SystemClock::new())
+    }
+}
+
+impl Default for Recorder {
+    fn default() -> Recorder {
+        Recorder::new()
+    }
+}
+
+impl<C: Clock> Recorder<C> {
+    pub fn with_clock(clock: C) -> Recorder<C> {
+        let start = clock.now();
+        Recorder { clock, start, session: Vec::new() }
+    }
+
+    // stamps the event with the time since recording started
+    pub fn record(&mut self, event: WebEvent) -> &Stamped {
+        let at = self.clock.now().saturating_sub(self.start);
+        self.session.push(Stamped { at, event });
+        self.session.last().unwrap()
+    }
+
+    pub fn clock(&mut self) -> &mut C {
+        &mut self.clock
+    }
+}
+
+// REPLAYING
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Pace {
+    // with the gaps between events as they were recorded
+    RealTime,
+    // the gaps divided by the speed, so 2.0 plays twice as fast
+    Speed(f64),
+    // no waiting at all: the caller decides when the next event happens by asking for it
+    LockStep,
+}
+
+pub struct Replayer<'a> {
+    session: &'a [Stamped],
+    pace: Pace,
+    // the index of the next event to play
+    next: usize,
+}
+
+impl<'a> Replayer<'a> {
+    // Panics if a Speed isn't a positive finite number
+    pub fn new(session: &'a [Stamped], pace: Pace) -> Replayer<'a> {
+        if let Pace::Speed(speed) = pace {
+            assert!(speed.is_finite() && speed > 0.0, "a replay speed has to be positive and finite, not {}", speed);
+        }
+        Replayer { session, pace, next: 0 }
+    }
+
+    pub fn remaining(&self) -> usize {
+        self.session.len() - self.next
+    }
+
+    // How long after the session's first event this one is played; None in lock-step. The session's own start
+    // isn't waited for, so a session whose first event came 5s in starts playing straight away.
+    pub fn due(&self, stamped: &Stamped) -> Option<Duration> {
+        let origin = self.session.first().map_or(Duration::from_secs(0), |first| first.at);
+        // an event stamped before the first one is due straight away
+        let offset = stamped.at.saturating_sub(origin);
+        match self.pace {
+            Pace::RealTime => Some(offset),
+            Pace::Speed(speed) => Some(offset.div_f64(speed)),
+            Pace::LockStep => None,
+        }
+    }
+
+    // the next event without waiting for it, whatever the pace
+    pub fn step(&mut self) -> Option<&'a Stamped> {
+        let stamped = self.session.get(self.next)?;
+        self.next += 1;
+        Some(stamped)
+    }
+
+    // Plays the rest of the session on the given clock, waiting until each event is due before handing it to the
+    // handler. The first event is played straight away, even after some steps. Returns how many were played.
+    pub fn play_with<C: Clock, F: FnMut(&Stamped)>(&mut self, clock: &mut C, mut handler: F) -> usize {
+        let start = clock.now();
+        // where the clock's start is in the session's time
+        let offset = match self.session.get(self.next) {
+            Some(first) => self.due(first),
+            None => return 0,
+        };
+        let mut played = 0;
+        while let Some(stamped) = self.session.get(self.next) {
+            if let (Some(due), Some(offset)) = (self.due(stamped), offset) {
+                // measuring from the start rather than the last event keeps slow handlers from adding up
+                let wait = due.saturating_sub(offset).saturating_sub(clock.now().saturating_sub(start));
+                if wait > Duration::from_secs(0) {
+                    clock.sleep(wait);
+                }
+            }
+            self.next += 1;
+            handler(stamped);
+            played += 1;
+        }
+        played
+    }
+
+    // Plays the rest of the session in real time, or sped up or slowed down by the pace
+    pub fn play<F: FnMut(&Stamped)>(&mut self, handler: F) -> usize {
+        self.play_with(&mut SystemClock::new(), handler)
+    }
+}
+
+// Every remaining event without waiting, as in lock-step
+impl<'a> Iterator for Replayer<'a> {
+    type Item = &'a Stamped;
+
+    fn next(&mut self) -> Option<&'a Stamped> {
+        self.step()
+    }
+}
+
+// DIFFING
+// The first place two sessions stop agreeing; indices count from 0
+#[derive(Debug, Clone, PartialEq)]
+pub enum Divergence {
+    // different events at the same index
+    Event { index: usize, left: Stamped, right: Stamped },
+    // the same event, further apart in time than the tolerance
+    Time { index: usize, left: Duration, right: Duration },
+    // one session goes on after the other ends, the events up to there all agreeing
+    Length { left: usize, right: usize },
+}
+
+impl Divergence {
+    // the index of the first event that differs
+    pub fn index(&self) -> usize {
+        match *self {
+            Divergence::Event { index, .. } | Divergence::Time { index, .. } => index,
+            Divergence::Length { left, right } => left.min(right),
+        }
+    }
+}
+
+impl fmt::Display for Divergence {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Divergence::Event { index, ref left, ref right } =>
+                write!(f, "event {}: the left session has `{}` and the right `{}`", index + 1, left, right),
+            Divergence::Time { index, left, right } =>
+                write!(f, "event {}: the left session has it at {:?}s and the right at {:?}s", index + 1,
+                       left.as_secs_f64(), right.as_secs_f64()),
+            Divergence::Length { left, right } =>
+                write!(f, "event {}: the left session has {} events and the right {}", self.index() + 1, left, right),
+        }
+    }
+}
+
+// None when both sessions have the same events, each within `tolerance` of its time in the other
+pub fn diverge(left: &[Stamped], right: &[Stamped], tolerance: Duration) -> Option<Divergence> {
+    for (index, (l, r)) in left.iter().zip(right).enumerate() {
+        if l.event != r.event {
+            return Some(Divergence::Event { index, left: l.clone(), right: r.clone() });
+        }
+        if l.at.abs_diff(r.at) > tolerance {
+            return Some(Divergence::Time { index, left: l.at, right: r.at });
+        }
+    }
+    if left.len() != right.len() {
+        return Some(Divergence::Length { left: left.len(), right: right.len() });
+    }
+    None
+}
+
+// A session saved as JSON Lines and read back, replayed, and compared with a slower copy missing its last event
+pub fn run(out: &mut impl Write) -> io::Result<()> {
+    let recorded = parse_log("0 page_load\n0.5 key_press é\n2 paste \"quoted\"\n3 page_unload\n").unwrap();
+    let text = encode(&recorded);
+    write!(out, "{}", text)?;
+    let decoded = decode(&text).unwrap();
+    writeln!(out, "decoded the same session: {}", decoded == recorded)?;
+
+    // a clock that doesn't really wait shows how long replaying at double speed would take
+    let mut clock = ManualClock::default();
+    let played = Replayer::new(&decoded, Pace::Speed(2.0)).play_with(&mut clock, |_| {});
+    writeln!(out, "replayed {} events at double speed in {:?}", played, clock.now)?;
+
+    let mut replayed = decoded.clone();
+    replayed[1].at += Duration::from_millis(30);
+    replayed.pop();
+    for &tolerance in &[Duration::from_millis(10), Duration::from_millis(50)] {
+        match diverge(&recorded, &replayed, tolerance) {
+            Some(divergence) => writeln!(out, "within {:?}: {}", tolerance, divergence)?,
+            None => writeln!(out, "within {:?}: the sessions match", tolerance)?,
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn at(millis: u64, event: WebEvent) -> Stamped {
+        Stamped { at: Duration::from_millis(millis), event }
+    }
+
+    fn session() -> Vec<Stamped> {
+        vec![
+            at(0, WebEvent::PageLoad),
+            at(1500, WebEvent::Click { x: 20, y: -80 }),
+            at(1500, WebEvent::KeyPress('é')),
+            at(1600, WebEvent::KeyPress('"')),
+            at(1700, WebEvent::KeyPress('😀')),
+            at(2250, WebEvent::Paste("two\nlines, \"quoted\"\t\\ \u{1}\u{7f}".to_owned())),
+            at(4000, WebEvent::PageUnload),
+        ]
+    }
+
+    #[test]
+    fn round_trip() {
+        let text = encode(&session());
+        assert_eq!(text.lines().nth(1), Some(r#"{"at_us":1500000,"event":"click","x":20,"y":-80}"#));
+        assert_eq!(text.lines().nth(2), Some(r#"{"at_us":1500000,"event":"key_press","key":"é"}"#));
+        assert_eq!(text.lines().nth(5),
+                   Some(r#"{"at_us":2250000,"event":"paste","text":"two\nlines, \"quoted\"\t\\ \u0001\u007f"}"#));
+        assert_eq!(text.lines().count(), 7);
+        assert_eq!(decode(&text), Ok(session()));
+
+        let fine = Stamped { at: Duration::new(1, 123_456_789), event: WebEvent::PageLoad };
+        assert_eq!(decode_line(&encode_line(&fine)).unwrap().at, Duration::new(1, 123_456_000));
+    }
+
+    #[test]
+    fn other_writers() {
+        let line = |line: &str| decode_line(line).unwrap().event;
+        assert_eq!(line(r#" { "event" : "key_press", "key" : "\u00e9", "at_us" : 5 } "#), WebEvent::KeyPress('é'));
+        assert_eq!(line(r#"{"at_us":5,"event":"key_press","key":"\ud83d\ude00"}"#), WebEvent::KeyPress('😀'));
+        assert_eq!(line(r#"{"at_us":5,"event":"paste","text":"a\/b\u0000\b\f\r"}"#),
+                   WebEvent::Paste("a/b\u{0}\u{8}\u{c}\r".to_owned()));
+        // unknown fields of any shape are skipped
+        assert_eq!(line(r#"{"at_us":0,"event":"click","x":1,"y":2,"tab":{"id":[1,-2.5e3,null,true]},"z":"x"}"#),
+                   WebEvent::Click { x: 1, y: 2 });
+        assert_eq!(decode("\n{\"at_us\":0,\"event\":\"page_load\"}\r\n\n"), Ok(vec![at(0, WebEvent::PageLoad)]));
+    }
+
+    #[test]
+    fn decode_errors() {
+        let error = |line: &str| {
+            let e = decode_line(line).unwrap_err();
+            (e.column, e.kind)
+        };
+        assert_eq!(error(""), (1, DecodeErrorKind::Expected { expected: "`{`", found: None }));
+        assert_eq!(error(r#"{"at_us" 0}"#), (10, DecodeErrorKind::Expected { expected: "`:`", found: Some('0') }));
+        assert_eq!(error(r#"{"at_us":0,}"#), (12, DecodeErrorKind::Expected { expected: "a field name", found: Some('}') }));
+        assert_eq!(error(r#"{"at_us":01}"#), (10, DecodeErrorKind::BadNumber("01".to_owned())));
+        assert_eq!(error(r#"{"at_us":0,"event":"page_load"} x"#),
+                   (33, DecodeErrorKind::TrailingCharacters("x".to_owned())));
+        assert_eq!(error(r#"{"at_us":0,"event":"paste","text":"é\q"}"#),
+                   (37, DecodeErrorKind::BadEscape("\\q".to_owned())));
+        assert_eq!(error(r#"{"at_us":0,"event":"key_press","key":"\ud83d"}"#),
+                   (39, DecodeErrorKind::BadEscape("\\ud83d".to_owned())));
+        assert_eq!(error(r#"{"at_us":0,"event":"key_press","key":"\ude00"}"#).1,
+                   DecodeErrorKind::BadEscape("\\ude00".to_owned()));
+        assert_eq!(error(r#"{"at_us":0,"event":"key_press","key":"\u00g0"}"#).1,
+                   DecodeErrorKind::BadEscape("\\u00g".to_owned()));
+        assert_eq!(error("{\"at_us\":0,\"event\":\"paste\",\"text\":\"a\tb\"}").1, DecodeErrorKind::ControlCharacter('\t'));
+        assert_eq!(error(r#"{"at_us":0,"event":"paste","text":"unterminated}"#).1,
+                   DecodeErrorKind::Expected { expected: "`\"`", found: None });
+        assert_eq!(error(r#"{"at_us":0,"event":"page_load","at_us":1}"#),
+                   (40, DecodeErrorKind::DuplicateField("at_us".to_owned())));
+        assert_eq!(error(r#"{"at_us":0,"event":"click","x":1}"#), (34, DecodeErrorKind::MissingField("y")));
+        assert_eq!(error(r#"{"at_us":0,"event":"scroll"}"#).1, DecodeErrorKind::UnknownEvent("scroll".to_owned()));
+        // an unknown field nested as deep as allowed is skipped; one more level, or a great many, is an error
+        let nested = |depth: usize| format!(r#"{{"at_us":0,"event":"page_load","extra":{}{}}}"#,
+                                            "[".repeat(depth), "]".repeat(depth));
+        assert!(decode_line(&nested(MAX_NESTING)).is_ok());
+        assert_eq!(error(&nested(MAX_NESTING + 1)), (40 + MAX_NESTING, DecodeErrorKind::TooDeep));
+        assert_eq!(error(&nested(200_000)).1, DecodeErrorKind::TooDeep);
+
+        let message = |line: &str| decode_line(line).unwrap_err().to_string();
+        assert_eq!(message(r#"{"at_us":-5,"event":"page_load"}"#),
+                   "line 1, column 10: at_us should be a whole number of microseconds, not -5");
+        assert_eq!(message(r#"{"at_us":0,"event":"click","x":1.5,"y":0}"#),
+                   "line 1, column 32: x should be a 64-bit integer, not 1.5");
+        assert_eq!(message(r#"{"at_us":0,"event":"key_press","key":"xy"}"#),
+                   "line 1, column 38: key should be a single char, not \"xy\"");
+        assert_eq!(message(r#"{"at_us":0,"event":null}"#), "line 1, column 20: event should be a string, not null");
+        assert_eq!(decode("{\"at_us\":0,\"event\":\"page_load\"}\n\n[]").unwrap_err().to_string(),
+                   "line 3, column 1: expected `{` but found `[`");
+    }
+
+    // sleeps like a ManualClock and remembers when it woke
+    #[derive(Default)]
+    struct Wakes {
+        clock: ManualClock,
+        wakes: Vec<Duration>,
+    }
+
+    impl Clock for Wakes {
+        fn now(&self) -> Duration {
+            self.clock.now
+        }
+
+        fn sleep(&mut self, duration: Duration) {
+            self.clock.sleep(duration);
+            self.wakes.push(self.clock.now);
+        }
+    }
+
+    #[test]
+    fn replay() {
+        let session = vec![at(1000, WebEvent::PageLoad), at(1500, WebEvent::KeyPress('a')),
+                           at(1500, WebEvent::KeyPress('b')), at(3000, WebEvent::PageUnload)];
+        let millis = |millis: &[u64]| millis.iter().map(|&m| Duration::from_millis(m)).collect::<Vec<_>>();
+
+        let mut clock = Wakes::default();
+        let mut played = Vec::new();
+        assert_eq!(Replayer::new(&session, Pace::RealTime).play_with(&mut clock, |s| played.push(s.clone())), 4);
+        assert_eq!(played, session);
+        assert_eq!(clock.wakes, millis(&[500, 2000]));
+
+        let mut clock = Wakes::default();
+        Replayer::new(&session, Pace::Speed(4.0)).play_with(&mut clock, |_| {});
+        assert_eq!(clock.wakes, millis(&[125, 500]));
+
+        // resuming after a step starts straight away and keeps the gaps from there
+        let mut clock = Wakes::default();
+        let mut replayer = Replayer::new(&session, Pace::Speed(0.5));
+        assert_eq!(replayer.step(), Some(&session[0]));
+        assert_eq!(replayer.remaining(), 3);
+        assert_eq!(replayer.play_with(&mut clock, |_| {}), 3);
+        assert_eq!(clock.wakes, millis(&[3000]));
+        assert_eq!(replayer.play_with(&mut clock, |_| {}), 0);
+
+        let mut clock = Wakes::default();
+        let mut replayer = Replayer::new(&session, Pace::LockStep);
+        assert_eq!(replayer.due(&session[3]), None);
+        assert_eq!(replayer.play_with(&mut clock, |_| {}), 4);
+        assert!(clock.wakes.is_empty());
+        assert_eq!(Replayer::new(&session, Pace::LockStep).map(|s| s.at).collect::<Vec<_>>(), millis(&[1000, 1500, 1500, 3000]));
+    }
+
+    #[test]
+    #[should_panic(expected = "a replay speed has to be positive")]
+    fn zero_speed() {
+        Replayer::new(&[], Pace::Speed(0.0));
+    }
+
+    #[test]
+    fn record() {
+        let mut recorder = Recorder::with_clock(ManualClock { now: Duration::from_secs(10) });
+        recorder.record(WebEvent::PageLoad);
+        recorder.clock().sleep(Duration::from_millis(250));
+        assert_eq!(recorder.record(WebEvent::KeyPress('x')).at, Duration::from_millis(250));
+        assert_eq!(recorder.session, vec![at(0, WebEvent::PageLoad), at(250, WebEvent::KeyPress('x'))]);
+    }
+
+    #[test]
+    fn divergence() {
+        let left = session();
+        let zero = Duration::from_secs(0);
+        assert_eq!(diverge(&left, &left, zero), None);
+
+        let mut right = left.clone();
+        right[3].at += Duration::from_millis(20);
+        assert_eq!(diverge(&left, &right, Duration::from_millis(20)), None);
+        assert_eq!(diverge(&left, &right, zero).unwrap().to_string(),
+                   "event 4: the left session has it at 1.6s and the right at 1.62s");
+
+        right[2].event = WebEvent::KeyPress('e');
+        let divergence = diverge(&left, &right, zero).unwrap();
+        assert_eq!(divergence.index(), 2);
+        assert_eq!(divergence.to_string(), "event 3: the left session has `1.5 key_press é` and the right `1.5 key_press e`");
+
+        let divergence = diverge(&left, &left[..5], zero).unwrap();
+        assert_eq!(divergence, Divergence::Length { left: 7, right: 5 });
+        assert_eq!(divergence.to_string(), "event 6: the left session has 7 events and the right 5");
+    }
+}
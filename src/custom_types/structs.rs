@@ -0,0 +1,72 @@
+// 3 types of structs: tuple structs(named tuples), class C structs and unit structs
+
+use std::io::{self, Write};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Person<'a> {
+    pub name: &'a str,
+    pub age: u8,
+}
+
+// a unit struct
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Nil;
+
+// a tuple struct
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Pair(pub i32, pub f32);
+
+// a struct with 2 fields
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Point {
+    pub x: f64,
+    pub y: f64,
+}
+
+// structs can be used as fields for other structs
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Rectangle {
+    pub p1: Point,
+    pub p2: Point,
+}
+
+pub fn run(out: &mut impl Write) -> io::Result<()> {
+    let name = "Peter";
+    let age = 27;
+    let peter = Person { name, age };
+
+    writeln!(out, "{:?}", peter)?;
+
+    // Instantiate a point
+    let point = Point { x: 0.3, y: 0.4 };
+
+    // access the fields of a point
+    writeln!(out, "point coordinates: ({}, {})", point.x, point.y)?;
+
+    // make a new point by using y from the old point
+    let new_point = Point { x: 0.1, ..point };
+    writeln!(out, "second point: ({}, {})", new_point.x, new_point.y)?;
+
+    // Destructure the point using a `let` binding
+    let Point { x: my_x, y: my_y } = point; // my_x and my_y are new variables created here that contain the components of the struct
+    writeln!(out, "my_x is {}; my_y is {}", my_x, my_y)?;
+
+    let _rectangle = Rectangle {
+        p1: Point { x: my_y, y: my_x },
+        p2: point,
+    };
+
+    // Instantiate a unit struct
+    let _nil = Nil;
+
+    // Instantiate a tuple struct
+    let pair = Pair(1, 0.1);
+
+    writeln!(out, "Pair contains {:?} and {:?}", pair.0, pair.1)?;
+
+    // Destructure a tuple struct
+    let Pair(integer, decimal) = pair;
+    writeln!(out, "pair contains: {:?} and {:?}", integer, decimal)?;
+
+    Ok(())
+}
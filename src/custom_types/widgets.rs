@@ -0,0 +1,431 @@
+// WIDGETS
+// A Tree of rectangular widgets that a click can be routed to, the way a browser routes one through the DOM:
+// - a click goes to the topmost widget under it, where siblings stack by their z and then by the order they were
+//   added, and a widget that clips hides the parts of its children outside its bounds
+// - handlers see the click on its way down from the root to the target (capture), at the target, and on its way
+//   back up (bubble), and any of them can stop it there
+// - the pointer hovers the target and every widget above it; moving it reports the widgets it leaves and enters
+//
+// Bounds are in the coordinates of the page, not the parent, and are half-open: a widget from (0, 0) to (10, 5)
+// holds the cells with x in 0..10 and y in 0..5, so widgets side by side never both contain a click.
+
+use std::cell::RefCell;
+use std::io::{self, Write};
+
+use custom_types::{Point, Rectangle, WebEvent};
+
+// The corners don't have to be in any order
+impl Rectangle {
+    pub fn min(&self) -> Point {
+        Point { x: self.p1.x.min(self.p2.x), y: self.p1.y.min(self.p2.y) }
+    }
+
+    pub fn max(&self) -> Point {
+        Point { x: self.p1.x.max(self.p2.x), y: self.p1.y.max(self.p2.y) }
+    }
+
+    // with the left and top edges inside and the right and bottom ones outside
+    pub fn contains(&self, point: Point) -> bool {
+        let (min, max) = (self.min(), self.max());
+        min.x <= point.x && point.x < max.x && min.y <= point.y && point.y < max.y
+    }
+
+    // None when they don't overlap, or only touch
+    pub fn intersection(&self, other: &Rectangle) -> Option<Rectangle> {
+        let min = Point { x: self.min().x.max(other.min().x), y: self.min().y.max(other.min().y) };
+        let max = Point { x: self.max().x.min(other.max().x), y: self.max().y.min(other.max().y) };
+        if min.x < max.x && min.y < max.y { Some(Rectangle { p1: min, p2: max }) } else { None }
+    }
+}
+
+// An index into a Tree; only meaningful for the tree that handed it out
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct WidgetId(usize);
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Widget {
+    pub name: String,
+    pub bounds: Rectangle,
+    // above the siblings with a lower z
+    pub z: i32,
+    // whether the children are cut off at the bounds
+    pub clip: bool,
+    parent: Option<WidgetId>,
+    // in the order they were added
+    children: Vec<WidgetId>,
+}
+
+impl Widget {
+    // at z 0, clipping its children
+    pub fn new(name: &str, bounds: Rectangle) -> Widget {
+        Widget { name: name.to_owned(), bounds, z: 0, clip: true, parent: None, children: Vec::new() }
+    }
+
+    pub fn z(mut self, z: i32) -> Widget {
+        self.z = z;
+        self
+    }
+
+    pub fn clip(mut self, clip: bool) -> Widget {
+        self.clip = clip;
+        self
+    }
+
+    pub fn parent(&self) -> Option<WidgetId> {
+        self.parent
+    }
+
+    pub fn children(&self) -> &[WidgetId] {
+        &self.children
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Phase {
+    // on an ancestor of the target, on the way down
+    Capture,
+    Target,
+    // on an ancestor of the target, on the way up
+    Bubble,
+}
+
+// What a click handler is told
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Click {
+    pub point: Point,
+    pub target: WidgetId,
+    // the widget whose handler this is
+    pub current: WidgetId,
+    pub phase: Phase,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Propagation {
+    Continue,
+    // no handler after this one sees the click, not even another one on the same widget
+    Stop,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Hover {
+    Enter(WidgetId),
+    Leave(WidgetId),
+}
+
+// What handling an event did
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Routed {
+    // the widgets left, innermost first, then the ones entered, outermost first
+    pub hover: Vec<Hover>,
+    // the widget clicked, if it was a click on a widget
+    pub target: Option<WidgetId>,
+}
+
+type ClickHandler<'a> = Box<dyn FnMut(&Click) -> Propagation + 'a>;
+
+pub struct Tree<'a> {
+    // indexed by WidgetId, the root first
+    widgets: Vec<Widget>,
+    // each with its widget and whether it handles the capture phase rather than the bubble one, in the order they
+    // were added
+    handlers: Vec<(WidgetId, bool, ClickHandler<'a>)>,
+    // the innermost widget under the pointer
+    hovered: Option<WidgetId>,
+}
+
+impl<'a> Tree<'a> {
+    // a tree with only its root, a widget covering the whole page
+    pub fn new(bounds: Rectangle) -> Tree<'a> {
+        Tree { widgets: vec![Widget::new("root", bounds)], handlers: Vec::new(), hovered: None }
+    }
+
+    pub fn root(&self) -> WidgetId {
+        WidgetId(0)
+    }
+
+    // Adds the widget above its siblings with the same z
+    pub fn add(&mut self, parent: WidgetId, widget: Widget) -> WidgetId {
+        let id = WidgetId(self.widgets.len());
+        self.widgets.push(Widget { parent: Some(parent), children: Vec::new(), ..widget });
+        self.widgets[parent.0].children.push(id);
+        id
+    }
+
+    pub fn widget(&self, id: WidgetId) -> &Widget {
+        &self.widgets[id.0]
+    }
+
+    pub fn widget_mut(&mut self, id: WidgetId) -> &mut Widget {
+        &mut self.widgets[id.0]
+    }
+
+    pub fn name(&self, id: WidgetId) -> &str {
+        &self.widget(id).name
+    }
+
+    // the first widget added with the name
+    pub fn find(&self, name: &str) -> Option<WidgetId> {
+        self.widgets.iter().position(|widget| widget.name == name).map(WidgetId)
+    }
+
+    // from the root down to the widget
+    pub fn path(&self, id: WidgetId) -> Vec<WidgetId> {
+        let mut path = vec![id];
+        while let Some(parent) = self.widget(*path.last().unwrap()).parent {
+            path.push(parent);
+        }
+        path.reverse();
+        path
+    }
+
+    // the widget's children from the bottom to the top
+    pub fn stacking(&self, id: WidgetId) -> Vec<WidgetId> {
+        let mut children = self.widget(id).children.clone();
+        // a stable sort leaves children with the same z in the order they were added
+        children.sort_by_key(|&child| self.widget(child).z);
+        children
+    }
+
+    // The part of the widget that isn't clipped away by the widgets above it; None if none of it is visible
+    pub fn visible(&self, id: WidgetId) -> Option<Rectangle> {
+        let path = self.path(id);
+        path[..path.len() - 1].iter()
+            .filter(|&&ancestor| self.widget(ancestor).clip)
+            .try_fold(self.widget(id).bounds, |visible, &ancestor| visible.intersection(&self.widget(ancestor).bounds))
+    }
+
+    // The topmost widget under the point
+    pub fn hit(&self, point: Point) -> Option<WidgetId> {
+        self.hit_in(self.root(), point)
+    }
+
+    fn hit_in(&self, id: WidgetId, point: Point) -> Option<WidgetId> {
+        let widget = self.widget(id);
+        let inside = widget.bounds.contains(point);
+        if widget.clip && !inside {
+            return None;
+        }
+        // an unclipped child sticking out of its parent can still be hit there
+        self.stacking(id).into_iter().rev()
+            .find_map(|child| self.hit_in(child, point))
+            .or(if inside { Some(id) } else { None })
+    }
+
+    pub fn on_capture<F: FnMut(&Click) -> Propagation + 'a>(&mut self, id: WidgetId, handler: F) {
+        self.handlers.push((id, true, Box::new(handler)));
+    }
+
+    pub fn on_bubble<F: FnMut(&Click) -> Propagation + 'a>(&mut self, id: WidgetId, handler: F) {
+        self.handlers.push((id, false, Box::new(handler)));
+    }
+
+    // Routes a click to the topmost widget under it: the capture handlers of its ancestors from the root down,
+    // then its own capture and bubble handlers, then the bubble handlers of its ancestors back up. Returns the
+    // target, or None when the click missed every widget.
+    pub fn click(&mut self, point: Point) -> Option<WidgetId> {
+        let target = self.hit(point)?;
+        let path = self.path(target);
+        let ancestors = &path[..path.len() - 1];
+        let route = ancestors.iter().map(|&id| (id, Phase::Capture, true))
+            .chain(vec![(target, Phase::Target, true), (target, Phase::Target, false)])
+            .chain(ancestors.iter().rev().map(|&id| (id, Phase::Bubble, false)));
+        for (current, phase, capture) in route {
+            let click = Click { point, target, current, phase };
+            for &mut (id, handles_capture, ref mut handler) in &mut self.handlers {
+                if id == current && handles_capture == capture && handler(&click) == Propagation::Stop {
+                    return Some(target);
+                }
+            }
+        }
+        Some(target)
+    }
+
+    pub fn hovered(&self) -> Option<WidgetId> {
+        self.hovered
+    }
+
+    // Moves the pointer to the point, or off the page with None
+    pub fn hover(&mut self, point: Option<Point>) -> Vec<Hover> {
+        let hovered = point.and_then(|point| self.hit(point));
+        let before = self.hovered.map_or(Vec::new(), |id| self.path(id));
+        let after = hovered.map_or(Vec::new(), |id| self.path(id));
+        let common = before.iter().zip(&after).take_while(|&(a, b)| a == b).count();
+        self.hovered = hovered;
+        before[common..].iter().rev().map(|&id| Hover::Leave(id))
+            .chain(after[common..].iter().map(|&id| Hover::Enter(id)))
+            .collect()
+    }
+
+    // A click moves the pointer there and clicks, and unloading the page takes the pointer off it
+    pub fn handle(&mut self, event: &WebEvent) -> Routed {
+        match *event {
+            WebEvent::Click { x, y } => {
+                let point = Point { x: x as f64, y: y as f64 };
+                let hover = self.hover(Some(point));
+                Routed { hover, target: self.click(point) }
+            },
+            WebEvent::PageUnload => Routed { hover: self.hover(None), target: None },
+            WebEvent::PageLoad | WebEvent::KeyPress(_) | WebEvent::Paste(_) => Routed::default(),
+        }
+    }
+}
+
+// A dialog with an ok button over a background: clicks on the button, on the background, and a page unload
+pub fn run(out: &mut impl Write) -> io::Result<()> {
+    let rect = |x1, y1, x2, y2| Rectangle { p1: Point { x: x1, y: y1 }, p2: Point { x: x2, y: y2 } };
+    // both handlers write to the log, so it needs shared mutable access
+    let log = RefCell::new(Vec::new());
+    {
+        let mut tree = Tree::new(rect(0.0, 0.0, 80.0, 24.0));
+        let root = tree.root();
+        let dialog = tree.add(root, Widget::new("dialog", rect(20.0, 5.0, 60.0, 15.0)).z(1));
+        let ok = tree.add(dialog, Widget::new("ok", rect(45.0, 12.0, 55.0, 14.0)));
+        tree.add(root, Widget::new("background", rect(0.0, 0.0, 80.0, 24.0)));
+        tree.on_capture(dialog, |click| {
+            log.borrow_mut().push(format!("the dialog sees a click in the {:?} phase", click.phase));
+            Propagation::Continue
+        });
+        tree.on_bubble(ok, |click| {
+            log.borrow_mut().push(format!("ok is clicked at ({}, {}) in the {:?} phase", click.point.x, click.point.y, click.phase));
+            if click.phase == Phase::Target { Propagation::Stop } else { Propagation::Continue }
+        });
+
+        for event in &[WebEvent::Click { x: 50, y: 13 }, WebEvent::Click { x: 5, y: 5 }, WebEvent::PageUnload] {
+            let routed = tree.handle(event);
+            let hover: Vec<String> = routed.hover.iter()
+                .map(|hover| match *hover {
+                    Hover::Enter(id) => format!("+{}", tree.name(id)),
+                    Hover::Leave(id) => format!("-{}", tree.name(id)),
+                })
+                .collect();
+            let target = routed.target.map_or("nothing", |id| tree.name(id));
+            writeln!(out, "{:?} hits {}, hover {}", event, target, hover.join(" "))?;
+        }
+    }
+    for line in log.borrow().iter() {
+        writeln!(out, "{}", line)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::cell::RefCell;
+
+    fn rect(x1: f64, y1: f64, x2: f64, y2: f64) -> Rectangle {
+        Rectangle { p1: Point { x: x1, y: y1 }, p2: Point { x: x2, y: y2 } }
+    }
+
+    fn point(x: f64, y: f64) -> Point {
+        Point { x, y }
+    }
+
+    // an 80 by 24 screen with a sidebar, a body with a button in it, a popup over both and a badge sticking out of
+    // the unclipped button
+    fn screen<'a>() -> Tree<'a> {
+        let mut tree = Tree::new(rect(0.0, 0.0, 80.0, 24.0));
+        let root = tree.root();
+        tree.add(root, Widget::new("sidebar", rect(0.0, 0.0, 20.0, 24.0)));
+        let body = tree.add(root, Widget::new("body", rect(20.0, 0.0, 80.0, 24.0)));
+        let button = tree.add(body, Widget::new("button", rect(30.0, 10.0, 40.0, 12.0)).clip(false));
+        tree.add(button, Widget::new("badge", rect(38.0, 8.0, 42.0, 10.0)));
+        // added before the body's sibling content, but on top of it
+        tree.add(root, Widget::new("popup", rect(10.0, 2.0, 30.0, 6.0)).z(1));
+        tree.add(body, Widget::new("overflow", rect(75.0, 20.0, 90.0, 30.0)));
+        tree
+    }
+
+    #[test]
+    fn rectangles() {
+        let r = rect(10.0, 5.0, 0.0, 0.0);
+        assert_eq!((r.min(), r.max()), (point(0.0, 0.0), point(10.0, 5.0)));
+        assert!(r.contains(point(0.0, 0.0)) && r.contains(point(9.5, 4.9)));
+        assert!(!r.contains(point(10.0, 2.0)) && !r.contains(point(2.0, 5.0)));
+        assert_eq!(r.intersection(&rect(5.0, 1.0, 20.0, 20.0)), Some(rect(5.0, 1.0, 10.0, 5.0)));
+        assert_eq!(r.intersection(&rect(10.0, 0.0, 20.0, 5.0)), None);
+    }
+
+    #[test]
+    fn hit_testing() {
+        let tree = screen();
+        let hit = |x, y| tree.hit(point(x, y)).map(|id| tree.name(id).to_owned());
+        assert_eq!(hit(5.0, 10.0).unwrap(), "sidebar");
+        assert_eq!(hit(50.0, 10.0).unwrap(), "body");
+        assert_eq!(hit(30.0, 10.0).unwrap(), "button");
+        assert_eq!(hit(40.0, 10.0).unwrap(), "body");
+        // the popup's z puts it over the sidebar and body
+        assert_eq!(hit(15.0, 3.0).unwrap(), "popup");
+        assert_eq!(hit(25.0, 3.0).unwrap(), "popup");
+        // the badge is outside the button, which doesn't clip, but inside the body, which does
+        assert_eq!(hit(41.0, 9.0).unwrap(), "badge");
+        assert_eq!(hit(79.0, 23.0).unwrap(), "overflow");
+        assert_eq!(hit(85.0, 25.0), None);
+
+        let overflow = tree.find("overflow").unwrap();
+        assert_eq!(tree.visible(overflow), Some(rect(75.0, 20.0, 80.0, 24.0)));
+        assert_eq!(tree.visible(tree.find("badge").unwrap()), Some(rect(38.0, 8.0, 42.0, 10.0)));
+        let names: Vec<&str> = tree.stacking(tree.root()).into_iter().map(|id| tree.name(id)).collect();
+        assert_eq!(names, vec!["sidebar", "body", "popup"]);
+        assert_eq!(tree.widget(overflow).parent(), tree.find("body"));
+    }
+
+    #[test]
+    fn capture_and_bubble() {
+        let log = RefCell::new(Vec::new());
+        let stop_at = RefCell::new(None);
+        let names: Vec<String> = {
+            let tree = screen();
+            (0..7).map(|id| tree.name(WidgetId(id)).to_owned()).collect()
+        };
+        let mut tree = screen();
+        for (id, name) in names.iter().enumerate() {
+            let id = WidgetId(id);
+            let handler = |capture| {
+                let (log, stop_at) = (&log, &stop_at);
+                move |click: &Click| {
+                    let entry = format!("{} {} {:?}", if capture { "capture" } else { "bubble" }, name, click.phase);
+                    log.borrow_mut().push(entry.clone());
+                    if *stop_at.borrow() == Some(entry) { Propagation::Stop } else { Propagation::Continue }
+                }
+            };
+            tree.on_capture(id, handler(true));
+            tree.on_bubble(id, handler(false));
+        }
+
+        assert_eq!(tree.click(point(31.0, 11.0)), tree.find("button"));
+        assert_eq!(*log.borrow(), vec![
+            "capture root Capture", "capture body Capture",
+            "capture button Target", "bubble button Target",
+            "bubble body Bubble", "bubble root Bubble",
+        ]);
+
+        log.borrow_mut().clear();
+        *stop_at.borrow_mut() = Some("capture body Capture".to_owned());
+        assert_eq!(tree.click(point(31.0, 11.0)), tree.find("button"));
+        assert_eq!(*log.borrow(), vec!["capture root Capture", "capture body Capture"]);
+
+        log.borrow_mut().clear();
+        assert_eq!(tree.click(point(100.0, 100.0)), None);
+        assert!(log.borrow().is_empty());
+    }
+
+    #[test]
+    fn hovering() {
+        let mut tree = screen();
+        let id = |name| tree.find(name).unwrap();
+        let (root, body, button, badge, sidebar) = (id("root"), id("body"), id("button"), id("badge"), id("sidebar"));
+
+        assert_eq!(tree.hover(Some(point(31.0, 11.0))), vec![Hover::Enter(root), Hover::Enter(body), Hover::Enter(button)]);
+        assert_eq!(tree.hover(Some(point(32.0, 11.0))), vec![]);
+        assert_eq!(tree.hover(Some(point(41.0, 9.0))), vec![Hover::Enter(badge)]);
+        let routed = tree.handle(&WebEvent::Click { x: 5, y: 5 });
+        assert_eq!(routed.hover, vec![Hover::Leave(badge), Hover::Leave(button), Hover::Leave(body), Hover::Enter(sidebar)]);
+        assert_eq!(routed.target, Some(sidebar));
+        assert_eq!(tree.hovered(), Some(sidebar));
+        assert_eq!(tree.handle(&WebEvent::KeyPress('x')), Routed::default());
+        assert_eq!(tree.handle(&WebEvent::PageUnload).hover, vec![Hover::Leave(sidebar), Hover::Leave(root)]);
+        assert_eq!(tree.hovered(), None);
+    }
+}
@@ -0,0 +1,5 @@
+// ERROR HANDLING
+// Error handling is the process of handling the possibility of failure: panic, Option and Result.
+
+pub mod option_unwrap;
+pub mod result;
@@ -0,0 +1,156 @@
+// the matches below are kept to compare them with the combinators
+#![allow(clippy::manual_map)]
+
+#![allow(dead_code)]
+
+// ERROR HANDLING
+// panic is used for tests and dealing with unrecoverable errors.
+// It prints an error message, starts unwinding the task and usually exists the program.
+
+use std::io::{self, Write};
+
+pub fn give_princes(out: &mut impl Write, gift: &str) -> io::Result<()> {
+    if gift == "snake" { panic!("AAAaaaa"); }
+    writeln!(out, "I love {}", gift)?;
+    Ok(())
+}
+
+pub fn run(out: &mut impl Write) -> io::Result<()> {
+    give_princes(out, "teddy bear")?;
+    // give_princes("snake"); // will panic here
+
+    // OPTION AND UNWRAP
+    let food = Some("cabbage");
+    let snake = Some("snake");
+    let void = None;
+    give_commoner(out, food)?;
+    give_commoner(out, snake)?;
+    give_commoner(out, void)?; // continues the program because of explicit handling
+    let food = Some("cabbage");
+    give_princess1(out, food)?;
+    // give_princess1(void); // will panic here
+
+    // COMBINATORS: map
+    let apple = Some(Food::Apple);
+    let carrot = Some(Food::Carrot);
+    let potato = None;
+    let cooked_apple = cook(chop(peel(apple)));
+    let cooked_carrot = cook(chop(peel(carrot)));
+    let cooked_potato = process(potato);
+    eat(out, cooked_apple)?;
+    eat(out, cooked_carrot)?;
+    eat(out, cooked_potato)?;
+
+    // COMBINATORS: and_then
+    let (cordon_bleu, steak, sushi) = (Food1::CordonBleu, Food1::Steak, Food1::Sushi);
+    eat1(out, cordon_bleu, Day::Monday)?;
+    eat1(out, steak, Day::Tuesday)?;
+    eat1(out, sushi, Day::Wednesday)
+}
+
+// OPTION AND UNWRAP
+// Option is an enum used when absence is a possibility: It has 2 elements: Some(T) and None
+// The cases can be handled via a match or implicitly with unwrap. Implicit handling will either return
+// the inner element or panic.
+pub fn give_commoner(out: &mut impl Write, gift: Option<&str>) -> io::Result<()> { // handling errors using a match
+    match gift {
+        Some("snake") => writeln!(out, "Found a snake")?,
+        Some(inner) => writeln!(out, "{}?", inner)?,
+        None => writeln!(out, "no gift")?,
+    }
+
+    Ok(())
+}
+
+pub fn give_princess1(out: &mut impl Write, gift: Option<&str>) -> io::Result<()> {
+    let inside = gift.unwrap(); // unwrap returns a panic when it receives a None.
+    if inside == "snake" { panic!("AAAAAAaa"); }
+    writeln!(out, "I love {}", inside)?;
+    Ok(())
+}
+
+// COMBINATORS: map
+// Heavy usage of match may be tedius. Combinators can be used to manage control flow in a modular fashion.
+// Option has a built in method called map(), a combinator for mapping Some -> Some and None -> None.
+// Multiple map calls can be chained together for more flexibility
+#[derive(Debug)] pub enum Food { Apple, Carrot, Potato }
+
+#[derive(Debug)] pub struct Peeled(pub Food);
+#[derive(Debug)] pub struct Chopped(pub Food);
+#[derive(Debug)] pub struct Cooked(pub Food);
+pub fn peel(food: Option<Food>) -> Option<Peeled> {
+    match food {
+        Some(food) => Some(Peeled(food)),
+        None => None,
+    }
+}
+
+pub fn chop(peeled: Option<Peeled>) -> Option<Chopped> {
+    match peeled {
+        Some(Peeled(food)) => Some(Chopped(food)),
+        None => None,
+    }
+}
+
+pub fn cook(chopped: Option<Chopped>) -> Option<Cooked> { // cook food; using map instead of match for case handling
+    chopped.map(|Chopped(food)| Cooked(food))
+}
+
+pub fn process(food: Option<Food>) -> Option<Cooked> {
+    food.map(Peeled)
+        .map(|Peeled(f)| Chopped(f))
+        .map(|Chopped(f)| Cooked(f))
+}
+
+pub fn eat(out: &mut impl Write, food: Option<Cooked>) -> io::Result<()> {
+    match food {
+        Some(food) => writeln!(out, "I love {:?}", food)?,
+        None => writeln!(out, "Not edible")?,
+    }
+
+    Ok(())
+}
+
+// COMBINATORS: and_then
+// Using map() on a function that returns an Option<T> results in netsted Option<Option<T>>
+// and_then works as a flatmap and calls its function input with the wrapped value and returns the result.
+// If the Option is None, it returns None instead.
+#[derive(Debug)] pub enum Food1 { CordonBleu, Steak, Sushi }
+#[derive(Debug)] pub enum Day { Monday, Tuesday, Wednesday }
+
+pub fn have_ingredients(food: Food1) -> Option<Food1> {
+    match food {
+        Food1::Sushi => None, // we don't have the ingredients to make sushi
+        _ => Some(food),
+    }
+}
+
+pub fn have_recipe(food: Food1) -> Option<Food1> {
+    match food {
+        Food1::CordonBleu => None, // we don't have a recipe for Cordon Bleu
+        _ => Some(food),
+    }
+}
+
+pub fn cookable_v1(food: Food1) -> Option<Food1> {
+    match have_ingredients(food) {
+        None => None,
+        Some(food) => match have_recipe(food) {
+            None => None,
+            Some(food) => Some(food),
+        },
+    }
+}
+
+pub fn cookable_v2(food: Food1) -> Option<Food1> { // v1 can be conveniently rewritten more compactly
+    have_ingredients(food).and_then(have_recipe)
+}
+
+pub fn eat1(out: &mut impl Write, food: Food1, day: Day) -> io::Result<()> {
+    match cookable_v2(food) {
+        Some(food) => writeln!(out, "On {:?} we get to eat {:?}", day, food)?,
+        None => writeln!(out, "We don't get to eat on {:?}", day)?,
+    }
+
+    Ok(())
+}
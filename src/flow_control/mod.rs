@@ -0,0 +1,7 @@
+// FLOW CONTROL
+// An essential part of any programming language are ways to modify control flow: if/else, loop, while, for and match.
+
+pub mod loops;
+pub mod matching;
+
+pub use self::matching::Color;
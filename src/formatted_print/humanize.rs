@@ -0,0 +1,301 @@
+// HUMANIZE
+// Wrappers that print numbers the way a person would write them in a report:
+//   Separated(1_234_567)    1,234,567
+//   Bytes(1536)             1.5 KiB
+//   Si(0.000001)            1 µ
+//   Ordinal(31)             31st
+//   Elapsed(duration)       1 hour, 2 minutes and 3 seconds
+// They implement Display, so they work with every formatting macro. Width, fill and alignment pad the whole text,
+// numbers to the right by default; precision is the number of decimals (or of units, for Elapsed).
+
+use std::fmt;
+use std::time;
+
+// The integer and float primitives. `render` prints the value with its own Display so that an f32 keeps its
+// shorter digits.
+pub trait Number: Copy {
+    fn render(self, precision: Option<usize>) -> String;
+    fn to_f64(self) -> f64;
+}
+
+pub trait Integer: Number {
+    fn to_i128(self) -> i128;
+}
+
+macro_rules! integer {
+    ($($t:ty),*) => {
+        $(
+            impl Number for $t {
+                fn render(self, _: Option<usize>) -> String { self.to_string() }
+                fn to_f64(self) -> f64 { self as f64 }
+            }
+
+            impl Integer for $t {
+                fn to_i128(self) -> i128 { self as i128 }
+            }
+        )*
+    };
+}
+
+integer!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, usize);
+
+macro_rules! float {
+    ($($t:ty),*) => {
+        $(
+            impl Number for $t {
+                fn render(self, precision: Option<usize>) -> String {
+                    match precision {
+                        Some(precision) => format!("{:.*}", precision, self),
+                        None => self.to_string(),
+                    }
+                }
+                fn to_f64(self) -> f64 { self as f64 }
+            }
+        )*
+    };
+}
+
+float!(f32, f64);
+
+// Like Formatter::pad, but without cutting the text short at the precision, which means something else here
+fn pad(f: &mut fmt::Formatter, s: &str, default: fmt::Alignment) -> fmt::Result {
+    let len = s.chars().count();
+    let padding = match f.width() {
+        Some(width) if width > len => width - len,
+        _ => return f.write_str(s),
+    };
+    let (before, after) = match f.align().unwrap_or(default) {
+        fmt::Alignment::Left => (0, padding),
+        fmt::Alignment::Center => (padding / 2, padding - padding / 2),
+        fmt::Alignment::Right => (padding, 0),
+    };
+    let fill = f.fill().to_string();
+    write!(f, "{}{}{}", fill.repeat(before), s, fill.repeat(after))
+}
+
+// puts a comma between every group of three digits in the integer part of `number`
+fn separate(number: &str) -> String {
+    let (sign, rest) = if let Some(rest) = number.strip_prefix('-') { ("-", rest) } else { ("", number) };
+    let (digits, fraction) = match rest.find(|c: char| !c.is_ascii_digit()) {
+        Some(end) => rest.split_at(end),
+        None => (rest, ""),
+    };
+    let mut separated = String::with_capacity(number.len() + digits.len() / 3);
+    separated.push_str(sign);
+    for (i, digit) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i) % 3 == 0 {
+            separated.push(',');
+        }
+        separated.push(digit);
+    }
+    separated.push_str(fraction);
+    separated
+}
+
+// prints `value` with `precision` decimals, or with at most `default` decimals and no trailing zeros
+fn decimals(value: f64, precision: Option<usize>, default: usize) -> String {
+    match precision {
+        Some(precision) => format!("{:.*}", precision, value),
+        None => {
+            let s = format!("{:.*}", default, value);
+            if s.contains('.') { s.trim_end_matches('0').trim_end_matches('.').to_owned() } else { s }
+        },
+    }
+}
+
+// SEPARATED
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Separated<T>(pub T);
+
+impl<T: Number> fmt::Display for Separated<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        pad(f, &separate(&self.0.render(f.precision())), fmt::Alignment::Right)
+    }
+}
+
+// BYTES
+// Sizes in the binary units, each 1024 times the one before
+pub const BINARY_UNITS: [&str; 7] = ["B", "KiB", "MiB", "GiB", "TiB", "PiB", "EiB"];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Bytes<T>(pub T);
+
+impl<T: Integer> fmt::Display for Bytes<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let bytes = self.0.to_i128();
+        let mut size = bytes.unsigned_abs() as f64;
+        let mut unit = 0;
+        while size >= 1024.0 && unit < BINARY_UNITS.len() - 1 {
+            size /= 1024.0;
+            unit += 1;
+        }
+        let mut number = if unit == 0 { bytes.unsigned_abs().to_string() } else { decimals(size, f.precision(), 1) };
+        // rounding can carry into the next unit, e.g. 1023.96 KiB
+        if unit > 0 && unit < BINARY_UNITS.len() - 1 && number.parse::<f64>().is_ok_and(|n| n >= 1024.0) {
+            unit += 1;
+            number = decimals(size / 1024.0, f.precision(), 1);
+        }
+        let sign = if bytes < 0 { "-" } else { "" };
+        pad(f, &format!("{}{} {}", sign, number, BINARY_UNITS[unit]), fmt::Alignment::Right)
+    }
+}
+
+// SI
+// The SI prefixes from 10^-24 to 10^24, a factor of 1000 apart; the empty one is 10^0
+pub const SI_PREFIXES: [&str; 17] = ["y", "z", "a", "f", "p", "n", "µ", "m", "", "k", "M", "G", "T", "P", "E", "Z", "Y"];
+const SI_UNIT: usize = 8;
+
+// Prints the value scaled to the nearest prefix, e.g. `1.5 k`, with at most two decimals by default. A value
+// without a prefix has no trailing space, so a unit can follow either directly: `{} s` / `{}s`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Si<T>(pub T);
+
+impl<T: Number> fmt::Display for Si<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let value = self.0.to_f64();
+        if value == 0.0 || !value.is_finite() {
+            return pad(f, &decimals(value, f.precision(), 2), fmt::Alignment::Right);
+        }
+        let mut prefix = (SI_UNIT as i32 + (value.abs().log10() / 3.0).floor() as i32)
+            .max(0)
+            .min(SI_PREFIXES.len() as i32 - 1) as usize;
+        let scale = |prefix: usize| value / 1000f64.powi(prefix as i32 - SI_UNIT as i32);
+        let mut number = decimals(scale(prefix), f.precision(), 2);
+        // rounding can carry into the next prefix, e.g. 999.999 to 1000
+        if prefix < SI_PREFIXES.len() - 1 && number.trim_start_matches('-').parse::<f64>().is_ok_and(|n| n >= 1000.0) {
+            prefix += 1;
+            number = decimals(scale(prefix), f.precision(), 2);
+        }
+        let text = match SI_PREFIXES[prefix] {
+            "" => number,
+            symbol => format!("{} {}", number, symbol),
+        };
+        pad(f, &text, fmt::Alignment::Right)
+    }
+}
+
+// ORDINAL
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Ordinal<T>(pub T);
+
+pub fn ordinal_suffix(n: i128) -> &'static str {
+    let n = n.unsigned_abs();
+    // 11th, 12th and 13th rather than 11st, 12nd and 13rd
+    match (n % 10, n % 100) {
+        (_, 11..=13) => "th",
+        (1, _) => "st",
+        (2, _) => "nd",
+        (3, _) => "rd",
+        _ => "th",
+    }
+}
+
+impl<T: Integer> fmt::Display for Ordinal<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let n = self.0.to_i128();
+        pad(f, &format!("{}{}", n, ordinal_suffix(n)), fmt::Alignment::Right)
+    }
+}
+
+// ELAPSED
+// A duration spelled out in days, hours, minutes, seconds and milliseconds, leaving out the units that are zero.
+// The precision is the most units shown, counting from the largest, e.g. `{:.2}` prints `1 hour and 2 minutes`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Elapsed(pub time::Duration);
+
+const TIME_UNITS: [(&str, u128); 5] = [
+    ("day", 86_400_000),
+    ("hour", 3_600_000),
+    ("minute", 60_000),
+    ("second", 1_000),
+    ("millisecond", 1),
+];
+
+impl fmt::Display for Elapsed {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut millis = self.0.as_millis();
+        let mut parts = Vec::new();
+        for &(unit, size) in TIME_UNITS.iter() {
+            let count = millis / size;
+            millis %= size;
+            if count > 0 {
+                parts.push(format!("{} {}{}", count, unit, if count == 1 { "" } else { "s" }));
+            }
+        }
+        if let Some(precision) = f.precision() {
+            parts.truncate(precision.max(1));
+        }
+        let text = match parts.len() {
+            0 => "0 seconds".to_owned(),
+            1 => parts.remove(0),
+            n => format!("{} and {}", parts[..n - 1].join(", "), parts[n - 1]),
+        };
+        pad(f, &text, fmt::Alignment::Left)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn separated() {
+        assert_eq!(Separated(1_234_567).to_string(), "1,234,567");
+        assert_eq!(Separated(-1_234i16).to_string(), "-1,234");
+        assert_eq!(Separated(255u8).to_string(), "255");
+        assert_eq!(Separated(u64::MAX).to_string(), "18,446,744,073,709,551,615");
+        assert_eq!(Separated(i8::MIN).to_string(), "-128");
+        assert_eq!(format!("{:.2}", Separated(1234.5f64)), "1,234.50");
+        assert_eq!(Separated(0.1f32).to_string(), "0.1");
+        assert_eq!(format!("[{:>10}]", Separated(12345)), "[    12,345]");
+        assert_eq!(format!("[{:*<10}]", Separated(12345)), "[12,345****]");
+    }
+
+    #[test]
+    fn bytes() {
+        assert_eq!(Bytes(0u8).to_string(), "0 B");
+        assert_eq!(Bytes(1023).to_string(), "1023 B");
+        assert_eq!(Bytes(1024).to_string(), "1 KiB");
+        assert_eq!(Bytes(1536).to_string(), "1.5 KiB");
+        assert_eq!(format!("{:.2}", Bytes(1536)), "1.50 KiB");
+        assert_eq!(Bytes(1024 * 1024 - 1).to_string(), "1 MiB");
+        assert_eq!(Bytes(-2048).to_string(), "-2 KiB");
+        assert_eq!(Bytes(u64::MAX).to_string(), "16 EiB");
+        assert_eq!(format!("{:>9}|", Bytes(1536)), "  1.5 KiB|");
+    }
+
+    #[test]
+    fn si() {
+        assert_eq!(Si(0.000001).to_string(), "1 µ");
+        assert_eq!(Si(1500).to_string(), "1.5 k");
+        assert_eq!(Si(12).to_string(), "12");
+        assert_eq!(Si(0).to_string(), "0");
+        assert_eq!(Si(-0.0025f32).to_string(), "-2.5 m");
+        assert_eq!(Si(999_999).to_string(), "1 M");
+        assert_eq!(format!("{:.3}", Si(1_234_567u64)), "1.235 M");
+        assert_eq!(Si(1e30).to_string(), "1000000 Y");
+        assert_eq!(Si(f64::INFINITY).to_string(), "inf");
+        assert_eq!(format!("{:<8}|", Si(1500)), "1.5 k   |");
+    }
+
+    #[test]
+    fn ordinal() {
+        let ordinals: Vec<String> = [0, 1, 2, 3, 4, 11, 12, 13, 21, 22, 23, 31, 101, 111, 112, 1003]
+            .iter().map(|&n| Ordinal(n).to_string()).collect();
+        assert_eq!(ordinals, ["0th", "1st", "2nd", "3rd", "4th", "11th", "12th", "13th", "21st", "22nd", "23rd",
+                              "31st", "101st", "111th", "112th", "1003rd"]);
+        assert_eq!(Ordinal(-1i8).to_string(), "-1st");
+        assert_eq!(format!("{:>5}", Ordinal(2u64)), "  2nd");
+    }
+
+    #[test]
+    fn elapsed() {
+        assert_eq!(Elapsed(Duration::from_secs(0)).to_string(), "0 seconds");
+        assert_eq!(Elapsed(Duration::from_millis(1500)).to_string(), "1 second and 500 milliseconds");
+        assert_eq!(Elapsed(Duration::from_secs(3723)).to_string(), "1 hour, 2 minutes and 3 seconds");
+        assert_eq!(Elapsed(Duration::from_secs(2 * 86_400 + 60)).to_string(), "2 days and 1 minute");
+        assert_eq!(format!("{:.2}", Elapsed(Duration::from_secs(3723))), "1 hour and 2 minutes");
+        assert_eq!(format!("{:12}|", Elapsed(Duration::from_secs(5))), "5 seconds   |");
+    }
+}
@@ -0,0 +1,88 @@
+// FORMATTED PRINT
+// Printing is handled by a series of macros defined in std::fmt: format!, print!, println!, eprint! and eprintln!
+// All of them parse the format string at compile time and check the arguments against it.
+
+pub mod runtime;
+pub mod table;
+pub mod pretty;
+pub mod humanize;
+
+use std::io::{self, Write};
+use std::time::Duration;
+
+use custom_types::{Person, Point, Rectangle};
+
+pub use self::runtime::{format_runtime, Arg, Args, FormatError};
+pub use self::table::{Cell, Columns, Style, Table};
+pub use self::pretty::{Doc, Pretty, PrettyDebug};
+pub use self::humanize::{Bytes, Elapsed, Ordinal, Separated, Si};
+
+#[derive(Debug)]
+pub struct Structure(pub i32);
+
+// Deep wraps a Structure but doesn't derive Debug, so it can't be printed with {:?}
+pub struct Deep(pub Structure);
+
+pub fn run(out: &mut impl Write) -> io::Result<()> {
+    writeln!(out, "{} days", 31)?;
+
+    writeln!(out, "{0}, this is {1}. {1}, this is {0}", "Alice", "Bob")?; // using positional arguments
+
+    // Special formatting can be specified after a ":"
+    writeln!(out, "{} of {:b} people know binary, the other half doesn't", 1, 2)?;
+
+    // We can right align text with a specific width
+    writeln!(out, "{number:>width$}", number=1, width=6)?;
+
+    // we can pad numbers with extra zeros
+    writeln!(out, "{number:>0width$}", number=1, width=6)?;
+
+    // all std library types are printable with :? too
+    writeln!(out, "{:?} months in a year", 12)?;
+    writeln!(out, "{1:?} {0:?} is the {actor:?} name.",
+        "Slater",
+        "Christian",
+        actor="actor's")?;
+
+    writeln!(out, "Now {:?} will print!", Structure(3))?;
+
+    // Rust also provides pretty printing with :#?
+    let name = "Peter";
+    let age = 27;
+    let peter = Person { name, age };
+
+    writeln!(out, "{:#?}", peter)?;
+
+    // PRETTY
+    // Pretty only breaks a value over several lines when it doesn't fit in the given width
+    writeln!(out, "{}", peter.to_pretty(80))?;
+    let rectangle = Rectangle { p1: Point { x: 0.0, y: 0.0 }, p2: Point { x: 3.0, y: 4.0 } };
+    writeln!(out, "{}", rectangle.to_pretty(40))?;
+    // and PrettyDebug does the same for anything that implements Debug
+    writeln!(out, "{}", PrettyDebug(&vec![Structure(1), Structure(2), Structure(3)]).to_pretty(20))?;
+
+    // RUNTIME FORMATTING
+    // format_runtime does the same with a template that is only known at runtime, and reports mistakes as errors
+    let template = "{0}, this is {1}. {number:>0width$}";
+    let args = Args::new().arg("Alice").arg("Bob").named("number", 1).named("width", 6);
+    match format_runtime(template, &args) {
+        Ok(formatted) => writeln!(out, "{}", formatted)?,
+        Err(e) => writeln!(out, "Error: {}", e)?,
+    }
+    if let Err(e) = format_runtime("{actor:?} {2}", &args) {
+        writeln!(out, "Error: {}", e)?;
+    }
+
+    // TABLES
+    // a list of records can be printed as a table, which works out the widths instead
+    let people = [peter, Person { name: "Christian Slater", age: 54 }];
+    Table::ascii().write(out, &people)?;
+    Table::markdown().max_width(10).write(out, &people)?;
+
+    // HUMANIZE
+    // wrappers from humanize print numbers for people rather than programs
+    writeln!(out, "{} visitors; you are the {} today; {:.1} used", Separated(1_234_567), Ordinal(31), Bytes(1536))?;
+    writeln!(out, "{}s is {}s; took {}", 0.000001, Si(0.000001), Elapsed(Duration::from_secs(3723)))?;
+
+    Ok(())
+}
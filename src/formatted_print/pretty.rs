@@ -0,0 +1,517 @@
+// PRETTY PRINTING
+// {:#?} puts every field on its own line, however small the value is. A pretty printer in the style of Wadler's
+// "A prettier printer" instead builds a document out of four pieces, and lays it out to fit a given width:
+//   text   - a string that is printed as is
+//   line   - a space if its group fits on the rest of the line, otherwise a new line
+//   nest   - indents the lines inside it
+//   group  - the unit that is either printed flat, on one line, or broken at its lines
+// A group is only broken when it doesn't fit, so `Point { x: 0.0, y: 0.0 }` stays on one line while the
+// Rectangle around it breaks.
+
+use std::fmt::{self, Debug};
+
+use custom_types::{List, Nil, Pair, Person, Point, Rectangle, Status, WebEvent, Work};
+use flow_control::matching::Color;
+use formatted_print::{Deep, Structure};
+use generics::{Container, Days, Years};
+use geometry::Triangle;
+use primitives::Matrix;
+
+// the indentation of a broken group, as with {:#?}
+pub const INDENT: usize = 4;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Doc {
+    Nil,
+    Text(String),
+    // a space when flat
+    Line,
+    // nothing when flat
+    SoftLine,
+    // text that is only printed when the enclosing group is broken, e.g. a trailing comma
+    IfBroken(String),
+    Concat(Vec<Doc>),
+    Nest(usize, Box<Doc>),
+    Group(Box<Doc>),
+}
+
+impl Doc {
+    pub fn text<S: Into<String>>(s: S) -> Doc {
+        Doc::Text(s.into())
+    }
+
+    pub fn concat(docs: Vec<Doc>) -> Doc {
+        Doc::Concat(docs)
+    }
+
+    pub fn append(self, doc: Doc) -> Doc {
+        match self {
+            Doc::Concat(mut docs) => {
+                docs.push(doc);
+                Doc::Concat(docs)
+            },
+            this => Doc::Concat(vec![this, doc]),
+        }
+    }
+
+    pub fn nest(self, indent: usize) -> Doc {
+        Doc::Nest(indent, Box::new(self))
+    }
+
+    pub fn group(self) -> Doc {
+        Doc::Group(Box::new(self))
+    }
+
+    // `open item, item close`, broken as one item per line with a trailing comma. `padded` puts spaces inside
+    // the brackets when flat, as in `Point { x: 0.0 }`
+    pub fn bracket(open: &str, items: Vec<Doc>, close: &str, padded: bool) -> Doc {
+        if items.is_empty() {
+            return Doc::text(format!("{}{}", open, close));
+        }
+        let line = || if padded { Doc::Line } else { Doc::SoftLine };
+        let mut inner = vec![line()];
+        for (i, item) in items.into_iter().enumerate() {
+            if i > 0 {
+                inner.push(Doc::text(","));
+                inner.push(Doc::Line);
+            }
+            inner.push(item);
+        }
+        inner.push(Doc::IfBroken(",".to_owned()));
+        Doc::concat(vec![Doc::text(open), Doc::concat(inner).nest(INDENT), line(), Doc::text(close)]).group()
+    }
+
+    // `Name { field: value, .. }`, or just `Name` without fields
+    pub fn record(name: &str, fields: Vec<(&str, Doc)>) -> Doc {
+        if fields.is_empty() {
+            return Doc::text(name);
+        }
+        let fields = fields.into_iter()
+            .map(|(field, value)| Doc::text(format!("{}: ", field)).append(value))
+            .collect();
+        Doc::text(format!("{} ", name)).append(Doc::bracket("{", fields, "}", true))
+    }
+
+    // `Name(value, ..)`, or just `Name` without values
+    pub fn tuple(name: &str, values: Vec<Doc>) -> Doc {
+        if values.is_empty() {
+            return Doc::text(name);
+        }
+        Doc::text(name).append(Doc::bracket("(", values, ")", false))
+    }
+
+    pub fn render(&self, width: usize) -> String {
+        let mut output = String::new();
+        let mut column = 0;
+        let mut stack = vec![(0, Mode::Break, self)];
+        while let Some((indent, mode, doc)) = stack.pop() {
+            match *doc {
+                Doc::Nil => {},
+                Doc::Text(ref s) => {
+                    output.push_str(s);
+                    column += s.chars().count();
+                },
+                Doc::IfBroken(ref s) => if mode == Mode::Break {
+                    output.push_str(s);
+                    column += s.chars().count();
+                },
+                Doc::Line | Doc::SoftLine if mode == Mode::Break => {
+                    output.push('\n');
+                    output.extend((0..indent).map(|_| ' '));
+                    column = indent;
+                },
+                Doc::Line => {
+                    output.push(' ');
+                    column += 1;
+                },
+                Doc::SoftLine => {},
+                Doc::Concat(ref docs) => stack.extend(docs.iter().rev().map(|doc| (indent, mode, doc))),
+                Doc::Nest(more, ref doc) => stack.push((indent + more, mode, doc)),
+                Doc::Group(ref doc) => {
+                    let mode = if mode == Mode::Flat || fits(width as isize - column as isize, (indent, doc), &stack) {
+                        Mode::Flat
+                    } else {
+                        Mode::Break
+                    };
+                    stack.push((indent, mode, doc));
+                },
+            }
+        }
+        output
+    }
+}
+
+// with a width, e.g. `{:40}`, renders to that width; otherwise to 80 columns
+impl fmt::Display for Doc {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.render(f.width().unwrap_or(80)))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Mode {
+    Flat,
+    Break,
+}
+
+// true if `doc` printed flat, followed by what comes after it up to the next line break, fits in `width` columns
+fn fits(mut width: isize, (indent, doc): (usize, &Doc), rest: &[(usize, Mode, &Doc)]) -> bool {
+    let mut stack = vec![(indent, Mode::Flat, doc)];
+    let mut rest = rest.iter().rev();
+    loop {
+        if width < 0 {
+            return false;
+        }
+        let (indent, mode, doc) = match stack.pop().or_else(|| rest.next().cloned()) {
+            Some(next) => next,
+            None => return true,
+        };
+        match *doc {
+            // the line ends at the first break after the group
+            Doc::Line | Doc::SoftLine if mode == Mode::Break => return true,
+            Doc::Nil | Doc::SoftLine => {},
+            Doc::Line => width -= 1,
+            Doc::Text(ref s) => width -= s.chars().count() as isize,
+            Doc::IfBroken(ref s) => if mode == Mode::Break { width -= s.chars().count() as isize },
+            Doc::Concat(ref docs) => stack.extend(docs.iter().rev().map(|doc| (indent, mode, doc))),
+            Doc::Nest(more, ref doc) => stack.push((indent + more, mode, doc)),
+            Doc::Group(ref doc) => stack.push((indent, mode, doc)),
+        }
+    }
+}
+
+// A type that can be laid out as a document
+pub trait Pretty {
+    fn pretty(&self) -> Doc;
+
+    fn to_pretty(&self, width: usize) -> String {
+        self.pretty().render(width)
+    }
+}
+
+// Primitives print as they do with {:?}
+macro_rules! pretty_as_debug {
+    ($($t:ty),*) => {
+        $(
+            impl Pretty for $t {
+                fn pretty(&self) -> Doc { Doc::text(format!("{:?}", self)) }
+            }
+        )*
+    };
+}
+
+pretty_as_debug!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize, f32, f64, bool, char, str, String);
+
+impl<T: Pretty + ?Sized> Pretty for &T {
+    fn pretty(&self) -> Doc { (**self).pretty() }
+}
+
+impl<T: Pretty + ?Sized> Pretty for Box<T> {
+    fn pretty(&self) -> Doc { (**self).pretty() }
+}
+
+impl<T: Pretty> Pretty for Option<T> {
+    fn pretty(&self) -> Doc {
+        match *self {
+            Some(ref value) => Doc::tuple("Some", vec![value.pretty()]),
+            None => Doc::text("None"),
+        }
+    }
+}
+
+impl<T: Pretty> Pretty for [T] {
+    fn pretty(&self) -> Doc {
+        Doc::bracket("[", self.iter().map(Pretty::pretty).collect(), "]", false)
+    }
+}
+
+impl<T: Pretty, const N: usize> Pretty for [T; N] {
+    fn pretty(&self) -> Doc { self[..].pretty() }
+}
+
+impl<T: Pretty> Pretty for Vec<T> {
+    fn pretty(&self) -> Doc { self.as_slice().pretty() }
+}
+
+// THE PROJECT'S TYPES
+// These lay out the same way as their Debug output would through PrettyDebug below
+
+impl Pretty for Structure {
+    fn pretty(&self) -> Doc { Doc::tuple("Structure", vec![self.0.pretty()]) }
+}
+
+// Deep has no Debug, but can still be pretty printed
+impl Pretty for Deep {
+    fn pretty(&self) -> Doc { Doc::tuple("Deep", vec![self.0.pretty()]) }
+}
+
+impl<'a> Pretty for Person<'a> {
+    fn pretty(&self) -> Doc {
+        Doc::record("Person", vec![("name", self.name.pretty()), ("age", self.age.pretty())])
+    }
+}
+
+impl Pretty for Nil {
+    fn pretty(&self) -> Doc { Doc::text("Nil") }
+}
+
+impl Pretty for Pair {
+    fn pretty(&self) -> Doc { Doc::tuple("Pair", vec![self.0.pretty(), self.1.pretty()]) }
+}
+
+impl Pretty for Point {
+    fn pretty(&self) -> Doc {
+        Doc::record("Point", vec![("x", self.x.pretty()), ("y", self.y.pretty())])
+    }
+}
+
+impl Pretty for Rectangle {
+    fn pretty(&self) -> Doc {
+        Doc::record("Rectangle", vec![("p1", self.p1.pretty()), ("p2", self.p2.pretty())])
+    }
+}
+
+impl Pretty for WebEvent {
+    fn pretty(&self) -> Doc {
+        match *self {
+            WebEvent::PageLoad => Doc::text("PageLoad"),
+            WebEvent::PageUnload => Doc::text("PageUnload"),
+            WebEvent::KeyPress(c) => Doc::tuple("KeyPress", vec![c.pretty()]),
+            WebEvent::Paste(ref s) => Doc::tuple("Paste", vec![s.pretty()]),
+            WebEvent::Click { x, y } => Doc::record("Click", vec![("x", x.pretty()), ("y", y.pretty())]),
+        }
+    }
+}
+
+impl Pretty for Status {
+    fn pretty(&self) -> Doc { Doc::text(format!("{:?}", self)) }
+}
+
+impl Pretty for Work {
+    fn pretty(&self) -> Doc { Doc::text(format!("{:?}", self)) }
+}
+
+// List prints like a slice of its elements, with its name in front
+impl<T: Pretty> Pretty for List<T> {
+    fn pretty(&self) -> Doc {
+        let elements = self.iter().map(Pretty::pretty).collect();
+        Doc::text("List ").append(Doc::bracket("[", elements, "]", false))
+    }
+}
+
+impl Pretty for Color {
+    fn pretty(&self) -> Doc {
+        let (name, a, b, c) = match *self {
+            Color::Red => return Doc::text("Red"),
+            Color::Blue => return Doc::text("Blue"),
+            Color::Green => return Doc::text("Green"),
+            Color::Rgb(a, b, c) => ("Rgb", a, b, c),
+            Color::Hsv(a, b, c) => ("Hsv", a, b, c),
+            Color::Hsl(a, b, c) => ("Hsl", a, b, c),
+            Color::Cmy(a, b, c) => ("Cmy", a, b, c),
+            Color::Cmyk(a, b, c) => ("Cmyk", a, b, c),
+        };
+        Doc::tuple(name, vec![a.pretty(), b.pretty(), c.pretty()])
+    }
+}
+
+impl<T: Pretty, const R: usize, const C: usize> Pretty for Matrix<T, R, C> {
+    fn pretty(&self) -> Doc { Doc::tuple("Matrix", vec![self.0.pretty()]) }
+}
+
+impl Pretty for Triangle {
+    fn pretty(&self) -> Doc {
+        Doc::record("Triangle", vec![("a", self.a.pretty()), ("b", self.b.pretty()), ("c", self.c.pretty())])
+    }
+}
+
+impl Pretty for Years {
+    fn pretty(&self) -> Doc { Doc::tuple("Years", vec![self.0.pretty()]) }
+}
+
+impl Pretty for Days {
+    fn pretty(&self) -> Doc { Doc::tuple("Days", vec![self.0.pretty()]) }
+}
+
+impl Pretty for Container {
+    fn pretty(&self) -> Doc { Doc::tuple("Container", vec![self.0.pretty(), self.1.pretty()]) }
+}
+
+// DEBUG ADAPTER
+// Any Debug value can be pretty printed by wrapping it: its {:?} output is split at the brackets and commas
+// that Debug puts around fields and elements, and rebuilt as a document.
+pub struct PrettyDebug<'a, T: 'a + ?Sized>(pub &'a T);
+
+impl<'a, T: Debug + ?Sized> Pretty for PrettyDebug<'a, T> {
+    fn pretty(&self) -> Doc {
+        from_debug(&format!("{:?}", self.0))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Open(char),
+    Close(char),
+    Comma,
+    Text(String),
+}
+
+fn tokenize(debug: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut text = String::new();
+    let mut chars = debug.chars();
+    while let Some(c) = chars.next() {
+        let token = match c {
+            '{' | '(' | '[' => Token::Open(c),
+            '}' | ')' | ']' => Token::Close(c),
+            ',' => Token::Comma,
+            // strings and chars are copied whole, so brackets and commas inside them aren't split on
+            '"' | '\'' => {
+                text.push(c);
+                while let Some(next) = chars.next() {
+                    text.push(next);
+                    if next == '\\' {
+                        text.extend(chars.next());
+                    } else if next == c {
+                        break;
+                    }
+                }
+                continue;
+            },
+            c => {
+                text.push(c);
+                continue;
+            },
+        };
+        if !text.is_empty() {
+            tokens.push(Token::Text(text.split_off(0)));
+        }
+        tokens.push(token);
+    }
+    if !text.is_empty() {
+        tokens.push(Token::Text(text));
+    }
+    tokens
+}
+
+fn from_debug(debug: &str) -> Doc {
+    let tokens = tokenize(debug);
+    let mut tokens = tokens.iter().peekable();
+    let mut items = Vec::new();
+    // anything left over after a stray closing bracket is kept as text
+    loop {
+        items.push(parse_item(&mut tokens));
+        match tokens.next() {
+            None => break,
+            Some(token) => items.push(Doc::text(match *token {
+                Token::Comma => ", ".to_owned(),
+                Token::Close(c) => c.to_string(),
+                _ => unreachable!("parse_item stops at commas and closing brackets"),
+            })),
+        }
+    }
+    Doc::concat(items)
+}
+
+type Tokens<'a> = ::std::iter::Peekable<::std::slice::Iter<'a, Token>>;
+
+// everything up to the next comma or closing bracket at this level
+fn parse_item(tokens: &mut Tokens) -> Doc {
+    let mut parts = Vec::new();
+    while let Some(token) = tokens.peek().cloned() {
+        match *token {
+            Token::Comma | Token::Close(_) => break,
+            Token::Text(ref text) => {
+                tokens.next();
+                // the spaces around items are put back by the layout
+                let text = if parts.is_empty() { text.trim_start() } else { text.as_str() };
+                parts.push(Doc::text(text));
+            },
+            Token::Open(open) => {
+                tokens.next();
+                let mut items = Vec::new();
+                let close = loop {
+                    let item = parse_item(tokens);
+                    if item != Doc::Concat(Vec::new()) {
+                        items.push(item);
+                    }
+                    match tokens.next() {
+                        Some(&Token::Comma) => {},
+                        Some(&Token::Close(close)) => break Some(close),
+                        _ => break None,
+                    }
+                };
+                let close = close.map(|c| c.to_string()).unwrap_or_default();
+                parts.push(Doc::bracket(&open.to_string(), items, &close, open == '{'));
+            },
+        }
+    }
+    // trim the end of the last piece of text
+    if let Some(Doc::Text(text)) = parts.last_mut() {
+        let trimmed = text.trim_end().len();
+        text.truncate(trimmed);
+    }
+    parts.retain(|part| *part != Doc::Text(String::new()));
+    Doc::concat(parts)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn rectangle() -> Rectangle {
+        Rectangle { p1: Point { x: 0.0, y: 0.0 }, p2: Point { x: 3.0, y: 4.0 } }
+    }
+
+    #[test]
+    fn fits_on_one_line() {
+        let peter = Person { name: "Peter", age: 27 };
+        assert_eq!(peter.to_pretty(80), r#"Person { name: "Peter", age: 27 }"#);
+        assert_eq!(rectangle().to_pretty(80), format!("{:?}", rectangle()));
+    }
+
+    #[test]
+    fn breaks_only_what_does_not_fit() {
+        assert_eq!(rectangle().to_pretty(40), "\
+Rectangle {
+    p1: Point { x: 0.0, y: 0.0 },
+    p2: Point { x: 3.0, y: 4.0 },
+}");
+        assert_eq!(rectangle().to_pretty(20), "\
+Rectangle {
+    p1: Point {
+        x: 0.0,
+        y: 0.0,
+    },
+    p2: Point {
+        x: 3.0,
+        y: 4.0,
+    },
+}");
+        let list = List::new().prepend(3).prepend(2).prepend(1);
+        assert_eq!(list.to_pretty(80), "List [1, 2, 3]");
+        assert_eq!(list.to_pretty(8), "List [\n    1,\n    2,\n    3,\n]");
+    }
+
+    #[test]
+    fn display_uses_the_width() {
+        let doc = vec![rectangle(); 2].pretty();
+        assert_eq!(format!("{}", doc), doc.render(80));
+        assert_eq!(format!("{:30}", doc), doc.render(30));
+    }
+
+    // the adapter over {:?} lays out the same as the hand written impls
+    #[test]
+    fn debug_adapter_matches() {
+        let events = vec![WebEvent::Click { x: 20, y: 80 }, WebEvent::Paste("a, (b) {c}".to_owned()),
+                          WebEvent::KeyPress('{'), WebEvent::PageLoad];
+        let people = vec![Some(Person { name: "Peter", age: 27 }), None];
+        for width in 0..100 {
+            assert_eq!(PrettyDebug(&rectangle()).to_pretty(width), rectangle().to_pretty(width), "width {}", width);
+            assert_eq!(PrettyDebug(&events).to_pretty(width), events.to_pretty(width), "width {}", width);
+            assert_eq!(PrettyDebug(&people).to_pretty(width), people.to_pretty(width), "width {}", width);
+        }
+        assert_eq!(PrettyDebug(&Vec::<i32>::new()).to_pretty(80), "[]");
+        assert_eq!(PrettyDebug(&Nil).to_pretty(80), "Nil");
+    }
+}
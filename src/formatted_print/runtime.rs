@@ -0,0 +1,595 @@
+// RUNTIME FORMATTING
+// format! checks its template at compile time, so it can't be used with templates that are only known at runtime,
+// e.g. ones loaded from a file. format_runtime takes the template as a value and supports the same grammar:
+//   {[argument][:[[fill]align][sign]['#']['0'][width]['.' precision][type]]}
+// where the argument is empty (the next positional one), an index or a name; width and precision may be taken
+// from an argument with `name$`, `0$` or `.*`; and the type is empty, `?`, `x`, `X`, `o`, `b`, `e` or `E`.
+// Problems with the template or the arguments are returned as a FormatError instead of panicking.
+
+use std::error;
+use std::fmt;
+use std::mem;
+
+// A value that can be formatted. Integers remember their width in bits so that negative numbers print in
+// two's complement with the radix types, like they do with format!
+#[derive(Debug, Clone, PartialEq)]
+pub enum Arg {
+    Int { value: i128, bits: u32 },
+    // `single` is true for an f32, which prints with fewer digits than the same value as an f64
+    Float { value: f64, single: bool },
+    Str(String),
+    Char(char),
+    Bool(bool),
+}
+
+macro_rules! int_arg {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Arg {
+                fn from(n: $t) -> Arg {
+                    Arg::Int { value: n as i128, bits: (mem::size_of::<$t>() * 8) as u32 }
+                }
+            }
+        )*
+    };
+}
+
+int_arg!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, usize);
+
+impl From<f32> for Arg {
+    fn from(value: f32) -> Arg { Arg::Float { value: value as f64, single: true } }
+}
+
+impl From<f64> for Arg {
+    fn from(value: f64) -> Arg { Arg::Float { value, single: false } }
+}
+
+impl<'a> From<&'a str> for Arg {
+    fn from(s: &'a str) -> Arg { Arg::Str(s.to_owned()) }
+}
+
+impl From<String> for Arg {
+    fn from(s: String) -> Arg { Arg::Str(s) }
+}
+
+impl From<char> for Arg {
+    fn from(c: char) -> Arg { Arg::Char(c) }
+}
+
+impl From<bool> for Arg {
+    fn from(b: bool) -> Arg { Arg::Bool(b) }
+}
+
+impl Arg {
+    // used in error messages
+    pub fn kind(&self) -> &'static str {
+        match *self {
+            Arg::Int { .. } => "integer",
+            Arg::Float { .. } => "float",
+            Arg::Str(_) => "string",
+            Arg::Char(_) => "char",
+            Arg::Bool(_) => "bool",
+        }
+    }
+}
+
+// The arguments for a template. As with format!, named arguments can also be referred to by index; they come
+// after the positional ones.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Args {
+    pub positional: Vec<Arg>,
+    pub named: Vec<(String, Arg)>,
+}
+
+impl Args {
+    pub fn new() -> Args {
+        Args::default()
+    }
+
+    pub fn arg<T: Into<Arg>>(mut self, value: T) -> Args {
+        self.positional.push(value.into());
+        self
+    }
+
+    pub fn named<T: Into<Arg>>(mut self, name: &str, value: T) -> Args {
+        self.named.push((name.to_owned(), value.into()));
+        self
+    }
+
+    fn by_index(&self, index: usize) -> Option<&Arg> {
+        self.positional.get(index)
+            .or_else(|| index.checked_sub(self.positional.len())
+                .and_then(|index| self.named.get(index))
+                .map(|named| &named.1))
+    }
+
+    fn by_name(&self, name: &str) -> Option<&Arg> {
+        self.named.iter().find(|named| named.0 == name).map(|named| &named.1)
+    }
+}
+
+// Every error carries the byte offset in the template of the `{` (or `}`) it is about.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FormatError {
+    // a `{` without a closing `}`
+    Unclosed { position: usize },
+    // a `}` that isn't part of `}}` or closing a placeholder
+    Unmatched { position: usize },
+    // there are fewer arguments than the placeholder needs
+    MissingArgument { position: usize, index: usize },
+    UnknownArgument { position: usize, name: String },
+    BadSpec { position: usize, spec: String, reason: &'static str },
+    // e.g. `{:x}` with a string
+    UnsupportedType { position: usize, ty: &'static str, kind: &'static str },
+    // a width or precision taken from an argument that isn't an integer from 0 to MAX_COUNT
+    BadCount { position: usize, kind: &'static str },
+}
+
+impl fmt::Display for FormatError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            FormatError::Unclosed { position } =>
+                write!(f, "unclosed `{{` at {}", position),
+            FormatError::Unmatched { position } =>
+                write!(f, "unmatched `}}` at {}; use `}}}}` for a literal brace", position),
+            FormatError::MissingArgument { position, index } =>
+                write!(f, "placeholder at {} refers to argument {} but there aren't enough arguments", position, index),
+            FormatError::UnknownArgument { position, ref name } =>
+                write!(f, "placeholder at {} refers to unknown argument `{}`", position, name),
+            FormatError::BadSpec { position, ref spec, reason } =>
+                write!(f, "bad format spec `{}` at {}: {}", spec, position, reason),
+            FormatError::UnsupportedType { position, ty, kind } =>
+                write!(f, "placeholder at {} can't format a {} as `{}`", position, kind, ty),
+            FormatError::BadCount { position, kind } =>
+                write!(f, "placeholder at {} takes a width or precision from a {}; it must be an integer from 0 to {}",
+                       position, kind, MAX_COUNT),
+        }
+    }
+}
+
+impl error::Error for FormatError {}
+
+#[derive(Debug, Clone, PartialEq)]
+enum ArgRef {
+    Next,
+    Index(usize),
+    Name(String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Count {
+    Is(usize),
+    Arg(ArgRef),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Align { Left, Center, Right }
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Type { Display, Debug, LowerHex, UpperHex, Octal, Binary, LowerExp, UpperExp }
+
+impl Type {
+    fn name(&self) -> &'static str {
+        match *self {
+            Type::Display => "",
+            Type::Debug => "?",
+            Type::LowerHex => "x",
+            Type::UpperHex => "X",
+            Type::Octal => "o",
+            Type::Binary => "b",
+            Type::LowerExp => "e",
+            Type::UpperExp => "E",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Spec {
+    fill: char,
+    align: Option<Align>,
+    plus: bool,
+    alternate: bool,
+    zero: bool,
+    width: Option<Count>,
+    precision: Option<Count>,
+    ty: Type,
+}
+
+pub fn format_runtime(template: &str, args: &Args) -> Result<String, FormatError> {
+    let mut output = String::with_capacity(template.len());
+    let mut chars = template.char_indices().peekable();
+    let mut next_positional = 0;
+
+    while let Some((position, c)) = chars.next() {
+        match c {
+            '{' if chars.peek().map(|&(_, c)| c) == Some('{') => {
+                chars.next();
+                output.push('{');
+            },
+            '{' => {
+                let end = loop {
+                    match chars.next() {
+                        Some((end, '}')) => break end,
+                        Some((_, '{')) | None => return Err(FormatError::Unclosed { position }),
+                        Some(_) => {},
+                    }
+                };
+                let placeholder = &template[position + 1..end];
+                format_placeholder(&mut output, placeholder, position, args, &mut next_positional)?;
+            },
+            '}' if chars.peek().map(|&(_, c)| c) == Some('}') => {
+                chars.next();
+                output.push('}');
+            },
+            '}' => return Err(FormatError::Unmatched { position }),
+            c => output.push(c),
+        }
+    }
+    Ok(output)
+}
+
+fn format_placeholder(output: &mut String, placeholder: &str, position: usize, args: &Args,
+                      next_positional: &mut usize) -> Result<(), FormatError> {
+    let (argument, spec) = match placeholder.find(':') {
+        Some(colon) => (&placeholder[..colon], &placeholder[colon + 1..]),
+        None => (placeholder, ""),
+    };
+    let argument = parse_arg_ref(argument).ok_or_else(|| FormatError::BadSpec {
+        position,
+        spec: placeholder.to_owned(),
+        reason: "an argument is an index or a name",
+    })?;
+    let spec = parse_spec(spec).map_err(|reason| FormatError::BadSpec {
+        position,
+        spec: spec.to_owned(),
+        reason,
+    })?;
+
+    // like format!, `.*` takes the precision before the value
+    let mut lookup = |argument: &ArgRef| -> Result<&Arg, FormatError> {
+        match *argument {
+            ArgRef::Next => {
+                let index = *next_positional;
+                *next_positional += 1;
+                args.by_index(index).ok_or(FormatError::MissingArgument { position, index })
+            },
+            ArgRef::Index(index) => args.by_index(index).ok_or(FormatError::MissingArgument { position, index }),
+            ArgRef::Name(ref name) =>
+                args.by_name(name).ok_or_else(|| FormatError::UnknownArgument { position, name: name.clone() }),
+        }
+    };
+    let mut count = |count: &Option<Count>| -> Result<Option<usize>, FormatError> {
+        match *count {
+            None => Ok(None),
+            Some(Count::Is(n)) => Ok(Some(n)),
+            Some(Count::Arg(ref argument)) => match *lookup(argument)? {
+                Arg::Int { value, .. } if value >= 0 && value <= MAX_COUNT as i128 => Ok(Some(value as usize)),
+                ref other => Err(FormatError::BadCount { position, kind: other.kind() }),
+            },
+        }
+    };
+    let precision = count(&spec.precision)?;
+    let width = count(&spec.width)?;
+    let value = lookup(&argument)?;
+
+    let body = render(value, &spec, precision).ok_or(FormatError::UnsupportedType {
+        position,
+        ty: spec.ty.name(),
+        kind: value.kind(),
+    })?;
+    pad(output, body, &spec, width);
+    Ok(())
+}
+
+fn is_identifier(s: &str) -> bool {
+    let mut chars = s.chars();
+    match chars.next() {
+        Some(c) if c.is_alphabetic() || c == '_' => chars.all(|c| c.is_alphanumeric() || c == '_'),
+        _ => false,
+    }
+}
+
+fn parse_arg_ref(s: &str) -> Option<ArgRef> {
+    if s.is_empty() {
+        Some(ArgRef::Next)
+    } else if s.chars().all(|c| c.is_ascii_digit()) {
+        s.parse().ok().map(ArgRef::Index)
+    } else if is_identifier(s) {
+        Some(ArgRef::Name(s.to_owned()))
+    } else {
+        None
+    }
+}
+
+fn align(c: char) -> Option<Align> {
+    match c {
+        '<' => Some(Align::Left),
+        '^' => Some(Align::Center),
+        '>' => Some(Align::Right),
+        _ => None,
+    }
+}
+
+// the largest width or precision, as for format!; far larger ones would only be a way to run out of memory
+const MAX_COUNT: usize = u16::MAX as usize;
+
+// a count is either a number or an argument followed by `$`; anything else is left for the type
+fn parse_count(s: &[char], i: &mut usize) -> Result<Option<Count>, &'static str> {
+    let start = *i;
+    let mut end = start;
+    while end < s.len() && (s[end].is_alphanumeric() || s[end] == '_') {
+        end += 1;
+    }
+    let word: String = s[start..end].iter().collect();
+    if word.is_empty() {
+        return Ok(None);
+    }
+    if s.get(end) == Some(&'$') {
+        let argument = match parse_arg_ref(&word) {
+            Some(argument) => argument,
+            None => return Ok(None),
+        };
+        *i = end + 1;
+        return Ok(Some(Count::Arg(argument)));
+    }
+    // a plain number, which may run straight into the type as in `6x`
+    let digits = word.chars().take_while(|c| c.is_ascii_digit()).count();
+    if digits == 0 {
+        return Ok(None);
+    }
+    let n: u16 = word[..digits].parse().map_err(|_| "a width or precision can be at most 65535")?;
+    *i = start + digits;
+    Ok(Some(Count::Is(n as usize)))
+}
+
+fn parse_spec(spec: &str) -> Result<Spec, &'static str> {
+    let s: Vec<char> = spec.chars().collect();
+    let mut i = 0;
+    let mut result = Spec {
+        fill: ' ',
+        align: None,
+        plus: false,
+        alternate: false,
+        zero: false,
+        width: None,
+        precision: None,
+        ty: Type::Display,
+    };
+
+    if s.len() >= 2 && align(s[1]).is_some() {
+        result.fill = s[0];
+        result.align = align(s[1]);
+        i = 2;
+    } else if !s.is_empty() && align(s[0]).is_some() {
+        result.align = align(s[0]);
+        i = 1;
+    }
+    match s.get(i) {
+        Some(&'+') => { result.plus = true; i += 1; },
+        Some(&'-') => i += 1, // accepted but unused, like format!
+        _ => {},
+    }
+    if s.get(i) == Some(&'#') {
+        result.alternate = true;
+        i += 1;
+    }
+    // `0$` is a width taken from argument 0, not the zero flag
+    if s.get(i) == Some(&'0') && s.get(i + 1) != Some(&'$') {
+        result.zero = true;
+        i += 1;
+    }
+    result.width = parse_count(&s, &mut i)?;
+    if s.get(i) == Some(&'.') {
+        i += 1;
+        if s.get(i) == Some(&'*') {
+            i += 1;
+            result.precision = Some(Count::Arg(ArgRef::Next));
+        } else {
+            result.precision = Some(parse_count(&s, &mut i)?.ok_or("expected a precision after `.`")?);
+        }
+    }
+    let ty: String = s[i..].iter().collect();
+    result.ty = match ty.as_str() {
+        "" => Type::Display,
+        "?" => Type::Debug,
+        "x" => Type::LowerHex,
+        "X" => Type::UpperHex,
+        "o" => Type::Octal,
+        "b" => Type::Binary,
+        "e" => Type::LowerExp,
+        "E" => Type::UpperExp,
+        _ => return Err("unknown format type"),
+    };
+    Ok(result)
+}
+
+// what a value renders to before padding; numbers keep their sign and prefix apart so that `0` can pad between
+// them and the digits
+enum Body {
+    Text(String),
+    Number { sign: &'static str, prefix: &'static str, digits: String },
+}
+
+fn number(rendered: String, plus: bool) -> Body {
+    if let Some(digits) = rendered.strip_prefix('-') {
+        Body::Number { sign: "-", prefix: "", digits: digits.to_owned() }
+    } else {
+        Body::Number { sign: if plus { "+" } else { "" }, prefix: "", digits: rendered }
+    }
+}
+
+// precision truncates text to at most that many chars
+fn text(s: &str, precision: Option<usize>) -> Body {
+    match precision {
+        Some(precision) => Body::Text(s.chars().take(precision).collect()),
+        None => Body::Text(s.to_owned()),
+    }
+}
+
+fn render(value: &Arg, spec: &Spec, precision: Option<usize>) -> Option<Body> {
+    let body = match (spec.ty, value) {
+        (Type::Display, Arg::Str(s)) => text(s, precision),
+        (Type::Debug, Arg::Str(s)) => text(&format!("{:?}", s), None),
+        (Type::Display, &Arg::Char(c)) => text(&c.to_string(), precision),
+        (Type::Debug, &Arg::Char(c)) => text(&format!("{:?}", c), None),
+        (Type::Display, &Arg::Bool(b)) | (Type::Debug, &Arg::Bool(b)) => text(&b.to_string(), precision),
+        (Type::Display, &Arg::Int { value, .. }) | (Type::Debug, &Arg::Int { value, .. }) =>
+            number(value.to_string(), spec.plus),
+        (Type::Display, &Arg::Float { value, single }) => number(match (precision, single) {
+            (Some(p), true) => format!("{:.*}", p, value as f32),
+            (Some(p), false) => format!("{:.*}", p, value),
+            (None, true) => format!("{}", value as f32),
+            (None, false) => format!("{}", value),
+        }, spec.plus),
+        (Type::Debug, &Arg::Float { value, single }) => number(match (precision, single) {
+            (Some(p), true) => format!("{:.*?}", p, value as f32),
+            (Some(p), false) => format!("{:.*?}", p, value),
+            (None, true) => format!("{:?}", value as f32),
+            (None, false) => format!("{:?}", value),
+        }, spec.plus),
+        (Type::LowerHex, &Arg::Int { value, bits }) | (Type::UpperHex, &Arg::Int { value, bits }) |
+        (Type::Octal, &Arg::Int { value, bits }) | (Type::Binary, &Arg::Int { value, bits }) => {
+            // negative numbers print as the two's complement of their own width
+            let mask = if bits >= 128 { !0 } else { (1u128 << bits) - 1 };
+            let bits = value as u128 & mask;
+            let (digits, prefix) = match spec.ty {
+                Type::LowerHex => (format!("{:x}", bits), "0x"),
+                Type::UpperHex => (format!("{:X}", bits), "0x"),
+                Type::Octal => (format!("{:o}", bits), "0o"),
+                _ => (format!("{:b}", bits), "0b"),
+            };
+            Body::Number {
+                sign: if spec.plus { "+" } else { "" },
+                prefix: if spec.alternate { prefix } else { "" },
+                digits,
+            }
+        },
+        (Type::LowerExp, &Arg::Int { value, .. }) => number(match precision {
+            Some(p) => format!("{:.*e}", p, value),
+            None => format!("{:e}", value),
+        }, spec.plus),
+        (Type::UpperExp, &Arg::Int { value, .. }) => number(match precision {
+            Some(p) => format!("{:.*E}", p, value),
+            None => format!("{:E}", value),
+        }, spec.plus),
+        (Type::LowerExp, &Arg::Float { value, single }) => number(match (precision, single) {
+            (Some(p), true) => format!("{:.*e}", p, value as f32),
+            (Some(p), false) => format!("{:.*e}", p, value),
+            (None, true) => format!("{:e}", value as f32),
+            (None, false) => format!("{:e}", value),
+        }, spec.plus),
+        (Type::UpperExp, &Arg::Float { value, single }) => number(match (precision, single) {
+            (Some(p), true) => format!("{:.*E}", p, value as f32),
+            (Some(p), false) => format!("{:.*E}", p, value),
+            (None, true) => format!("{:E}", value as f32),
+            (None, false) => format!("{:E}", value),
+        }, spec.plus),
+        _ => return None,
+    };
+    Some(body)
+}
+
+fn push_fill(output: &mut String, fill: char, n: usize) {
+    output.extend((0..n).map(|_| fill));
+}
+
+fn pad(output: &mut String, body: Body, spec: &Spec, width: Option<usize>) {
+    let width = width.unwrap_or(0);
+    let (text, default_align) = match body {
+        // the `0` flag pads numbers after the sign and prefix and ignores fill and alignment
+        Body::Number { sign, prefix, digits } if spec.zero => {
+            let len = sign.len() + prefix.len() + digits.chars().count();
+            output.push_str(sign);
+            output.push_str(prefix);
+            push_fill(output, '0', width.saturating_sub(len));
+            output.push_str(&digits);
+            return;
+        },
+        Body::Number { sign, prefix, digits } => (format!("{}{}{}", sign, prefix, digits), Align::Right),
+        Body::Text(text) => (text, Align::Left),
+    };
+    let padding = width.saturating_sub(text.chars().count());
+    let (before, after) = match spec.align.unwrap_or(default_align) {
+        Align::Left => (0, padding),
+        Align::Center => (padding / 2, padding - padding / 2),
+        Align::Right => (padding, 0),
+    };
+    push_fill(output, spec.fill, before);
+    output.push_str(&text);
+    push_fill(output, spec.fill, after);
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // each case is checked against format! with the same template
+    macro_rules! same_as_format {
+        ($template:expr, $($arg:expr),*) => {
+            assert_eq!(format_runtime($template, &Args::new()$(.arg($arg))*).unwrap(),
+                       format!($template, $($arg),*), "{}", $template);
+        };
+    }
+
+    #[test]
+    fn matches_format() {
+        same_as_format!("{} days",  31);
+        same_as_format!("{0}, this is {1}. {1}, this is {0}", "Alice", "Bob");
+        same_as_format!("{} of {:b} people know binary", 1, 2);
+        same_as_format!("{:>6}|{:<6}|{:^6}|{:*^7}", 1, 2, "ab", "c");
+        same_as_format!("{:06}|{:+06}|{:+}|{:06}", 1, 42, 7, -5);
+        same_as_format!("{:#x}|{:#X}|{:#o}|{:#b}|{:#010x}|{:x}", 255, 255, 8, 5, 255, -1i8);
+        same_as_format!("{:?}|{:?}|{:?}|{:?}", "quote\"d\n", 'c', 1.0, 0.1f32);
+        same_as_format!("{:.2}|{:8.3}|{:<8.1}|{:.3}|{:+.1}", 1.23456, 2.5, -1.25, "truncated", 1.0);
+        same_as_format!("{:e}|{:E}|{:.2e}|{:e}", 1234.5, 0.00012, 1500.0, 1500);
+        same_as_format!("{:5}|{:<5}|{:>5}|{:^5}", true, 'x', "ab", false);
+        same_as_format!("{{{}}} }}{{", 1);
+    }
+
+    #[test]
+    fn named_and_counted_arguments() {
+        let args = Args::new().named("number", 1).named("width", 6);
+        assert_eq!(format_runtime("{number:>width$}|{number:>0width$}", &args).unwrap(),
+                   format!("{number:>width$}|{number:>0width$}", number=1, width=6));
+        let args = Args::new().arg(3).arg(1.23456).arg(7);
+        assert_eq!(format_runtime("{:.*}|{2:>0$}|{2:<0$}", &args).unwrap(), format!("{:.*}|{2:>0$}|{2:<0$}", 3, 1.23456, 7));
+        // named arguments can also be used by index, after the positional ones
+        let args = Args::new().arg("a").named("b", "b");
+        assert_eq!(format_runtime("{1}{0}", &args).unwrap(), "ba");
+    }
+
+    #[test]
+    fn errors() {
+        let args = Args::new().arg(1).arg("text");
+        assert_eq!(format_runtime("{} {} {}", &args), Err(FormatError::MissingArgument { position: 6, index: 2 }));
+        assert_eq!(format_runtime("{name}", &args),
+                   Err(FormatError::UnknownArgument { position: 0, name: "name".to_owned() }));
+        assert_eq!(format_runtime("ab {0", &args), Err(FormatError::Unclosed { position: 3 }));
+        assert_eq!(format_runtime("a } b", &args), Err(FormatError::Unmatched { position: 2 }));
+        assert_eq!(format_runtime("{:q}", &args),
+                   Err(FormatError::BadSpec { position: 0, spec: "q".to_owned(), reason: "unknown format type" }));
+        assert_eq!(format_runtime("{:.}", &args),
+                   Err(FormatError::BadSpec { position: 0, spec: ".".to_owned(), reason: "expected a precision after `.`" }));
+        assert_eq!(format_runtime("{1:x}", &args),
+                   Err(FormatError::UnsupportedType { position: 0, ty: "x", kind: "string" }));
+        assert_eq!(format_runtime("{0:1$}", &args), Err(FormatError::BadCount { position: 0, kind: "string" }));
+    }
+
+    #[test]
+    fn count_limits() {
+        // far too much to allocate, which used to abort rather than fail
+        let too_wide = Err(FormatError::BadSpec {
+            position: 0, spec: "99999999999999".to_owned(), reason: "a width or precision can be at most 65535",
+        });
+        assert_eq!(format_runtime("{:99999999999999}", &Args::new().arg(1)), too_wide);
+        assert!(matches!(format_runtime("{:.65536}", &Args::new().arg(1.5)), Err(FormatError::BadSpec { .. })));
+        assert_eq!(format_runtime("{:65535}", &Args::new().arg(1)).unwrap().len(), 65535);
+        assert_eq!(format_runtime("{:.0065535}", &Args::new().arg("x")), Ok("x".to_owned()));
+
+        let args = Args::new().arg(1).arg(65536).arg(-1).arg(1u64 << 40);
+        for spec in &["{0:1$}", "{0:.2$}", "{0:3$}"] {
+            assert_eq!(format_runtime(spec, &args), Err(FormatError::BadCount { position: 0, kind: "integer" }));
+        }
+        assert!(format_runtime("{0:1$}", &args).unwrap_err().to_string().ends_with("an integer from 0 to 65535"));
+    }
+}
@@ -0,0 +1,256 @@
+// TABLES
+// Lining up records with `{:<width$}` by hand means working out every column's width first. A table does that:
+// each row type says what its columns are by implementing Columns, and Table renders a list of rows as an ASCII
+// or Markdown table with the widths computed, numbers aligned right and text left, cells over a maximum width
+// cut short with an ellipsis, and optional borders.
+
+use std::fmt::Display;
+use std::io::{self, Write};
+
+use custom_types::Person;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Cell {
+    Text(String),
+    Number(String),
+}
+
+impl Cell {
+    pub fn text<T: Display>(value: T) -> Cell {
+        Cell::Text(value.to_string())
+    }
+
+    pub fn number<T: Display>(value: T) -> Cell {
+        Cell::Number(value.to_string())
+    }
+
+    pub fn as_str(&self) -> &str {
+        match *self {
+            Cell::Text(ref s) | Cell::Number(ref s) => s,
+        }
+    }
+
+    pub fn is_number(&self) -> bool {
+        match *self {
+            Cell::Number(_) => true,
+            Cell::Text(_) => false,
+        }
+    }
+}
+
+// A type whose values can be the rows of a table. `cells` returns one cell per header.
+pub trait Columns {
+    fn headers() -> Vec<&'static str>;
+    fn cells(&self) -> Vec<Cell>;
+}
+
+// The Person of formatted_print and custom_types::structs is the same type
+impl<'a> Columns for Person<'a> {
+    fn headers() -> Vec<&'static str> {
+        vec!["name", "age"]
+    }
+
+    fn cells(&self) -> Vec<Cell> {
+        vec![Cell::text(self.name), Cell::number(self.age)]
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Style {
+    Ascii,
+    Markdown,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Table {
+    pub style: Style,
+    // cells longer than this many chars are truncated, ending in `…`
+    pub max_width: Option<usize>,
+    // the outer frame: `+--+` lines and `|` edges for ASCII, the leading and trailing `|` for Markdown
+    pub borders: bool,
+}
+
+impl Table {
+    pub fn ascii() -> Table {
+        Table { style: Style::Ascii, max_width: None, borders: true }
+    }
+
+    pub fn markdown() -> Table {
+        Table { style: Style::Markdown, max_width: None, borders: true }
+    }
+
+    pub fn max_width(mut self, max_width: usize) -> Table {
+        self.max_width = Some(max_width);
+        self
+    }
+
+    pub fn borders(mut self, borders: bool) -> Table {
+        self.borders = borders;
+        self
+    }
+
+    fn fit(&self, s: &str) -> String {
+        // a `|` inside a cell would end it early in Markdown
+        let s = match self.style {
+            Style::Markdown => s.replace('|', "\\|"),
+            Style::Ascii => s.to_owned(),
+        };
+        match self.max_width {
+            Some(max) if s.chars().count() > max => {
+                let mut cut: String = s.chars().take(max.saturating_sub(1)).collect();
+                if max > 0 {
+                    cut.push('…');
+                }
+                cut
+            },
+            _ => s,
+        }
+    }
+
+    pub fn render<R: Columns>(&self, rows: &[R]) -> String {
+        let headers: Vec<String> = R::headers().iter().map(|header| self.fit(header)).collect();
+        let cells: Vec<Vec<(String, bool)>> = rows.iter()
+            .map(|row| row.cells().iter().map(|cell| (self.fit(cell.as_str()), cell.is_number())).collect())
+            .collect();
+
+        // a column is aligned right when every cell in it is a number
+        let right: Vec<bool> = (0..headers.len())
+            .map(|column| !cells.is_empty() && cells.iter().all(|row| row.get(column).is_some_and(|cell| cell.1)))
+            .collect();
+        let widths: Vec<usize> = (0..headers.len())
+            .map(|column| cells.iter()
+                .filter_map(|row| row.get(column))
+                .map(|cell| cell.0.chars().count())
+                .chain(Some(headers[column].chars().count()))
+                .max()
+                .unwrap_or(0))
+            // the `:---` marker under a Markdown header needs at least three chars
+            .map(|width| if self.style == Style::Markdown { width.max(3) } else { width })
+            .collect();
+
+        let mut lines = Vec::with_capacity(rows.len() + 4);
+        let header_cells: Vec<&str> = headers.iter().map(|header| header.as_str()).collect();
+        match self.style {
+            Style::Ascii => {
+                let rule = self.ascii_rule(&widths);
+                if self.borders {
+                    lines.push(rule.clone());
+                }
+                lines.push(self.line(&header_cells, &widths, &right));
+                lines.push(rule.clone());
+                for row in &cells {
+                    let row: Vec<&str> = row.iter().map(|cell| cell.0.as_str()).collect();
+                    lines.push(self.line(&row, &widths, &right));
+                }
+                if self.borders {
+                    lines.push(rule);
+                }
+            },
+            Style::Markdown => {
+                lines.push(self.line(&header_cells, &widths, &right));
+                let markers: Vec<String> = widths.iter().zip(&right)
+                    // `:` marks the side the column is aligned to
+                    .map(|(&width, &right)| {
+                        let dashes = "-".repeat(width - 1);
+                        if right { format!("{}:", dashes) } else { format!(":{}", dashes) }
+                    })
+                    .collect();
+                let markers: Vec<&str> = markers.iter().map(|marker| marker.as_str()).collect();
+                lines.push(self.line(&markers, &widths, &right));
+                for row in &cells {
+                    let row: Vec<&str> = row.iter().map(|cell| cell.0.as_str()).collect();
+                    lines.push(self.line(&row, &widths, &right));
+                }
+            },
+        }
+
+        let mut table = lines.join("\n");
+        table.push('\n');
+        table
+    }
+
+    pub fn write<R: Columns>(&self, out: &mut impl Write, rows: &[R]) -> io::Result<()> {
+        write!(out, "{}", self.render(rows))
+    }
+
+    // e.g. `+-------+-----+`, or `-----  ---` under the header without borders
+    fn ascii_rule(&self, widths: &[usize]) -> String {
+        let dashes: Vec<String> = widths.iter()
+            .map(|&width| "-".repeat(if self.borders { width + 2 } else { width }))
+            .collect();
+        if self.borders {
+            format!("+{}+", dashes.join("+"))
+        } else {
+            dashes.join("  ")
+        }
+    }
+
+    fn line(&self, cells: &[&str], widths: &[usize], right: &[bool]) -> String {
+        let padded: Vec<String> = widths.iter().enumerate()
+            .map(|(column, &width)| {
+                let cell = cells.get(column).cloned().unwrap_or("");
+                // `{:>width$}` pads by chars, so the ellipsis counts as one
+                if right[column] {
+                    format!("{:>width$}", cell, width = width)
+                } else {
+                    format!("{:<width$}", cell, width = width)
+                }
+            })
+            .collect();
+        let line = match (self.style, self.borders) {
+            (Style::Ascii, true) | (Style::Markdown, true) => format!("| {} |", padded.join(" | ")),
+            (Style::Markdown, false) => padded.join(" | "),
+            (Style::Ascii, false) => padded.join("  "),
+        };
+        // without a right border, the padding of the last column is just trailing space
+        if self.borders { line } else { line.trim_end().to_owned() }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn people() -> Vec<Person<'static>> {
+        vec![Person { name: "Peter", age: 27 }, Person { name: "Christian Slater", age: 101 }]
+    }
+
+    #[test]
+    fn ascii() {
+        assert_eq!(Table::ascii().render(&people()), "\
++------------------+-----+
+| name             | age |
++------------------+-----+
+| Peter            |  27 |
+| Christian Slater | 101 |
++------------------+-----+
+");
+        assert_eq!(Table::ascii().borders(false).max_width(8).render(&people()), "\
+name      age
+--------  ---
+Peter      27
+Christi…  101
+");
+    }
+
+    #[test]
+    fn markdown() {
+        let people = vec![Person { name: "a|b", age: 1 }];
+        assert_eq!(Table::markdown().render(&people), "\
+| name | age |
+| :--- | --: |
+| a\\|b |   1 |
+");
+        assert_eq!(Table::markdown().borders(false).render(&people), "\
+name | age
+:--- | --:
+a\\|b |   1
+");
+    }
+
+    #[test]
+    fn empty() {
+        let people: Vec<Person> = Vec::new();
+        assert_eq!(Table::ascii().render(&people), "+------+-----+\n| name | age |\n+------+-----+\n+------+-----+\n");
+    }
+}
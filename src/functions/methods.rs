@@ -0,0 +1,98 @@
+// the final expression of a function is used as the return value.
+// The return statement can be used to return a value earlier from within the function
+
+#![allow(clippy::manual_is_multiple_of)]
+
+use std::io::{self, Write};
+
+use custom_types::{Point, Rectangle};
+use traits::Pair;
+
+pub fn run(out: &mut impl Write) -> io::Result<()> {
+    write!(out, "Is 4 divisible by 2: {:?}", is_divisible_by(4, 2))?;
+    fizzbuzz_to(out, 5)?;
+
+    let rectangle = Rectangle {
+        p1: Point::origin(),     // static methods are called using ::
+        p2: Point::new(3.0, 4.0),
+    };
+
+    // instance methods are called using the dot operator
+    writeln!(out, "Rectangle perimeter: {}", rectangle.perimeter())?;
+    writeln!(out, "Rectangle area: {}", rectangle.area())?;
+
+    let mut square = Rectangle {
+        p1: Point::origin(),
+        p2: Point::new(1.0, 1.0),
+    };
+    square.translate(1.0, 1.0); // mutable methods can only be applied on mutable objects
+
+    let pair = Pair(Box::new(1), Box::new(2));
+    pair.destroy(out)?;
+
+    Ok(())
+}
+
+pub fn is_divisible_by(lhs: u32, rhs: u32) -> bool {
+    if rhs == 0 { return false; }
+    lhs % rhs == 0 // last expression, so the return keyword isn't needed here
+}
+
+// the if/else is an expression too, so the result of the chosen `writeln!` is returned
+pub fn fizzbuzz(out: &mut impl Write, n: u32) -> io::Result<()> {
+    if is_divisible_by(n, 5) {
+        writeln!(out, "fizzbuzz")
+    } else {
+        writeln!(out, "{}", n)
+    }
+}
+
+// `?` returns early from the function with the error if a write fails
+pub fn fizzbuzz_to(out: &mut impl Write, n: u32) -> io::Result<()> {
+    for n in 1..n+1 {
+        fizzbuzz(out, n)?;
+    }
+    Ok(())
+}
+
+// MEHTODS
+// methods are functions attached to objects. `Point` and `Rectangle` are defined in custom_types;
+// an impl block can live anywhere in the crate that defines the type.
+// Implementation block: all `Point` methods go in here.
+impl Point {
+    pub fn origin() -> Point { // this is a static method. They don't need to be called by an instance.
+        Point { x: 0.0, y: 0.0 } // these methods are generally used as constructors
+    }
+
+    pub fn new(x: f64, y: f64) -> Point {
+        Point { x, y }
+    }
+}
+
+impl Rectangle {
+    pub fn area(&self) -> f64 { // this is an instance method; where &self is sugar for `self: &Self`
+        let Point { x: x1, y: y1 } = self.p1;
+        let Point { x: x2, y: y2 } = self.p2;
+        ((x1 - x2) * (y1-y2)).abs() // `abs` is an f64 method that gives the absolute value
+    }
+
+    pub fn perimeter(&self) -> f64 {
+        let Point { x: x1, y: y1 } = self.p1;
+        let Point { x: x2, y: y2 } = self.p2;
+        2.0 * ((x1-x2).abs() + (y1 - y2).abs())
+    }
+
+    pub fn translate(&mut self, x: f64, y: f64) { // &mut self desuggars to `self: &mut Self`
+        self.p1.x += x;
+        self.p2.x += x;
+        self.p1.y += y;
+        self.p2.y += y;
+    }
+}
+
+impl Pair {
+    pub fn destroy(self, out: &mut impl Write) -> io::Result<()> {
+        let Pair(first, second) = self; // destructure self
+        writeln!(out, "Destroying pair({}, {})", first, second)
+    }
+}
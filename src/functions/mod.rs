@@ -0,0 +1,7 @@
+// FUNCTIONS
+// Functions are declared using the fn keyword. Methods are functions attached to objects, closures are
+// anonymous functions that can capture their environment, and higher order functions take or return other functions.
+
+pub mod methods;
+pub mod closures;
+pub mod higher_order;
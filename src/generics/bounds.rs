@@ -0,0 +1,138 @@
+// When working with generics, the type parameters often must use traits as bounds to stipulate what functionality
+// a type implements, e.g. one example here uses the traitl Display to print so it requires T to be
+// bound by Display; that is, T must implement Display
+
+use std::fmt::Display;
+use std::io::{self, Write};
+
+use custom_types::{Point, Rectangle};
+use geometry::{Shape, Triangle};
+
+pub fn printer<T: Display>(out: &mut impl Write, t: T) -> io::Result<()> {
+    writeln!(out, "{}", t)?;
+    Ok(())
+}
+
+// Bounding restricts the generic to types that conform to the bounds. That is :
+pub struct S<T: Display>(pub T); // S can only be a type that implements the Display trait - so no vectors
+
+// Another effect of bounding is that generic instances are allowed to access the methods of traits
+// specified in the bounds
+use std::fmt::Debug; // a trait that implements print marker {:?}
+pub trait HasArea {
+    fn area(&self) -> f64;
+}
+
+// Every geometry::Shape has an area, so this gives all of them HasArea, Rectangle and Triangle included
+impl<S: Shape> HasArea for S {
+    fn area(&self) -> f64 { Shape::area(self) }
+}
+
+// The generic T must implement Debug. Regardless of the type, this will work properly
+pub fn print_debug<T: Debug>(out: &mut impl Write, t: &T) -> io::Result<()> {
+    writeln!(out, "{:?}", t)?;
+    Ok(())
+}
+
+// T must implement HasArea. Any function which meets the bound can access HasArea's function area
+pub fn area<T: HasArea>(t: &T) -> f64 { t.area() }
+
+
+pub fn run(out: &mut impl Write) -> io::Result<()> {
+    let rectangle = Rectangle { p1: Point::origin(), p2: Point::new(3.0, 4.0) };
+    let triangle = Triangle::new(Point::origin(), Point::new(3.0, 0.0), Point::new(0.0, 4.0));
+    // area(&Point::origin()) won't compile: a Point isn't a Shape, so it doesn't implement HasArea
+
+    print_debug(out, &rectangle)?;
+    writeln!(out, "Area: {}", area(&rectangle))?;
+    print_debug(out, &triangle)?;
+    writeln!(out, "Area: {}", area(&triangle))?;
+
+
+    // TESTCASE: EMPTY BOUNDS
+    let cardinal = Cardinal;
+    let bluejay = BlueJay;
+    let _turkey = Turkey;
+
+    // red() won't work on bluejay and blue won't work on cardinal
+    writeln!(out, "A cardinal is: {}", red(&cardinal))?;
+    writeln!(out, "A blue jay is: {}", blue(&bluejay))?;
+
+    // MULTIPLE BOUNDS
+    let string = "words";
+    let array = [1, 2, 3];
+    let vec = vec![1, 2, 3];
+
+    compare_prints(out, &string)?;
+    compare_types(out, &array, &vec)?;
+
+    // WHERE CLAUSES
+    let vec = vec![1, 2, 3];
+
+    vec.print_in_option(out)?;
+
+    Ok(())
+}
+
+
+// TESTCASE: EMPTY BOUNDS
+// A consequence of how bounds work is that even if a trait doesn't include any functionality, it can be used
+// as a bound. Eq and Ord are example of such traits from the std library
+
+pub struct Cardinal;
+pub struct BlueJay;
+pub struct Turkey;
+
+pub trait Red {}
+pub trait Blue {}
+
+impl Red for Cardinal {}
+impl Blue for BlueJay {}
+
+// These functions are only valid for types which implement these traits.
+// The fact that these traits are empty is irrelevant
+
+pub fn red<T: Red>(_: &T) -> &'static str { "red" }
+pub fn blue<T: Blue>(_: &T) -> &'static str { "blue" }
+
+// MULTIPLE BOUNDS
+// can be applied with a `+`. Different types are separated by a `,`.
+pub fn compare_prints<T: Debug + Display>(out: &mut impl Write, t: &T) -> io::Result<()> {
+    writeln!(out, "Debug: `{:?}`", t)?;
+    writeln!(out, "Display: `{}`", t)?;
+    Ok(())
+}
+
+pub fn compare_types<T: Debug, U: Debug>(out: &mut impl Write, t: &T, u: &U) -> io::Result<()> {
+    writeln!(out, "t: `{:?}`", t)?;
+    writeln!(out, "u: `{:?}`", u)?;
+    Ok(())
+}
+
+// WHERE CLAUSES
+// A bound can also be expressed using a where clause immediately before the opening {, rather than at the type's first mention
+// where clauses can apply bounds to arbitrary types, rather than to type parameters.
+
+// A where clause is useful when specifying generic types and bounds separately is clearer
+// impl <A: TraitB + TraitC, D: TraitE + TraitF> MyTrait<A, D> for YourType {}
+
+// Expressing bounds with a where clause
+// impl <A, D> MyTrait<A, D> for YourType where
+//     A: TraitB + TraitC,
+//     D: TraitE + TraitF {}
+
+
+// Sometimes, using a where clause is more expressive than using normal syntax. The impl in this example cannot be
+// directly expressed
+pub trait PrintInOption {
+    fn print_in_option(self, out: &mut impl Write) -> io::Result<()>;
+}
+
+// Because we would have to express this as `T: Debug` or use another method of indirect approach,
+// this requires a where clause
+impl <T> PrintInOption for T where
+    Option<T>: Debug {
+        fn print_in_option(self, out: &mut impl Write) -> io::Result<()> {
+            writeln!(out, "{:?}", Some(self))
+        }
+    }
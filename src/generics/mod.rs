@@ -0,0 +1,16 @@
+// GENERICS
+// Generics is the topic of generalizing types and functionalities to broader cases.
+
+pub mod basics;
+pub mod bounds;
+pub mod new_types;
+pub mod associated_types;
+
+pub use self::associated_types::{Inch, Length, Mm};
+pub use self::bounds::HasArea;
+pub use self::new_types::{Days, Years};
+
+// Container is shared by the associated items examples: new_types implements a `Contains` trait that is generic
+// over A and B, associated_types implements one that uses associated types instead.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Container(pub i32, pub i32);
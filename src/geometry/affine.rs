@@ -0,0 +1,489 @@
+// AFFINE TRANSFORMS
+// An Affine2 moves, scales, rotates and shears the plane, keeping straight lines straight and parallel ones
+// parallel. It is the 3x3 matrix that takes (x, y, 1) to (x', y', 1):
+//
+//     ( xx xy x0 )
+//     ( yx yy y0 )
+//     (  0  0  1 )
+//
+// Transforms compose: `a.then(b)` does a and then b, which as matrices is `b * a`. That is what nesting frames is:
+// a child's frame inside its parent's is the child's transform followed by the parent's, and the inverse takes a
+// point on the page back into the child.
+//
+// The Transform trait applies one to points and shapes. A rectangle turned by anything but a quarter turn is no
+// longer axis aligned, so it becomes a Quad: still a Rectangle, an OrientedBox, or a parallelogram Polygon once
+// it's sheared.
+
+use std::fmt;
+use std::ops::Mul;
+
+use custom_types::{Point, Rectangle};
+use primitives::Matrix;
+
+use super::{add, bounds, dot, length, sub, times, Circle, Outline, Polygon, Shape, Triangle, EPSILON};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Affine2 {
+    // the bottom row is always 0 0 1
+    matrix: Matrix<f64, 3, 3>,
+}
+
+impl Affine2 {
+    fn new(xx: f64, xy: f64, x0: f64, yx: f64, yy: f64, y0: f64) -> Affine2 {
+        Affine2 { matrix: Matrix::new([[xx, xy, x0], [yx, yy, y0], [0.0, 0.0, 1.0]]) }
+    }
+
+    pub fn identity() -> Affine2 {
+        Affine2 { matrix: Matrix::identity() }
+    }
+
+    pub fn translate(dx: f64, dy: f64) -> Affine2 {
+        Affine2::new(1.0, 0.0, dx, 0.0, 1.0, dy)
+    }
+
+    // about the origin; a negative factor mirrors
+    pub fn scale(sx: f64, sy: f64) -> Affine2 {
+        Affine2::new(sx, 0.0, 0.0, 0.0, sy, 0.0)
+    }
+
+    // counterclockwise about the origin, in radians
+    pub fn rotate(angle: f64) -> Affine2 {
+        let (sin, cos) = angle.sin_cos();
+        Affine2::new(cos, -sin, 0.0, sin, cos, 0.0)
+    }
+
+    // x moves by kx times y, and y by ky times x
+    pub fn shear(kx: f64, ky: f64) -> Affine2 {
+        Affine2::new(1.0, kx, 0.0, ky, 1.0, 0.0)
+    }
+
+    // this transform followed by the next one
+    pub fn then(&self, next: &Affine2) -> Affine2 {
+        Affine2 { matrix: next.matrix * self.matrix }
+    }
+
+    // None when it squashes the plane onto a line or a point, which can't be undone. Rounding rarely leaves an exact
+    // zero determinant, so anything within EPSILON of one, relative to the squared size of the 2x2 block, counts
+    pub fn inverse(&self) -> Option<Affine2> {
+        let [[xx, xy, x0], [yx, yy, y0], _] = self.matrix.0;
+        let det = self.determinant();
+        if det.abs() <= EPSILON * (xx * xx + xy * xy + yx * yx + yy * yy) {
+            return None;
+        }
+        // the inverse of the 2x2 block, then the translation undone through it
+        let (ixx, ixy, iyx, iyy) = (yy / det, -xy / det, -yx / det, xx / det);
+        Some(Affine2::new(ixx, ixy, -(ixx * x0 + ixy * y0), iyx, iyy, -(iyx * x0 + iyy * y0)))
+    }
+
+    // how much areas grow by, negative when it mirrors
+    pub fn determinant(&self) -> f64 {
+        let [[xx, xy, _], [yx, yy, _], _] = self.matrix.0;
+        xx * yy - xy * yx
+    }
+
+    pub fn matrix(&self) -> &Matrix<f64, 3, 3> {
+        &self.matrix
+    }
+
+    pub fn apply(&self, point: Point) -> Point {
+        let [[xx, xy, x0], [yx, yy, y0], _] = self.matrix.0;
+        Point { x: xx * point.x + xy * point.y + x0, y: yx * point.x + yy * point.y + y0 }
+    }
+
+    // the difference between two points, which the translation doesn't affect
+    pub fn apply_vector(&self, vector: Point) -> Point {
+        let [[xx, xy, _], [yx, yy, _], _] = self.matrix.0;
+        Point { x: xx * vector.x + xy * vector.y, y: yx * vector.x + yy * vector.y }
+    }
+
+    // whether every element is within EPSILON of the other's
+    pub fn approx_eq(&self, other: &Affine2) -> bool {
+        self.matrix.0.iter().flatten().zip(other.matrix.0.iter().flatten()).all(|(a, b)| (a - b).abs() <= EPSILON)
+    }
+}
+
+impl Default for Affine2 {
+    fn default() -> Affine2 {
+        Affine2::identity()
+    }
+}
+
+// `a * b` does b first, as with the matrices
+impl Mul for Affine2 {
+    type Output = Affine2;
+
+    fn mul(self, rhs: Affine2) -> Affine2 {
+        rhs.then(&self)
+    }
+}
+
+// like a Matrix, so a precision applies to every element:
+//   ( 1 0 2 )
+//   ( 0 1 3 )
+//   ( 0 0 1 )
+impl fmt::Display for Affine2 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&self.matrix, f)
+    }
+}
+
+// APPLYING
+pub trait Transform {
+    type Output;
+
+    fn transform(&self, affine: &Affine2) -> Self::Output;
+}
+
+impl Transform for Point {
+    type Output = Point;
+
+    fn transform(&self, affine: &Affine2) -> Point {
+        affine.apply(*self)
+    }
+}
+
+impl Transform for Triangle {
+    type Output = Triangle;
+
+    fn transform(&self, affine: &Affine2) -> Triangle {
+        Triangle::new(affine.apply(self.a), affine.apply(self.b), affine.apply(self.c))
+    }
+}
+
+impl Transform for Polygon {
+    type Output = Polygon;
+
+    fn transform(&self, affine: &Affine2) -> Polygon {
+        Polygon::new(self.corners().iter().map(|&corner| affine.apply(corner)).collect())
+            .expect("a polygon keeps its corners")
+    }
+}
+
+// None unless the transform scales both ways alike: anything else stretches the circle into an ellipse
+impl Transform for Circle {
+    type Output = Option<Circle>;
+
+    fn transform(&self, affine: &Affine2) -> Option<Circle> {
+        let (u, v) = (affine.apply_vector(Point::new(1.0, 0.0)), affine.apply_vector(Point::new(0.0, 1.0)));
+        let uniform = (length(u) - length(v)).abs() <= EPSILON * length(u).max(length(v));
+        if uniform && orthogonal(u, v) {
+            Some(Circle::new(affine.apply(self.center), self.radius * length(u)))
+        } else {
+            None
+        }
+    }
+}
+
+impl Transform for Rectangle {
+    type Output = Quad;
+
+    fn transform(&self, affine: &Affine2) -> Quad {
+        let (min, max) = (self.min(), self.max());
+        let width = affine.apply_vector(Point::new(max.x - min.x, 0.0));
+        let height = affine.apply_vector(Point::new(0.0, max.y - min.y));
+        Quad::parallelogram(affine.apply(min), width, height)
+    }
+}
+
+impl Transform for OrientedBox {
+    type Output = Quad;
+
+    fn transform(&self, affine: &Affine2) -> Quad {
+        let (across, up) = self.axes();
+        let (width, height) = (times(across, 2.0 * self.half_width), times(up, 2.0 * self.half_height));
+        let corner = sub(self.center, times(add(width, height), 0.5));
+        Quad::parallelogram(affine.apply(corner), affine.apply_vector(width), affine.apply_vector(height))
+    }
+}
+
+impl Transform for Quad {
+    type Output = Quad;
+
+    fn transform(&self, affine: &Affine2) -> Quad {
+        match *self {
+            Quad::Aligned(ref rectangle) => rectangle.transform(affine),
+            Quad::Oriented(ref oriented) => oriented.transform(affine),
+            // unshearing it can make it a box again
+            Quad::Skewed(ref polygon) => match *polygon.corners() {
+                [corner, next, _, last] => {
+                    let (width, height) = (sub(next, corner), sub(last, corner));
+                    Quad::parallelogram(affine.apply(corner), affine.apply_vector(width), affine.apply_vector(height))
+                },
+                _ => Quad::Skewed(polygon.transform(affine)),
+            },
+        }
+    }
+}
+
+// the vectors are at right angles, give or take EPSILON relative to their lengths
+fn orthogonal(u: Point, v: Point) -> bool {
+    dot(u, v).abs() <= EPSILON * length(u) * length(v)
+}
+
+// ORIENTED BOXES
+// A rectangle turned by `angle` radians counterclockwise about its center
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OrientedBox {
+    pub center: Point,
+    pub half_width: f64,
+    pub half_height: f64,
+    pub angle: f64,
+}
+
+impl OrientedBox {
+    pub fn new(center: Point, half_width: f64, half_height: f64, angle: f64) -> OrientedBox {
+        OrientedBox { center, half_width, half_height, angle }
+    }
+
+    // unit vectors along its width and its height
+    pub fn axes(&self) -> (Point, Point) {
+        let (sin, cos) = self.angle.sin_cos();
+        (Point::new(cos, sin), Point::new(-sin, cos))
+    }
+
+    // counterclockwise, from the one that was the minimum corner before turning
+    pub fn corners(&self) -> [Point; 4] {
+        let (across, up) = self.axes();
+        let (across, up) = (times(across, self.half_width), times(up, self.half_height));
+        [
+            sub(sub(self.center, across), up),
+            sub(add(self.center, across), up),
+            add(add(self.center, across), up),
+            add(sub(self.center, across), up),
+        ]
+    }
+}
+
+impl Shape for OrientedBox {
+    fn area(&self) -> f64 {
+        4.0 * self.half_width * self.half_height
+    }
+
+    fn perimeter(&self) -> f64 {
+        4.0 * (self.half_width + self.half_height)
+    }
+
+    fn centroid(&self) -> Point {
+        self.center
+    }
+
+    fn bounding_box(&self) -> Rectangle {
+        bounds(&self.corners())
+    }
+
+    // within the half sizes along each axis
+    fn contains_point(&self, point: Point) -> bool {
+        let (across, up) = self.axes();
+        let offset = sub(point, self.center);
+        dot(offset, across).abs() <= self.half_width + EPSILON && dot(offset, up).abs() <= self.half_height + EPSILON
+    }
+
+    fn outline(&self) -> Outline {
+        Outline::Polygon(self.corners().to_vec())
+    }
+}
+
+// QUADS
+// What a transformed rectangle turns into, as the simplest shape that is exactly it
+#[derive(Debug, Clone, PartialEq)]
+pub enum Quad {
+    // moved, scaled, mirrored or turned by a quarter
+    Aligned(Rectangle),
+    // turned by any other angle, and maybe scaled
+    Oriented(OrientedBox),
+    // sheared into a parallelogram, with its corners in order
+    Skewed(Polygon),
+}
+
+impl Quad {
+    // the parallelogram with a corner at `corner` and the two edges from it
+    fn parallelogram(corner: Point, width: Point, height: Point) -> Quad {
+        let aligned = |u: Point, v: Point| u.y.abs() <= EPSILON * length(u) && v.x.abs() <= EPSILON * length(v);
+        let far = add(corner, add(width, height));
+        if aligned(width, height) || aligned(height, width) {
+            Quad::Aligned(bounds(&[corner, far]))
+        } else if orthogonal(width, height) {
+            let center = add(corner, times(add(width, height), 0.5));
+            let (half_width, half_height) = (length(width) / 2.0, length(height) / 2.0);
+            // mirrored, the height runs clockwise of the width; the same box then has its width the other way
+            let angle = if dot(Point::new(-width.y, width.x), height) >= 0.0 { width.y.atan2(width.x) }
+                        else { (-width.y).atan2(-width.x) };
+            Quad::Oriented(OrientedBox::new(center, half_width, half_height, angle))
+        } else {
+            let corners = vec![corner, add(corner, width), far, add(corner, height)];
+            Quad::Skewed(Polygon::new(corners).expect("four corners"))
+        }
+    }
+
+    pub fn shape(&self) -> &dyn Shape {
+        match *self {
+            Quad::Aligned(ref rectangle) => rectangle,
+            Quad::Oriented(ref oriented) => oriented,
+            Quad::Skewed(ref polygon) => polygon,
+        }
+    }
+}
+
+impl Shape for Quad {
+    fn area(&self) -> f64 {
+        self.shape().area()
+    }
+
+    fn perimeter(&self) -> f64 {
+        self.shape().perimeter()
+    }
+
+    fn centroid(&self) -> Point {
+        self.shape().centroid()
+    }
+
+    fn bounding_box(&self) -> Rectangle {
+        self.shape().bounding_box()
+    }
+
+    fn contains_point(&self, point: Point) -> bool {
+        self.shape().contains_point(point)
+    }
+
+    fn outline(&self) -> Outline {
+        self.shape().outline()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use geometry::approx_eq;
+    use std::f64::consts::{FRAC_PI_2, FRAC_PI_4, PI};
+
+    fn point_eq(a: Point, b: Point) -> bool {
+        approx_eq(a.x, b.x) && approx_eq(a.y, b.y)
+    }
+
+    #[test]
+    fn transforms() {
+        let p = Point::new(1.0, 2.0);
+        assert_eq!(Affine2::identity().apply(p), p);
+        assert_eq!(Affine2::translate(3.0, -1.0).apply(p), Point::new(4.0, 1.0));
+        assert_eq!(Affine2::scale(2.0, -1.0).apply(p), Point::new(2.0, -2.0));
+        assert!(point_eq(Affine2::rotate(FRAC_PI_2).apply(p), Point::new(-2.0, 1.0)));
+        assert_eq!(Affine2::shear(1.0, 0.0).apply(p), Point::new(3.0, 2.0));
+        // vectors don't move
+        assert_eq!(Affine2::translate(3.0, -1.0).apply_vector(p), p);
+
+        // then goes left to right, * right to left
+        let (scale, shift) = (Affine2::scale(2.0, 2.0), Affine2::translate(1.0, 0.0));
+        assert_eq!(scale.then(&shift).apply(p), Point::new(3.0, 4.0));
+        assert_eq!(shift.then(&scale).apply(p), Point::new(4.0, 4.0));
+        assert_eq!(shift * scale, scale.then(&shift));
+        assert!(Affine2::rotate(FRAC_PI_4).then(&Affine2::rotate(FRAC_PI_4)).approx_eq(&Affine2::rotate(FRAC_PI_2)));
+        assert_eq!(Affine2::scale(2.0, 3.0).determinant(), 6.0);
+        assert_eq!(p.transform(&shift), Point::new(2.0, 2.0));
+    }
+
+    #[test]
+    fn inverse() {
+        let frame = Affine2::rotate(0.3).then(&Affine2::shear(0.5, 0.0)).then(&Affine2::translate(10.0, -4.0));
+        let inverse = frame.inverse().unwrap();
+        assert!(frame.then(&inverse).approx_eq(&Affine2::identity()));
+        assert!(inverse.then(&frame).approx_eq(&Affine2::identity()));
+        assert_eq!(inverse.matrix().0[2], [0.0, 0.0, 1.0]);
+        let p = Point::new(-3.5, 7.25);
+        assert!(point_eq(inverse.apply(frame.apply(p)), p));
+        assert_eq!(Affine2::scale(1.0, 0.0).inverse(), None);
+        assert_eq!(Affine2::shear(1.0, 1.0).inverse(), None);
+        // flattened between two rotations, the determinant is rounding error rather than zero, which the plain matrix
+        // inverse takes at face value
+        let flattened = Affine2::rotate(0.3).then(&Affine2::scale(1.0, 0.0)).then(&Affine2::rotate(0.7));
+        assert!(flattened.determinant() != 0.0 && flattened.matrix().inverse().is_some());
+        assert_eq!(flattened.inverse(), None);
+        // small isn't singular
+        let small = Affine2::scale(1e-5, 1e-5).then(&Affine2::translate(1.0, 2.0));
+        assert!(small.then(&small.inverse().unwrap()).approx_eq(&Affine2::identity()));
+    }
+
+    #[test]
+    fn display() {
+        let affine = Affine2::translate(2.0, 3.0).then(&Affine2::scale(10.0, 1.0));
+        assert_eq!(affine.to_string(), "( 10 0 20 )\n(  0 1  3 )\n(  0 0  1 )");
+        assert_eq!(format!("{:.1}", Affine2::rotate(PI)), "( -1.0 -0.0 0.0 )\n(  0.0 -1.0 0.0 )\n(  0.0  0.0 1.0 )");
+    }
+
+    #[test]
+    fn rectangles() {
+        let rectangle = Rectangle { p1: Point::new(0.0, 0.0), p2: Point::new(4.0, 2.0) };
+
+        // a quarter turn keeps it a Rectangle, the other way up
+        let turned = rectangle.transform(&Affine2::rotate(FRAC_PI_2));
+        match turned {
+            Quad::Aligned(r) => {
+                assert!(point_eq(r.p1, Point::new(-2.0, 0.0)) && point_eq(r.p2, Point::new(0.0, 4.0)));
+            },
+            ref other => panic!("expected a Rectangle, got {:?}", other),
+        }
+        let mirrored = rectangle.transform(&Affine2::scale(-1.0, 1.0));
+        assert_eq!(mirrored, Quad::Aligned(Rectangle { p1: Point::new(-4.0, 0.0), p2: Point::new(0.0, 2.0) }));
+
+        // an eighth of a turn about its center doesn't, and keeps its area
+        let about_center = Affine2::translate(-2.0, -1.0)
+            .then(&Affine2::rotate(FRAC_PI_4))
+            .then(&Affine2::translate(2.0, 1.0));
+        let oriented = match rectangle.transform(&about_center) {
+            Quad::Oriented(oriented) => oriented,
+            other => panic!("expected an OrientedBox, got {:?}", other),
+        };
+        assert!(point_eq(oriented.center, Point::new(2.0, 1.0)));
+        assert!(approx_eq(oriented.half_width, 2.0) && approx_eq(oriented.half_height, 1.0));
+        assert!(approx_eq(oriented.angle, FRAC_PI_4));
+        assert!(approx_eq(oriented.area(), rectangle.area()));
+        // (4, 0) was a corner; now it's outside, the top corner is above the old top edge, and the bounds have grown
+        let half = 1.0 / 2.0_f64.sqrt();
+        assert!(!oriented.contains_point(Point::new(4.0, 0.0)));
+        assert!(oriented.contains_point(Point::new(2.0 + half, 1.0 + 3.0 * half)));
+        assert!(!oriented.contains_point(Point::new(2.0, 1.0 + 3.0 * half)));
+        assert!(approx_eq(oriented.bounding_box().max().x - oriented.bounding_box().min().x, 3.0 * 2.0_f64.sqrt()));
+        // turning it back gives the rectangle again
+        let back = oriented.transform(&about_center.inverse().unwrap());
+        match back {
+            Quad::Aligned(r) => assert!(point_eq(r.p1, rectangle.p1) && point_eq(r.p2, rectangle.p2)),
+            other => panic!("expected a Rectangle, got {:?}", other),
+        }
+
+        // mirroring a turned box keeps it a box
+        let flipped = Quad::Oriented(oriented).transform(&Affine2::scale(1.0, -1.0));
+        match flipped {
+            Quad::Oriented(o) => {
+                assert!(point_eq(o.center, Point::new(2.0, -1.0)) && approx_eq(o.area(), 8.0));
+                for corner in o.corners().iter() {
+                    assert!(oriented.corners().iter().any(|c| point_eq(Point::new(c.x, -c.y), *corner)));
+                }
+            },
+            other => panic!("expected an OrientedBox, got {:?}", other),
+        }
+
+        // a shear gives a parallelogram with the same area
+        let sheared = rectangle.transform(&Affine2::shear(0.5, 0.0));
+        match sheared {
+            Quad::Skewed(ref polygon) => assert_eq!(polygon.corners()[2], Point::new(5.0, 2.0)),
+            ref other => panic!("expected a Polygon, got {:?}", other),
+        }
+        assert!(approx_eq(sheared.area(), 8.0));
+        assert!(sheared.contains_point(Point::new(4.5, 1.0)) && !sheared.contains_point(Point::new(0.5, 2.0)));
+    }
+
+    #[test]
+    fn shapes() {
+        let circle = Circle::new(Point::new(1.0, 0.0), 2.0);
+        let similar = Affine2::rotate(1.0).then(&Affine2::scale(3.0, 3.0));
+        let moved = circle.transform(&similar).unwrap();
+        assert!(approx_eq(moved.radius, 6.0) && point_eq(moved.center, similar.apply(circle.center)));
+        assert_eq!(circle.transform(&Affine2::scale(1.0, 2.0)), None);
+        assert_eq!(circle.transform(&Affine2::shear(0.1, 0.0)), None);
+
+        let triangle = Triangle::new(Point::new(0.0, 0.0), Point::new(2.0, 0.0), Point::new(0.0, 2.0));
+        let scaled = triangle.transform(&Affine2::scale(2.0, 1.5));
+        assert!(approx_eq(scaled.area(), triangle.area() * 3.0));
+        let polygon = Polygon::new(triangle.corners().to_vec()).unwrap().transform(&Affine2::translate(1.0, 1.0));
+        assert_eq!(polygon.corners()[2], Point::new(1.0, 3.0));
+    }
+}
@@ -0,0 +1,309 @@
+// GEOMETRY
+// Shapes on the plane behind one `Shape` trait: each one knows its area, perimeter, centroid and bounding box,
+// whether a point is in it, and whether it overlaps another shape of any kind. Circle, Triangle and Polygon live in
+// geometry::shapes; the Rectangle is the one from custom_types, whose corners can be in any order.
+//
+// Floats rarely land exactly on an edge, so a point within EPSILON of a boundary counts as inside, and two shapes
+// that come within EPSILON of each other count as touching, which is intersecting.
+//
+// geometry::affine moves them around: an Affine2 translates, scales, rotates and shears points and shapes.
+// geometry::quadtree indexes values by Point, to find the ones in a Rectangle or near a point.
+
+pub mod shapes;
+pub mod affine;
+pub mod quadtree;
+
+use std::io::{self, Write};
+
+use custom_types::{Point, Rectangle};
+
+pub use self::shapes::{Circle, Polygon, Triangle};
+pub use self::affine::{Affine2, OrientedBox, Quad, Transform};
+pub use self::quadtree::QuadTree;
+
+// how far apart two coordinates can be and still be the same; it's absolute, so it suits coordinates near 1 to 1e6
+pub const EPSILON: f64 = 1e-9;
+
+pub fn approx_eq(a: f64, b: f64) -> bool {
+    (a - b).abs() <= EPSILON
+}
+
+pub trait Shape {
+    fn area(&self) -> f64;
+
+    fn perimeter(&self) -> f64;
+
+    // the center of mass of the shape's area
+    fn centroid(&self) -> Point;
+
+    // the smallest axis aligned Rectangle around the shape, with p1 as its minimum corner
+    fn bounding_box(&self) -> Rectangle;
+
+    // the boundary counts as inside, give or take EPSILON
+    fn contains_point(&self, point: Point) -> bool;
+
+    // what the intersection tests work on: a circle, or the corners of the shape in order
+    fn outline(&self) -> Outline;
+
+    // whether the shapes share a point, touching included
+    fn intersects(&self, other: &dyn Shape) -> bool {
+        self.outline().intersects(&other.outline())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Outline {
+    Circle { center: Point, radius: f64 },
+    Polygon(Vec<Point>),
+}
+
+impl Outline {
+    pub fn intersects(&self, other: &Outline) -> bool {
+        match (self, other) {
+            (&Outline::Circle { center: a, radius: ra }, &Outline::Circle { center: b, radius: rb }) =>
+                a.distance(b) <= ra + rb + EPSILON,
+            (&Outline::Circle { center, radius }, Outline::Polygon(corners)) |
+            (Outline::Polygon(corners), &Outline::Circle { center, radius }) =>
+                polygon_contains(corners, center) ||
+                    edges(corners).any(|(p, q)| segment_distance(center, p, q) <= radius + EPSILON),
+            (Outline::Polygon(a), Outline::Polygon(b)) => {
+                // either some edges cross or touch, or one polygon lies wholly inside the other
+                edges(a).any(|(p, q)| edges(b).any(|(r, s)| segments_intersect(p, q, r, s))) ||
+                    a.first().is_some_and(|&corner| polygon_contains(b, corner)) ||
+                    b.first().is_some_and(|&corner| polygon_contains(a, corner))
+            },
+        }
+    }
+}
+
+impl Point {
+    pub fn distance(&self, other: Point) -> f64 {
+        (self.x - other.x).hypot(self.y - other.y)
+    }
+}
+
+// HELPERS
+// shared by the shapes, on points as vectors from the origin
+
+fn add(a: Point, b: Point) -> Point {
+    Point { x: a.x + b.x, y: a.y + b.y }
+}
+
+fn sub(a: Point, b: Point) -> Point {
+    Point { x: a.x - b.x, y: a.y - b.y }
+}
+
+fn dot(a: Point, b: Point) -> f64 {
+    a.x * b.x + a.y * b.y
+}
+
+// positive when b is counterclockwise of a
+fn cross(a: Point, b: Point) -> f64 {
+    a.x * b.y - a.y * b.x
+}
+
+fn times(a: Point, k: f64) -> Point {
+    Point { x: a.x * k, y: a.y * k }
+}
+
+fn length(a: Point) -> f64 {
+    a.x.hypot(a.y)
+}
+
+// the smallest axis aligned Rectangle around the corners, with p1 as its minimum
+fn bounds(corners: &[Point]) -> Rectangle {
+    let first = corners[0];
+    let (min, max) = corners.iter().fold((first, first), |(min, max), p| {
+        (Point::new(min.x.min(p.x), min.y.min(p.y)), Point::new(max.x.max(p.x), max.y.max(p.y)))
+    });
+    Rectangle { p1: min, p2: max }
+}
+
+// each corner with the next one, the last with the first
+fn edges(corners: &[Point]) -> impl Iterator<Item = (Point, Point)> + '_ {
+    corners.iter().cloned().zip(corners.iter().cloned().cycle().skip(1))
+}
+
+// from the point to the closest point of the segment from p to q
+fn segment_distance(point: Point, p: Point, q: Point) -> f64 {
+    let edge = sub(q, p);
+    let length = dot(edge, edge);
+    if length == 0.0 {
+        return point.distance(p);
+    }
+    let t = (dot(sub(point, p), edge) / length).clamp(0.0, 1.0);
+    point.distance(Point { x: p.x + t * edge.x, y: p.y + t * edge.y })
+}
+
+fn segments_intersect(p: Point, q: Point, r: Point, s: Point) -> bool {
+    // each segment has the ends of the other strictly on either side of it...
+    let sides = |a: Point, b: Point, c: Point, d: Point| {
+        let (c, d) = (cross(sub(b, a), sub(c, a)), cross(sub(b, a), sub(d, a)));
+        (c < 0.0 && d > 0.0) || (c > 0.0 && d < 0.0)
+    };
+    let crossing = sides(p, q, r, s) && sides(r, s, p, q);
+    // ...or an end touches the other segment, which also covers segments on the same line
+    crossing ||
+        segment_distance(p, r, s) <= EPSILON || segment_distance(q, r, s) <= EPSILON ||
+        segment_distance(r, p, q) <= EPSILON || segment_distance(s, p, q) <= EPSILON
+}
+
+// whether the segments cross each other, each with the ends of the other more than EPSILON away on either side of
+// it; unlike segments_intersect, touching doesn't count
+fn segments_cross(p: Point, q: Point, r: Point, s: Point) -> bool {
+    let sides = |a: Point, b: Point, c: Point, d: Point| {
+        let edge = a.distance(b);
+        let (c, d) = (cross(sub(b, a), sub(c, a)) / edge, cross(sub(b, a), sub(d, a)) / edge);
+        (c < -EPSILON && d > EPSILON) || (c > EPSILON && d < -EPSILON)
+    };
+    sides(p, q, r, s) && sides(r, s, p, q)
+}
+
+// by counting the edges a ray to the right crosses, after checking the boundary itself
+fn polygon_contains(corners: &[Point], point: Point) -> bool {
+    if edges(corners).any(|(p, q)| segment_distance(point, p, q) <= EPSILON) {
+        return true;
+    }
+    let mut inside = false;
+    for (p, q) in edges(corners) {
+        if (p.y > point.y) != (q.y > point.y) {
+            let x = p.x + (point.y - p.y) / (q.y - p.y) * (q.x - p.x);
+            if point.x < x {
+                inside = !inside;
+            }
+        }
+    }
+    inside
+}
+
+pub fn run(out: &mut impl Write) -> io::Result<()> {
+    let shapes: Vec<(&str, Box<dyn Shape>)> = vec![
+        ("circle", Box::new(Circle::new(Point::new(0.0, 0.0), 1.0))),
+        ("triangle", Box::new(Triangle::new(Point::new(0.0, 0.0), Point::new(4.0, 0.0), Point::new(0.0, 3.0)))),
+        ("rectangle", Box::new(Rectangle { p1: Point::new(3.0, 4.0), p2: Point::new(1.0, 2.0) })),
+        ("L-shaped polygon", Box::new(Polygon::new(vec![
+            Point::new(5.0, 0.0), Point::new(9.0, 0.0), Point::new(9.0, 1.0),
+            Point::new(6.0, 1.0), Point::new(6.0, 4.0), Point::new(5.0, 4.0),
+        ]).unwrap())),
+    ];
+
+    // the same calls on every kind of shape, through the trait object
+    for &(name, ref shape) in &shapes {
+        let (centroid, bounds) = (shape.centroid(), shape.bounding_box());
+        writeln!(out, "{}: area {:.3}, perimeter {:.3}, centroid ({:.3}, {:.3}), bounds ({}, {}) to ({}, {})",
+                 name, shape.area(), shape.perimeter(), centroid.x, centroid.y,
+                 bounds.p1.x, bounds.p1.y, bounds.p2.x, bounds.p2.y)?;
+    }
+
+    // the L's centroid is outside it
+    let (_, ref l) = shapes[3];
+    writeln!(out, "the L contains its centroid: {}", l.contains_point(l.centroid()))?;
+    let (_, ref triangle) = shapes[1];
+    writeln!(out, "the triangle contains (2, 1.5), on its long edge: {}", triangle.contains_point(Point::new(2.0, 1.5)))?;
+
+    for (i, &(a, ref first)) in shapes.iter().enumerate() {
+        for &(b, ref second) in &shapes[i + 1..] {
+            writeln!(out, "{} and {} intersect: {}", a, b, first.intersects(second.as_ref()))?;
+        }
+    }
+
+    // TRANSFORMS
+    // a dialog placed at (100, 50) on the page, and a button inside it turned by 30 degrees and drawn at half size
+    let dialog = Affine2::translate(100.0, 50.0);
+    let button = Affine2::scale(0.5, 0.5)
+        .then(&Affine2::rotate(30f64.to_radians()))
+        .then(&Affine2::translate(20.0, 10.0));
+    // the button's frame on the page is its own transform followed by the dialog's
+    let page = button.then(&dialog);
+    writeln!(out, "the button's frame on the page:\n{:.3}", page)?;
+    let corner = Point::new(40.0, 20.0);
+    let on_page = page.apply(corner);
+    writeln!(out, "its corner (40, 20) is at ({:.3}, {:.3}) on the page", on_page.x, on_page.y)?;
+    let back = page.inverse().expect("scaling by a half can be undone").apply(on_page);
+    writeln!(out, "and back in the button: ({:.3}, {:.3})", back.x, back.y)?;
+
+    // turned by anything but a quarter, a rectangle stays a rectangle only as an oriented box
+    let outline = Rectangle { p1: Point::origin(), p2: corner };
+    for &(name, affine) in &[("dialog", dialog), ("page", page), ("sheared", Affine2::shear(0.5, 0.0))] {
+        match outline.transform(&affine) {
+            Quad::Aligned(r) => writeln!(out, "{}: a rectangle from ({}, {}) to ({}, {})",
+                                         name, r.p1.x, r.p1.y, r.p2.x, r.p2.y)?,
+            Quad::Oriented(o) => writeln!(out, "{}: a {} by {} box turned {:.1} degrees about ({:.3}, {:.3})",
+                                          name, 2.0 * o.half_width, 2.0 * o.half_height, o.angle.to_degrees(),
+                                          o.center.x, o.center.y)?,
+            Quad::Skewed(p) => writeln!(out, "{}: a parallelogram with corners {:?}", name,
+                                        p.corners().iter().map(|c| (c.x, c.y)).collect::<Vec<_>>())?,
+        }
+    }
+
+    // QUADTREE
+    // the houses on a 10 by 10 block, numbered along each street, found without scanning all of them
+    let houses: QuadTree<u32> = (0..100u32)
+        .map(|n| (Point::new((n % 10) as f64 * 10.0, (n / 10) as f64 * 10.0), n))
+        .collect();
+    let here = Point::new(42.0, 47.0);
+    let nearest: Vec<u32> = houses.nearest(here, 3).iter().map(|&(_, &n)| n).collect();
+    writeln!(out, "the 3 houses nearest ({}, {}): {:?}", here.x, here.y, nearest)?;
+    let mut close: Vec<u32> = houses.within(here, 10.0).iter().map(|&(_, &n)| n).collect();
+    close.sort();
+    writeln!(out, "within 10 of it: {:?}", close)?;
+    let block = Rectangle { p1: Point::new(0.0, 0.0), p2: Point::new(20.0, 20.0) };
+    writeln!(out, "in the corner block from (0, 0) to (20, 20), edges included: {}", houses.range(&block).len())?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn square(x: f64, y: f64, side: f64) -> Rectangle {
+        Rectangle { p1: Point::new(x, y), p2: Point::new(x + side, y + side) }
+    }
+
+    #[test]
+    fn segments() {
+        let (a, b) = (Point::new(0.0, 0.0), Point::new(2.0, 2.0));
+        assert!(segments_intersect(a, b, Point::new(0.0, 2.0), Point::new(2.0, 0.0)));
+        // an end on the other segment, and overlapping segments on one line
+        assert!(segments_intersect(a, b, Point::new(1.0, 1.0), Point::new(3.0, 0.0)));
+        assert!(segments_intersect(a, b, Point::new(1.0, 1.0), Point::new(3.0, 3.0)));
+        assert!(!segments_intersect(a, b, Point::new(3.0, 3.0), Point::new(4.0, 4.0)));
+        assert!(!segments_intersect(a, b, Point::new(1.0, 0.0), Point::new(2.0, 1.0)));
+        assert_eq!(segment_distance(Point::new(0.0, 5.0), a, a), 5.0);
+        assert_eq!(segment_distance(Point::new(3.0, 0.0), Point::new(0.0, 0.0), Point::new(0.0, 4.0)), 3.0);
+    }
+
+    #[test]
+    fn intersections() {
+        let circle = Circle::new(Point::new(0.0, 0.0), 1.0);
+        // touching counts, even a hair away because of rounding
+        assert!(circle.intersects(&Circle::new(Point::new(2.0, 0.0), 1.0)));
+        assert!(circle.intersects(&Circle::new(Point::new(0.1 + 0.2 + 1.7, 0.0), 1.0)));
+        assert!(!circle.intersects(&Circle::new(Point::new(2.0 + 1e-6, 0.0), 1.0)));
+        assert!(circle.intersects(&square(1.0, -0.5, 1.0)));
+        assert!(!circle.intersects(&square(0.8, 0.8, 1.0)));
+        // one inside the other, without any edges meeting
+        assert!(circle.intersects(&square(-0.1, -0.1, 0.2)));
+        assert!(square(-5.0, -5.0, 10.0).intersects(&circle));
+        assert!(square(-5.0, -5.0, 10.0).intersects(&square(1.0, 1.0, 1.0)));
+
+        let triangle = Triangle::new(Point::new(0.0, 0.0), Point::new(4.0, 0.0), Point::new(0.0, 4.0));
+        assert!(triangle.intersects(&square(2.0, 2.0, 1.0)));
+        assert!(!triangle.intersects(&square(2.0 + 1e-6, 2.0, 1.0)));
+        assert!(triangle.intersects(&triangle));
+    }
+
+    #[test]
+    fn concave() {
+        // a U whose gap a small square sits in without touching
+        let u = Polygon::new(vec![
+            Point::new(0.0, 0.0), Point::new(3.0, 0.0), Point::new(3.0, 3.0), Point::new(2.0, 3.0),
+            Point::new(2.0, 1.0), Point::new(1.0, 1.0), Point::new(1.0, 3.0), Point::new(0.0, 3.0),
+        ]).unwrap();
+        let gap = square(1.25, 1.5, 0.5);
+        assert!(!u.intersects(&gap) && !gap.intersects(&u));
+        assert!(u.intersects(&square(1.25, 0.5, 0.5)));
+        assert!(!u.intersects(&Circle::new(Point::new(1.5, 2.0), 0.4)));
+        assert!(u.intersects(&Circle::new(Point::new(1.5, 2.0), 0.5)));
+    }
+}
@@ -0,0 +1,588 @@
+// QUADTREE
+// A QuadTree<T> keeps values at Points so that the ones near a place can be found without looking at all of them.
+// Every node covers a Rectangle; a leaf holds up to `node_capacity` entries, and once it has more it splits into
+// four children, one for each quarter of its rectangle. A query only visits the nodes whose rectangles could hold
+// an answer:
+// - `range` finds the entries in a Rectangle, edges included
+// - `within` finds the ones within a radius of a point
+// - `nearest` finds the k closest to a point, closest first, visiting the nodes nearest first and stopping as soon
+//   as no node left could hold anything closer
+//
+// The tree grows to take points outside its bounds, doubling them towards the point. Several values can share a
+// point. `bulk_load` builds the tree in one go from all the entries, splitting each node once rather than every
+// time it fills up.
+
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+use std::iter::FromIterator;
+use std::mem;
+use std::slice;
+
+use custom_types::{Point, Rectangle};
+
+use super::{bounds, EPSILON};
+
+// past this many levels a leaf holds however many entries it gets, so many values at one point don't split forever
+const MAX_DEPTH: usize = 32;
+
+pub struct QuadTree<T> {
+    // with p1 as the minimum corner
+    bounds: Rectangle,
+    root: Node<T>,
+    len: usize,
+    node_capacity: usize,
+}
+
+enum Node<T> {
+    Leaf(Vec<(Point, T)>),
+    // the point the quarters meet at, and the children by quadrant; see `quadrant`
+    Branch(Point, Box<[Node<T>; 4]>),
+}
+
+impl<T> QuadTree<T> {
+    // the corners of the bounds don't have to be in any order
+    pub fn new(bounds: Rectangle) -> QuadTree<T> {
+        QuadTree { bounds: padded(bounds.min(), bounds.max()), root: Node::Leaf(Vec::new()), len: 0, node_capacity: 8 }
+    }
+
+    // how many entries a leaf holds before it splits; only affects the nodes split from now on
+    pub fn node_capacity(mut self, node_capacity: usize) -> QuadTree<T> {
+        assert!(node_capacity > 0, "a node has to hold at least one entry");
+        self.node_capacity = node_capacity;
+        self
+    }
+
+    // in bounds that fit the points
+    pub fn bulk_load(entries: Vec<(Point, T)>) -> QuadTree<T> {
+        let mut tree = QuadTree::new(Rectangle { p1: Point::origin(), p2: Point::new(1.0, 1.0) });
+        if let Some(&(first, _)) = entries.first() {
+            for &(point, _) in &entries {
+                check(point);
+            }
+            let fitted = entries.iter().fold(bounds(&[first]), |b, &(point, _)| bounds(&[b.p1, b.p2, point]));
+            tree.bounds = padded(fitted.p1, fitted.p2);
+        }
+        tree.len = entries.len();
+        tree.root = build(tree.bounds, entries, tree.node_capacity, 0);
+        tree
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    // what the tree covers now, which is more than it started with once it has grown
+    pub fn bounds(&self) -> Rectangle {
+        self.bounds
+    }
+
+    // panics if a coordinate is NaN or infinite
+    pub fn insert(&mut self, point: Point, value: T) {
+        check(point);
+        while !closed_contains(&self.bounds, point) {
+            self.grow_towards(point);
+        }
+        let mut node = &mut self.root;
+        let mut bounds = self.bounds;
+        let mut depth = 0;
+        loop {
+            match *node {
+                Node::Leaf(ref mut entries) if entries.len() < self.node_capacity || depth >= MAX_DEPTH => {
+                    entries.push((point, value));
+                    break;
+                },
+                Node::Leaf(_) => split(node, bounds),
+                Node::Branch(..) => {},
+            }
+            if let Node::Branch(middle, ref mut children) = *node {
+                let i = quadrant(middle, point);
+                bounds = quarter(&bounds, middle, i);
+                node = &mut children[i];
+                depth += 1;
+            }
+        }
+        self.len += 1;
+    }
+
+    // one of the values at exactly this point, if there are any
+    pub fn remove(&mut self, point: Point) -> Option<T> {
+        if !closed_contains(&self.bounds, point) {
+            return None;
+        }
+        let removed = remove(&mut self.root, self.bounds, point, self.node_capacity);
+        if removed.is_some() {
+            self.len -= 1;
+        }
+        removed
+    }
+
+    // the entries in the rectangle, edges included give or take EPSILON like Shape::contains_point
+    pub fn range(&self, rectangle: &Rectangle) -> Vec<(Point, &T)> {
+        let area = Rectangle { p1: rectangle.min(), p2: rectangle.max() };
+        let mut found = Vec::new();
+        let mut stack = vec![(&self.root, self.bounds)];
+        while let Some((node, bounds)) = stack.pop() {
+            if !overlaps(&bounds, &area) {
+                continue;
+            }
+            match *node {
+                Node::Leaf(ref entries) => found.extend(entries.iter()
+                    .filter(|&&(point, _)| near_contains(&area, point))
+                    .map(|&(point, ref value)| (point, value))),
+                Node::Branch(middle, ref children) =>
+                    stack.extend(children.iter().enumerate().map(|(i, child)| (child, quarter(&bounds, middle, i)))),
+            }
+        }
+        found
+    }
+
+    // the entries at most `radius` from the center, give or take EPSILON like Circle::contains_point
+    pub fn within(&self, center: Point, radius: f64) -> Vec<(Point, &T)> {
+        let reach = radius + EPSILON;
+        let mut found = Vec::new();
+        let mut stack = vec![(&self.root, self.bounds)];
+        while let Some((node, bounds)) = stack.pop() {
+            if distance_to(&bounds, center) > reach {
+                continue;
+            }
+            match *node {
+                Node::Leaf(ref entries) => found.extend(entries.iter()
+                    .filter(|&&(point, _)| point.distance(center) <= reach)
+                    .map(|&(point, ref value)| (point, value))),
+                Node::Branch(middle, ref children) =>
+                    stack.extend(children.iter().enumerate().map(|(i, child)| (child, quarter(&bounds, middle, i)))),
+            }
+        }
+        found
+    }
+
+    // the k entries closest to the point, closest first; entries as close as each other come in no particular order
+    pub fn nearest(&self, point: Point, k: usize) -> Vec<(Point, &T)> {
+        let mut found = Vec::with_capacity(k.min(self.len));
+        if k == 0 {
+            return found;
+        }
+        // nodes and entries alike, by how close they could be; an entry that comes off the heap first is the closest
+        // thing left, since no node can hold anything closer than its own distance
+        let mut heap = BinaryHeap::new();
+        heap.push(Candidate { distance: distance_to(&self.bounds, point), item: Item::Node(&self.root, self.bounds) });
+        while let Some(Candidate { item, .. }) = heap.pop() {
+            match item {
+                Item::Entry(at, value) => {
+                    found.push((at, value));
+                    if found.len() == k {
+                        break;
+                    }
+                },
+                Item::Node(Node::Leaf(entries), _) => heap.extend(entries.iter().map(|&(at, ref value)| {
+                    Candidate { distance: at.distance(point), item: Item::Entry(at, value) }
+                })),
+                Item::Node(&Node::Branch(middle, ref children), bounds) => {
+                    heap.extend(children.iter().enumerate().map(|(i, child)| {
+                        let bounds = quarter(&bounds, middle, i);
+                        Candidate { distance: distance_to(&bounds, point), item: Item::Node(child, bounds) }
+                    }))
+                },
+            }
+        }
+        found
+    }
+
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter { stack: vec![&self.root], entries: [].iter() }
+    }
+
+    // twice the size, with the old root as the quarter furthest from the point. The new middle is the old root's
+    // corner and the old edges are kept as they are, so the old root's bounds come out of `quarter` exactly
+    fn grow_towards(&mut self, point: Point) {
+        let (min, max) = (self.bounds.p1, self.bounds.p2);
+        // bounds too thin for their coordinates would grow by nothing
+        let (width, height) = ((max.x - min.x).max(spacing(min.x, max.x)), (max.y - min.y).max(spacing(min.y, max.y)));
+        let (left, below) = (point.x < min.x, point.y < min.y);
+        let (x1, middle_x, x2) = if left { (min.x - width, min.x, max.x) } else { (min.x, max.x, max.x + width) };
+        let (y1, middle_y, y2) = if below { (min.y - height, min.y, max.y) } else { (min.y, max.y, max.y + height) };
+        self.bounds = Rectangle { p1: Point::new(x1, y1), p2: Point::new(x2, y2) };
+        // the old root is east of the new middle when the tree grew to the west, and north of it when it grew south
+        let old = (left as usize) + 2 * (below as usize);
+        let mut children: [Node<T>; 4] = [(); 4].map(|_| Node::Leaf(Vec::new()));
+        children[old] = mem::replace(&mut self.root, Node::Leaf(Vec::new()));
+        self.root = Node::Branch(Point::new(middle_x, middle_y), Box::new(children));
+    }
+}
+
+impl<T> FromIterator<(Point, T)> for QuadTree<T> {
+    fn from_iter<I: IntoIterator<Item = (Point, T)>>(iter: I) -> QuadTree<T> {
+        QuadTree::bulk_load(iter.into_iter().collect())
+    }
+}
+
+impl<'a, T> IntoIterator for &'a QuadTree<T> {
+    type Item = (Point, &'a T);
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+// Every entry, in no particular order
+pub struct Iter<'a, T: 'a> {
+    stack: Vec<&'a Node<T>>,
+    entries: slice::Iter<'a, (Point, T)>,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = (Point, &'a T);
+
+    fn next(&mut self) -> Option<(Point, &'a T)> {
+        loop {
+            if let Some(&(point, ref value)) = self.entries.next() {
+                return Some((point, value));
+            }
+            match *self.stack.pop()? {
+                Node::Leaf(ref entries) => self.entries = entries.iter(),
+                Node::Branch(_, ref children) => self.stack.extend(children.iter()),
+            }
+        }
+    }
+}
+
+// NODES
+// 0 is the south west quarter, below and left of the middle, then south east, north west and north east; a point on
+// a middle line goes to the quarter above or right of it
+fn quadrant(middle: Point, point: Point) -> usize {
+    (point.x >= middle.x) as usize + 2 * (point.y >= middle.y) as usize
+}
+
+fn quarter(bounds: &Rectangle, middle: Point, i: usize) -> Rectangle {
+    let (min, max) = (bounds.p1, bounds.p2);
+    let (x1, x2) = if i & 1 == 0 { (min.x, middle.x) } else { (middle.x, max.x) };
+    let (y1, y2) = if i < 2 { (min.y, middle.y) } else { (middle.y, max.y) };
+    Rectangle { p1: Point::new(x1, y1), p2: Point::new(x2, y2) }
+}
+
+fn middle(bounds: &Rectangle) -> Point {
+    Point::new((bounds.p1.x + bounds.p2.x) / 2.0, (bounds.p1.y + bounds.p2.y) / 2.0)
+}
+
+// a leaf becomes a branch, its entries shared out between the new leaves
+fn split<T>(node: &mut Node<T>, bounds: Rectangle) {
+    if let Node::Leaf(ref mut entries) = *node {
+        let middle = middle(&bounds);
+        let mut children = [Vec::new(), Vec::new(), Vec::new(), Vec::new()];
+        for (point, value) in entries.drain(..) {
+            children[quadrant(middle, point)].push((point, value));
+        }
+        let [a, b, c, d] = children;
+        *node = Node::Branch(middle, Box::new([Node::Leaf(a), Node::Leaf(b), Node::Leaf(c), Node::Leaf(d)]));
+    }
+}
+
+fn build<T>(bounds: Rectangle, entries: Vec<(Point, T)>, capacity: usize, depth: usize) -> Node<T> {
+    if entries.len() <= capacity || depth >= MAX_DEPTH {
+        return Node::Leaf(entries);
+    }
+    let middle = middle(&bounds);
+    let mut quarters = [Vec::new(), Vec::new(), Vec::new(), Vec::new()];
+    for (point, value) in entries {
+        quarters[quadrant(middle, point)].push((point, value));
+    }
+    let [a, b, c, d] = quarters;
+    let child = |i, entries| build(quarter(&bounds, middle, i), entries, capacity, depth + 1);
+    Node::Branch(middle, Box::new([child(0, a), child(1, b), child(2, c), child(3, d)]))
+}
+
+// and merges a branch back into a leaf once its leaves hold few enough entries between them
+fn remove<T>(node: &mut Node<T>, bounds: Rectangle, point: Point, capacity: usize) -> Option<T> {
+    let removed = match *node {
+        Node::Leaf(ref mut entries) => {
+            let i = entries.iter().position(|&(at, _)| at == point)?;
+            return Some(entries.swap_remove(i).1);
+        },
+        Node::Branch(middle, ref mut children) => {
+            let i = quadrant(middle, point);
+            remove(&mut children[i], quarter(&bounds, middle, i), point, capacity)?
+        },
+    };
+    let merged = match *node {
+        Node::Branch(_, ref mut children) => {
+            let leaves = children.iter().map(|child| match *child {
+                Node::Leaf(ref entries) => Some(entries.len()),
+                Node::Branch(..) => None,
+            });
+            match leaves.sum::<Option<usize>>() {
+                Some(total) if total <= capacity => Some(children.iter_mut().flat_map(|child| match *child {
+                    Node::Leaf(ref mut entries) => entries.drain(..),
+                    Node::Branch(..) => unreachable!("only leaves are merged"),
+                }).collect()),
+                _ => None,
+            }
+        },
+        Node::Leaf(_) => None,
+    };
+    if let Some(entries) = merged {
+        *node = Node::Leaf(entries);
+    }
+    Some(removed)
+}
+
+// QUERIES
+struct Candidate<'a, T: 'a> {
+    distance: f64,
+    item: Item<'a, T>,
+}
+
+enum Item<'a, T: 'a> {
+    Node(&'a Node<T>, Rectangle),
+    Entry(Point, &'a T),
+}
+
+// reversed, so the BinaryHeap gives the closest first; an entry comes before a node at the same distance
+impl<'a, T> Ord for Candidate<'a, T> {
+    fn cmp(&self, other: &Candidate<'a, T>) -> Ordering {
+        let is_entry = |candidate: &Candidate<'a, T>| match candidate.item {
+            Item::Entry(..) => true,
+            Item::Node(..) => false,
+        };
+        other.distance.total_cmp(&self.distance).then(is_entry(self).cmp(&is_entry(other)))
+    }
+}
+
+impl<'a, T> PartialOrd for Candidate<'a, T> {
+    fn partial_cmp(&self, other: &Candidate<'a, T>) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<'a, T> PartialEq for Candidate<'a, T> {
+    fn eq(&self, other: &Candidate<'a, T>) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<'a, T> Eq for Candidate<'a, T> {}
+
+fn check(point: Point) {
+    assert!(point.x.is_finite() && point.y.is_finite(), "can't place ({}, {}) in a QuadTree", point.x, point.y);
+}
+
+// with some width and height to split, even for a single point; far from the origin half a unit would round away
+fn padded(min: Point, max: Point) -> Rectangle {
+    let pad = |low: f64, high: f64| {
+        if high > low {
+            (low, high)
+        } else {
+            let pad = spacing(low, high).max(0.5);
+            (low - pad, high + pad)
+        }
+    };
+    let ((x1, x2), (y1, y2)) = (pad(min.x, max.x), pad(min.y, max.y));
+    Rectangle { p1: Point::new(x1, y1), p2: Point::new(x2, y2) }
+}
+
+// a few ULPs of the larger coordinate: the least an edge can move by and still change
+fn spacing(a: f64, b: f64) -> f64 {
+    4.0 * f64::EPSILON * a.abs().max(b.abs())
+}
+
+fn closed_contains(bounds: &Rectangle, point: Point) -> bool {
+    bounds.p1.x <= point.x && point.x <= bounds.p2.x && bounds.p1.y <= point.y && point.y <= bounds.p2.y
+}
+
+fn near_contains(area: &Rectangle, point: Point) -> bool {
+    area.p1.x - EPSILON <= point.x && point.x <= area.p2.x + EPSILON &&
+        area.p1.y - EPSILON <= point.y && point.y <= area.p2.y + EPSILON
+}
+
+fn overlaps(bounds: &Rectangle, area: &Rectangle) -> bool {
+    bounds.p1.x <= area.p2.x + EPSILON && area.p1.x - EPSILON <= bounds.p2.x &&
+        bounds.p1.y <= area.p2.y + EPSILON && area.p1.y - EPSILON <= bounds.p2.y
+}
+
+// from the point to the closest point of the bounds, 0 inside them
+fn distance_to(bounds: &Rectangle, point: Point) -> f64 {
+    let dx = (bounds.p1.x - point.x).max(point.x - bounds.p2.x).max(0.0);
+    let dy = (bounds.p1.y - point.y).max(point.y - bounds.p2.y).max(0.0);
+    dx.hypot(dy)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // xorshift64*, enough to scatter points without a dependency
+    struct Rng(u64);
+
+    impl Rng {
+        fn next(&mut self) -> u64 {
+            self.0 ^= self.0 >> 12;
+            self.0 ^= self.0 << 25;
+            self.0 ^= self.0 >> 27;
+            self.0.wrapping_mul(0x2545_f491_4f6c_dd1d)
+        }
+
+        // in [low, high)
+        fn float(&mut self, low: f64, high: f64) -> f64 {
+            low + (self.next() >> 11) as f64 / (1u64 << 53) as f64 * (high - low)
+        }
+
+        // on a grid half the time, so that points share coordinates and sit on the middle lines of nodes
+        fn point(&mut self) -> Point {
+            if self.next() & 1 == 0 {
+                Point::new(self.float(0.0, 1000.0), self.float(0.0, 1000.0))
+            } else {
+                Point::new((self.next() % 11) as f64 * 100.0, (self.next() % 11) as f64 * 100.0)
+            }
+        }
+    }
+
+    fn sorted(mut found: Vec<(Point, &usize)>) -> Vec<usize> {
+        let mut ids: Vec<usize> = found.drain(..).map(|(_, &id)| id).collect();
+        ids.sort();
+        ids
+    }
+
+    // every query against a scan of all the entries
+    fn cross_check(tree: &QuadTree<usize>, entries: &[(Point, usize)], rng: &mut Rng) {
+        assert_eq!(tree.len(), entries.len());
+        assert_eq!(sorted(tree.iter().collect()), sorted(entries.iter().map(|&(p, ref id)| (p, id)).collect()));
+        for _ in 0..50 {
+            let area = Rectangle { p1: rng.point(), p2: rng.point() };
+            let ordered = Rectangle { p1: area.min(), p2: area.max() };
+            let expected = entries.iter().filter(|&&(p, _)| near_contains(&ordered, p));
+            assert_eq!(sorted(tree.range(&area)), sorted(expected.map(|&(p, ref id)| (p, id)).collect()));
+
+            let (center, radius) = (rng.point(), rng.float(0.0, 300.0));
+            let expected = entries.iter().filter(|&&(p, _)| p.distance(center) <= radius + EPSILON);
+            assert_eq!(sorted(tree.within(center, radius)), sorted(expected.map(|&(p, ref id)| (p, id)).collect()));
+
+            // ties make the order of equally close entries arbitrary, so it's the distances that have to agree
+            let k = (rng.next() % 20) as usize;
+            let mut distances: Vec<f64> = entries.iter().map(|&(p, _)| p.distance(center)).collect();
+            distances.sort_by(f64::total_cmp);
+            distances.truncate(k);
+            let nearest = tree.nearest(center, k);
+            assert_eq!(nearest.iter().map(|&(p, _)| p.distance(center)).collect::<Vec<_>>(), distances);
+            for &(p, &id) in &nearest {
+                assert!(entries.contains(&(p, id)));
+            }
+        }
+    }
+
+    #[test]
+    fn random_queries() {
+        let mut rng = Rng(0x9e37_79b9_7f4a_7c15);
+        let bounds = Rectangle { p1: Point::origin(), p2: Point::new(1000.0, 1000.0) };
+        let mut tree = QuadTree::new(bounds).node_capacity(4);
+        let mut entries = Vec::new();
+        for id in 0..3000 {
+            let point = rng.point();
+            tree.insert(point, id);
+            entries.push((point, id));
+        }
+        cross_check(&tree, &entries, &mut rng);
+
+        // removing half of them, some twice
+        for _ in 0..1500 {
+            let i = (rng.next() % entries.len() as u64) as usize;
+            let (point, _) = entries[i];
+            let id = tree.remove(point).unwrap();
+            let j = entries.iter().position(|&entry| entry == (point, id)).unwrap();
+            entries.swap_remove(j);
+            if !entries.iter().any(|&(p, _)| p == point) {
+                assert_eq!(tree.remove(point), None);
+            }
+        }
+        cross_check(&tree, &entries, &mut rng);
+    }
+
+    #[test]
+    fn bulk_load() {
+        let mut rng = Rng(42);
+        let entries: Vec<(Point, usize)> = (0..3000).map(|id| (rng.point(), id)).collect();
+        let mut tree: QuadTree<usize> = entries.iter().cloned().collect();
+        cross_check(&tree, &entries, &mut rng);
+
+        // and it takes more afterwards
+        let mut entries = entries;
+        for id in 3000..3500 {
+            let point = rng.point();
+            tree.insert(point, id);
+            entries.push((point, id));
+        }
+        cross_check(&tree, &entries, &mut rng);
+
+        let empty = QuadTree::<usize>::bulk_load(Vec::new());
+        assert!(empty.is_empty() && empty.nearest(Point::origin(), 3).is_empty());
+        let single = QuadTree::bulk_load(vec![(Point::new(5.0, 5.0), 'a')]);
+        assert_eq!(single.nearest(Point::origin(), 3), vec![(Point::new(5.0, 5.0), &'a')]);
+    }
+
+    #[test]
+    fn growing() {
+        let mut tree = QuadTree::new(Rectangle { p1: Point::new(1.0, 1.0), p2: Point::origin() }).node_capacity(2);
+        let mut rng = Rng(7);
+        let mut entries = Vec::new();
+        // further and further out, in every direction
+        for id in 0..500 {
+            let scale = 10f64.powi((id % 7) as i32);
+            let point = Point::new(rng.float(-scale, scale), rng.float(-scale, scale));
+            tree.insert(point, id);
+            entries.push((point, id));
+        }
+        assert!(entries.iter().all(|&(p, _)| closed_contains(&tree.bounds(), p)));
+        let bounds = tree.bounds();
+        assert!(bounds.p2.x - bounds.p1.x >= 1e6);
+        cross_check(&tree, &entries, &mut rng);
+    }
+
+    #[test]
+    fn huge_coordinates() {
+        // half a unit of padding rounds away at 1e20, which left bounds with no width to double
+        let mut tree = QuadTree::new(Rectangle { p1: Point::new(1e20, 0.0), p2: Point::new(1e20, 1.0) });
+        tree.insert(Point::new(2e20, 0.5), 1);
+        tree.insert(Point::new(-3e20, 1e300), 2);
+        let bounds = tree.bounds();
+        assert!(bounds.p1.x < 1e20 && bounds.p2.x > 1e20);
+        assert_eq!(tree.nearest(Point::new(2e20, 0.0), 1), vec![(Point::new(2e20, 0.5), &1)]);
+
+        // bounds only a few ULPs wide grow too
+        let mut tree = QuadTree::new(Rectangle { p1: Point::new(1e20, 1e20), p2: Point::new(1e20 + 16384.0, 1e20) });
+        tree.insert(Point::new(1e21, 1e20), 3);
+        assert_eq!(tree.len(), 1);
+    }
+
+    #[test]
+    fn shared_points() {
+        // far more values at one point than a node holds; past MAX_DEPTH the leaf takes them all
+        let mut tree = QuadTree::new(Rectangle { p1: Point::origin(), p2: Point::new(1.0, 1.0) }).node_capacity(1);
+        let point = Point::new(0.25, 0.75);
+        for id in 0..100 {
+            tree.insert(point, id);
+        }
+        tree.insert(Point::new(0.9, 0.1), 100);
+        assert_eq!(tree.within(point, 0.0).len(), 100);
+        assert_eq!(tree.nearest(Point::new(1.0, 0.0), 2)[0], (Point::new(0.9, 0.1), &100));
+        for _ in 0..100 {
+            assert!(tree.remove(point).is_some());
+        }
+        assert_eq!(tree.remove(point), None);
+        assert_eq!(tree.len(), 1);
+        // everything merged back into the root
+        match tree.root {
+            Node::Leaf(ref entries) => assert_eq!(entries.len(), 1),
+            Node::Branch(..) => panic!("expected the root to be a leaf again"),
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "can't place (NaN, 0) in a QuadTree")]
+    fn nan() {
+        let mut tree = QuadTree::new(Rectangle { p1: Point::origin(), p2: Point::new(1.0, 1.0) });
+        tree.insert(Point::new(f64::NAN, 0.0), ());
+    }
+}
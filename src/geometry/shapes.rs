@@ -0,0 +1,300 @@
+// SHAPES
+// The shapes that implement geometry::Shape. A Polygon can be any simple polygon, concave ones included, with its
+// corners going either way round; a Triangle is the same thing with three corners and less bookkeeping.
+
+use std::f64::consts::PI;
+
+use custom_types::{Point, Rectangle};
+
+use super::{bounds, cross, edges, polygon_contains, segments_cross, sub, Outline, Shape, EPSILON};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Circle {
+    pub center: Point,
+    pub radius: f64,
+}
+
+impl Circle {
+    pub fn new(center: Point, radius: f64) -> Circle {
+        Circle { center, radius }
+    }
+}
+
+impl Shape for Circle {
+    fn area(&self) -> f64 {
+        PI * self.radius * self.radius
+    }
+
+    fn perimeter(&self) -> f64 {
+        2.0 * PI * self.radius
+    }
+
+    fn centroid(&self) -> Point {
+        self.center
+    }
+
+    fn bounding_box(&self) -> Rectangle {
+        let Circle { center: Point { x, y }, radius } = *self;
+        Rectangle { p1: Point::new(x - radius, y - radius), p2: Point::new(x + radius, y + radius) }
+    }
+
+    fn contains_point(&self, point: Point) -> bool {
+        self.center.distance(point) <= self.radius + EPSILON
+    }
+
+    fn outline(&self) -> Outline {
+        Outline::Circle { center: self.center, radius: self.radius }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Triangle {
+    pub a: Point,
+    pub b: Point,
+    pub c: Point,
+}
+
+impl Triangle {
+    pub fn new(a: Point, b: Point, c: Point) -> Triangle {
+        Triangle { a, b, c }
+    }
+
+    pub fn corners(&self) -> [Point; 3] {
+        [self.a, self.b, self.c]
+    }
+}
+
+impl Shape for Triangle {
+    fn area(&self) -> f64 {
+        cross(sub(self.b, self.a), sub(self.c, self.a)).abs() / 2.0
+    }
+
+    fn perimeter(&self) -> f64 {
+        self.a.distance(self.b) + self.b.distance(self.c) + self.c.distance(self.a)
+    }
+
+    fn centroid(&self) -> Point {
+        Point::new((self.a.x + self.b.x + self.c.x) / 3.0, (self.a.y + self.b.y + self.c.y) / 3.0)
+    }
+
+    fn bounding_box(&self) -> Rectangle {
+        bounds(&self.corners())
+    }
+
+    fn contains_point(&self, point: Point) -> bool {
+        polygon_contains(&self.corners(), point)
+    }
+
+    fn outline(&self) -> Outline {
+        Outline::Polygon(self.corners().to_vec())
+    }
+}
+
+// The corners don't have to be in any order, so a Rectangle is measured from its min and max
+impl Rectangle {
+    // counterclockwise from the minimum corner
+    pub fn corners(&self) -> [Point; 4] {
+        let (min, max) = (self.min(), self.max());
+        [min, Point::new(max.x, min.y), max, Point::new(min.x, max.y)]
+    }
+}
+
+// Rectangle::contains is half-open for hit-testing widgets; contains_point is closed like every other Shape's
+impl Shape for Rectangle {
+    fn area(&self) -> f64 {
+        Rectangle::area(self)
+    }
+
+    fn perimeter(&self) -> f64 {
+        Rectangle::perimeter(self)
+    }
+
+    fn centroid(&self) -> Point {
+        Point::new((self.p1.x + self.p2.x) / 2.0, (self.p1.y + self.p2.y) / 2.0)
+    }
+
+    fn bounding_box(&self) -> Rectangle {
+        Rectangle { p1: self.min(), p2: self.max() }
+    }
+
+    fn contains_point(&self, point: Point) -> bool {
+        let (min, max) = (self.min(), self.max());
+        min.x - EPSILON <= point.x && point.x <= max.x + EPSILON &&
+            min.y - EPSILON <= point.y && point.y <= max.y + EPSILON
+    }
+
+    fn outline(&self) -> Outline {
+        Outline::Polygon(self.corners().to_vec())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Polygon {
+    corners: Vec<Point>,
+}
+
+impl Polygon {
+    // None with fewer than three corners, or with edges that cross each other, where the area and what is inside
+    // would be wrong. Edges may touch, as they do when a transform flattens the polygon onto a line
+    pub fn new(corners: Vec<Point>) -> Option<Polygon> {
+        if corners.len() < 3 || crosses_itself(&corners) { None } else { Some(Polygon { corners }) }
+    }
+
+    pub fn corners(&self) -> &[Point] {
+        &self.corners
+    }
+
+    // by the shoelace formula: positive when the corners go counterclockwise
+    fn signed_area(&self) -> f64 {
+        edges(&self.corners).map(|(p, q)| cross(p, q)).sum::<f64>() / 2.0
+    }
+}
+
+// every edge against every other one but its neighbours, which share a corner with it
+fn crosses_itself(corners: &[Point]) -> bool {
+    let edges: Vec<_> = edges(corners).collect();
+    let n = edges.len();
+    (0..n).any(|i| (i + 2..n).filter(|&j| (i, j) != (0, n - 1)).any(|j| {
+        let ((p, q), (r, s)) = (edges[i], edges[j]);
+        segments_cross(p, q, r, s)
+    }))
+}
+
+impl Shape for Polygon {
+    fn area(&self) -> f64 {
+        self.signed_area().abs()
+    }
+
+    fn perimeter(&self) -> f64 {
+        edges(&self.corners).map(|(p, q)| p.distance(q)).sum()
+    }
+
+    fn centroid(&self) -> Point {
+        let area = self.signed_area();
+        let n = self.corners.len() as f64;
+        if area.abs() <= EPSILON {
+            // all on one line, where the area gives no weights; the corners will have to do
+            let sum = self.corners.iter().fold(Point::origin(), |sum, p| Point::new(sum.x + p.x, sum.y + p.y));
+            return Point::new(sum.x / n, sum.y / n);
+        }
+        // relative to the first corner, which keeps the products small for polygons far from the origin
+        let origin = self.corners[0];
+        let (mut x, mut y) = (0.0, 0.0);
+        for (p, q) in edges(&self.corners) {
+            let (p, q) = (sub(p, origin), sub(q, origin));
+            let weight = cross(p, q);
+            x += (p.x + q.x) * weight;
+            y += (p.y + q.y) * weight;
+        }
+        Point::new(origin.x + x / (6.0 * area), origin.y + y / (6.0 * area))
+    }
+
+    fn bounding_box(&self) -> Rectangle {
+        bounds(&self.corners)
+    }
+
+    fn contains_point(&self, point: Point) -> bool {
+        polygon_contains(&self.corners, point)
+    }
+
+    fn outline(&self) -> Outline {
+        Outline::Polygon(self.corners.clone())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use geometry::approx_eq;
+
+    fn point_eq(a: Point, b: Point) -> bool {
+        approx_eq(a.x, b.x) && approx_eq(a.y, b.y)
+    }
+
+    #[test]
+    fn circle() {
+        let circle = Circle::new(Point::new(1.0, 2.0), 2.0);
+        assert!(approx_eq(circle.area(), 4.0 * PI) && approx_eq(circle.perimeter(), 4.0 * PI));
+        assert_eq!(circle.centroid(), Point::new(1.0, 2.0));
+        assert_eq!(circle.bounding_box(), Rectangle { p1: Point::new(-1.0, 0.0), p2: Point::new(3.0, 4.0) });
+        assert!(circle.contains_point(Point::new(3.0, 2.0)));
+        assert!(circle.contains_point(Point::new(1.0 + 2.0_f64.sqrt(), 2.0 + 2.0_f64.sqrt())));
+        assert!(!circle.contains_point(Point::new(2.5, 3.5)));
+    }
+
+    #[test]
+    fn triangle() {
+        let triangle = Triangle::new(Point::new(0.0, 0.0), Point::new(0.0, 3.0), Point::new(4.0, 0.0));
+        assert!(approx_eq(triangle.area(), 6.0) && approx_eq(triangle.perimeter(), 12.0));
+        assert!(point_eq(triangle.centroid(), Point::new(4.0 / 3.0, 1.0)));
+        assert_eq!(triangle.bounding_box(), Rectangle { p1: Point::new(0.0, 0.0), p2: Point::new(4.0, 3.0) });
+        // on the long edge, at a corner, and just past the long edge
+        assert!(triangle.contains_point(Point::new(2.0, 1.5)));
+        assert!(triangle.contains_point(Point::new(4.0, 0.0)));
+        assert!(!triangle.contains_point(Point::new(2.0, 1.5 + 1e-6)));
+        assert!(!triangle.contains_point(Point::new(-1.0, 0.0)));
+    }
+
+    #[test]
+    fn rectangle() {
+        // with the corners the other way round
+        let rectangle = Rectangle { p1: Point::new(3.0, 4.0), p2: Point::new(1.0, 2.0) };
+        assert_eq!(Shape::area(&rectangle), 4.0);
+        assert_eq!(Shape::perimeter(&rectangle), 8.0);
+        assert_eq!(rectangle.centroid(), Point::new(2.0, 3.0));
+        assert_eq!(rectangle.bounding_box(), Rectangle { p1: Point::new(1.0, 2.0), p2: Point::new(3.0, 4.0) });
+        // unlike `contains`, the far edges are inside
+        assert!(rectangle.contains_point(Point::new(3.0, 4.0)) && !rectangle.contains(Point::new(3.0, 4.0)));
+        assert!(!rectangle.contains_point(Point::new(3.0, 4.1)));
+        let polygon = Polygon::new(rectangle.corners().to_vec()).unwrap();
+        assert_eq!(polygon.area(), 4.0);
+        assert!(point_eq(polygon.centroid(), rectangle.centroid()));
+    }
+
+    #[test]
+    fn polygon() {
+        assert_eq!(Polygon::new(vec![Point::origin(), Point::new(1.0, 1.0)]), None);
+        // a bow tie, whose edges cross in the middle, and a pentagram
+        let bow_tie = vec![Point::new(0.0, 0.0), Point::new(2.0, 2.0), Point::new(2.0, 0.0), Point::new(0.0, 2.0)];
+        assert_eq!(Polygon::new(bow_tie), None);
+        let star = (0..5).map(|i| {
+            let angle = i as f64 * 4.0 * PI / 5.0;
+            Point::new(angle.cos(), angle.sin())
+        });
+        assert_eq!(Polygon::new(star.collect()), None);
+        // a corner touching another edge isn't a crossing
+        let touching = Polygon::new(vec![
+            Point::new(0.0, 0.0), Point::new(4.0, 0.0), Point::new(4.0, 2.0),
+            Point::new(2.0, 0.0), Point::new(0.0, 2.0),
+        ]);
+        assert!(touching.is_some());
+        // nor are edges on top of each other in a polygon squashed onto a line
+        let squashed = vec![Point::new(0.0, 0.0), Point::new(3.0, 3.0), Point::new(1.0, 1.0), Point::new(2.0, 2.0)];
+        assert!(Polygon::new(squashed).is_some());
+
+        // an L made of a 4 by 1 bar and a 1 by 3 post, clockwise
+        let l = Polygon::new(vec![
+            Point::new(0.0, 0.0), Point::new(0.0, 4.0), Point::new(1.0, 4.0),
+            Point::new(1.0, 1.0), Point::new(4.0, 1.0), Point::new(4.0, 0.0),
+        ]).unwrap();
+        assert!(l.signed_area() < 0.0);
+        assert_eq!(l.area(), 7.0);
+        assert_eq!(l.perimeter(), 16.0);
+        // the bar's centroid (2, 0.5) weighted 4 and the post's (0.5, 2.5) weighted 3
+        let centroid = Point::new((2.0 * 4.0 + 0.5 * 3.0) / 7.0, (0.5 * 4.0 + 2.5 * 3.0) / 7.0);
+        assert!(point_eq(l.centroid(), centroid));
+        assert!(!l.contains_point(centroid));
+        assert!(l.contains_point(Point::new(0.5, 3.5)) && l.contains_point(Point::new(1.0, 2.0)));
+        assert!(!l.contains_point(Point::new(2.0, 2.0)));
+        assert_eq!(l.bounding_box(), Rectangle { p1: Point::new(0.0, 0.0), p2: Point::new(4.0, 4.0) });
+
+        // far from the origin the centroid is still exact
+        let far = Polygon::new(l.corners().iter().map(|p| Point::new(p.x + 1e6, p.y - 1e6)).collect()).unwrap();
+        assert!(point_eq(far.centroid(), Point::new(centroid.x + 1e6, centroid.y - 1e6)));
+
+        // with no area, the centroid is the average of the corners
+        let flat = Polygon::new(vec![Point::new(0.0, 0.0), Point::new(1.0, 1.0), Point::new(5.0, 5.0)]).unwrap();
+        assert_eq!(flat.area(), 0.0);
+        assert_eq!(flat.centroid(), Point::new(2.0, 2.0));
+    }
+}
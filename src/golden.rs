@@ -0,0 +1,126 @@
+// GOLDEN OUTPUT
+// The output of every example is captured and compared against an expectation stored in `golden/<path>.txt`,
+// so that a change in behaviour, e.g. to `fizzbuzz` in functions/methods, is caught instead of going unnoticed.
+// `rbe check` reports the differences and `rbe bless` rewrites the expectations after an intended change.
+
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use catalog::Example;
+use custom_types::config::{self, Config};
+
+// the directory holding the expected output of every example
+pub fn dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("golden")
+}
+
+pub fn path(example: &Example) -> PathBuf {
+    dir().join(format!("{}.txt", example.path))
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Outcome {
+    Match,
+    // there is no stored expectation for the example yet
+    Missing,
+    // `line` is 1 based; a side that ran out of lines is `None`
+    Mismatch { line: usize, expected: Option<String>, actual: Option<String> },
+    // every line matches but one side ends with a newline and the other doesn't; `expected` is whether the
+    // expectation is the one that does
+    TrailingNewline { expected: bool },
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fn show(line: &Option<String>) -> String {
+            match *line {
+                Some(ref line) => format!("{:?}", line),
+                None => "end of output".to_owned(),
+            }
+        }
+
+        match *self {
+            Outcome::Match => write!(f, "ok"),
+            Outcome::Missing => write!(f, "no expected output; run `rbe bless` to record it"),
+            Outcome::Mismatch { line, ref expected, ref actual } =>
+                write!(f, "line {}: expected {}, got {}", line, show(expected), show(actual)),
+            Outcome::TrailingNewline { expected: true } => write!(f, "missing trailing newline"),
+            Outcome::TrailingNewline { expected: false } => write!(f, "unexpected trailing newline"),
+        }
+    }
+}
+
+// finds the first line where the captured output differs from the expectation
+pub fn compare(expected: &str, actual: &str) -> Outcome {
+    let mut expected_lines = expected.lines();
+    let mut actual_lines = actual.lines();
+    let mut line = 1;
+    loop {
+        match (expected_lines.next(), actual_lines.next()) {
+            (None, None) => break,
+            (Some(e), Some(a)) if e == a => line += 1,
+            (e, a) => return Outcome::Mismatch {
+                line,
+                expected: e.map(str::to_owned),
+                actual: a.map(str::to_owned),
+            },
+        }
+    }
+    // `lines` hides a missing or extra trailing newline
+    if expected.ends_with('\n') != actual.ends_with('\n') {
+        return Outcome::TrailingNewline { expected: expected.ends_with('\n') };
+    }
+    Outcome::Match
+}
+
+// The output of the example with the global config reset to the defaults, so that the expectation doesn't depend on
+// the environment, e.g. an $RBE_THRESHOLD, of whoever checks or blesses it
+fn output(example: &Example) -> io::Result<String> {
+    config::set_global(Config::defaults());
+    example.output()
+}
+
+pub fn check(example: &Example) -> io::Result<Outcome> {
+    let actual = output(example)?;
+    match fs::read_to_string(path(example)) {
+        Ok(expected) => Ok(compare(&expected, &actual)),
+        Err(ref e) if e.kind() == io::ErrorKind::NotFound => Ok(Outcome::Missing),
+        Err(e) => Err(e),
+    }
+}
+
+// records the current output of the example as its expectation
+pub fn bless(example: &Example) -> io::Result<()> {
+    let path = path(example);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, output(example)?)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use catalog::EXAMPLES;
+
+    #[test]
+    fn every_example_matches_its_golden_output() {
+        for example in EXAMPLES {
+            assert_eq!(check(example).unwrap(), Outcome::Match, "{}", example.path);
+        }
+    }
+
+    #[test]
+    fn compare_reports_the_first_differing_line() {
+        assert_eq!(compare("1\n2\n", "1\n2\n"), Outcome::Match);
+        assert_eq!(compare("1\nfizzbuzz\n", "1\n5\n"),
+                   Outcome::Mismatch { line: 2, expected: Some("fizzbuzz".to_owned()), actual: Some("5".to_owned()) });
+        assert_eq!(compare("1\n2\n", "1\n"), Outcome::Mismatch { line: 2, expected: Some("2".to_owned()), actual: None });
+        assert_eq!(compare("1\n2\n", "1\n").to_string(), "line 2: expected \"2\", got end of output");
+        assert_eq!(compare("1\n", "1"), Outcome::TrailingNewline { expected: true });
+        assert_eq!(compare("1\n", "1").to_string(), "missing trailing newline");
+        assert_eq!(compare("1", "1\n").to_string(), "unexpected trailing newline");
+    }
+}
@@ -0,0 +1,9 @@
+// HELLO WORLD
+// The traditional first program. println! is a macro that prints text to the console.
+
+use std::io::{self, Write};
+
+pub fn run(out: &mut impl Write) -> io::Result<()> {
+    writeln!(out, "Hello, world")?;
+    Ok(())
+}
@@ -0,0 +1,26 @@
+// rust-by-example as a library
+// Every chapter lives in its own module and exports the types used in that chapter, so other crates can
+// depend on `rust-by-example` and use them directly. Each example module also has a `run` function that
+// holds what used to be the binary's `main` and writes its output to the `io::Write` sink it is given; the
+// binaries in src/bin are thin drivers that pass it stdout, while `golden` captures it to check against
+// the expected output.
+
+pub mod hello;
+pub mod formatted_print;
+pub mod primitives;
+pub mod custom_types;
+pub mod variable_bindings;
+pub mod types;
+pub mod conversion;
+pub mod flow_control;
+pub mod functions;
+pub mod modules;
+pub mod generics;
+pub mod scoping;
+pub mod traits;
+pub mod macros;
+pub mod error;
+pub mod geometry;
+
+pub mod catalog;
+pub mod golden;
@@ -0,0 +1,6 @@
+// MACROS
+// Rust provides a powerful macro system that allows metaprogramming. Macros are expanded into source code
+// that gets compiled with the rest of the program.
+
+pub mod rules_syntax;
+pub mod dry_dsl_variadics;
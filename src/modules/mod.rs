@@ -0,0 +1,6 @@
+// MODULES
+// Rust provides a powerful module system that can be used to hierarchically split code in logical units (modules)
+// and manage visibility (public/private) between them.
+
+pub mod visibility;
+pub mod use_self_super;
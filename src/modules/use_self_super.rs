@@ -0,0 +1,62 @@
+// The use declaration can be used to bind a full path to a new name
+// an example - `import torch.data.utils.DataLoader as data_loader` becomes `use torch::data::utils::DataLoader as data_loader;`
+
+// use bindings have a local scope. if a use is declared in a scope, the binding is not valid outside that scope
+
+// SUPER AND SELF
+// these can be used in the path to remove ambiguity when accesing items and to prevent unnecesary hardcoding of paths
+
+use std::io::{self, Write};
+
+fn function(out: &mut impl Write) -> io::Result<()> {
+    writeln!(out, "called `function()`")
+}
+
+mod cool {
+    use std::io::{self, Write};
+
+    pub fn function(out: &mut impl Write) -> io::Result<()> {
+        writeln!(out, "called `cool::function()`")
+    }
+}
+
+mod my {
+    use std::io::{self, Write};
+
+    fn function(out: &mut impl Write) -> io::Result<()> {
+        writeln!(out, "called `my::function()`")
+    }
+
+    mod cool {
+        use std::io::{self, Write};
+
+        pub fn function(out: &mut impl Write) -> io::Result<()> {
+            writeln!(out, "called `my::cool::function()`")
+        }
+    }
+
+    pub fn indirect_call(out: &mut impl Write) -> io::Result<()> { // we'll access all the functions named functions() from this scope
+        write!(out, "called `my::indirect_call()` that\n ")?;
+
+        self::function(out)?; // the self keywork refers to the current module scope; in this case my
+        function(out)?; // self::function and function() give the same result.
+
+        // we can use self to access another module inside my
+        self::cool::function(out)?;
+
+        // the super keyword refers to the parent scope (outside the my module)
+        super::function(out)?;
+
+        { // this will bind the cool::function() in the example's scope - the outermost scope in this case
+            // paths in a use declaration start from the crate root
+            use modules::use_self_super::cool::function as root_function;
+            root_function(out)?;
+        }
+
+        Ok(())
+    }
+}
+
+pub fn run(out: &mut impl Write) -> io::Result<()> {
+    my::indirect_call(out)
+}
@@ -0,0 +1,137 @@
+// By default, all modules are private. Only public modules declared with pub can be accessed from outside the scope
+
+#![allow(clippy::print_with_newline)]
+
+use std::io::{self, Write};
+
+mod my_mod { // private module. Can be called in main() but not outside this code
+    use std::io::{self, Write};
+
+    fn private_function(out: &mut impl Write) -> io::Result<()> { // not available outside the mod. Cannot be called in main() but can be called inside this module
+        writeln!(out, "called `mod::private_function()`")
+    }
+
+    pub fn function(out: &mut impl Write) -> io::Result<()> { // public function - can be called anywhere where my_mod is visible
+        writeln!(out, "called `my_mod::function()`")
+    }
+
+    // items can access other items in the same module, even when private
+    pub fn indirect_access(out: &mut impl Write) -> io::Result<()> { // indirect access can be called in main() and can indirectly call private_function()
+        writeln!(out, "called `my_mod::indirect_access()` that\n>")?;
+        private_function(out)
+    }
+
+    pub mod nested {
+        use std::io::{self, Write};
+
+        pub fn function(out: &mut impl Write) -> io::Result<()> { // called as my_mod::nested::function()
+            writeln!(out, "called `my_mod::nested::function()")
+        }
+
+        #[allow(dead_code)]
+        fn private_function(out: &mut impl Write) -> io::Result<()> {
+            writeln!(out, "called `my_mod::nested::private_function()`")
+        }
+
+        // functions declared using `pub(in path)` are only visible within the given path
+        // path must be a parent or ancestor module, written from the crate root
+        pub(in modules::visibility::my_mod) fn public_function_in_my_mod(out: &mut impl Write) -> io::Result<()> {
+            writeln!(out, "called `my_mod::nested::public_function_in_my_mod()`, that\n")?;
+            public_function_in_nested(out)
+        }
+
+        // functions declared using `pub(self)` syntax are only visible within the parent module
+        #[allow(clippy::needless_pub_self)]
+        pub(self) fn public_function_in_nested(out: &mut impl Write) -> io::Result<()> {
+            writeln!(out, "called `my_mod::nested::public_function_in_nested()`")
+        }
+
+        // functions declared using `pub(super)` are only visible within the parent module
+        pub(super) fn public_function_in_super_mod(out: &mut impl Write) -> io::Result<()> {
+            writeln!(out, "called `my_mode::nested::public_function_in_super_mod()")
+        }
+    }
+
+    pub fn call_public_function_in_my_mod(out: &mut impl Write) -> io::Result<()> {
+        writeln!(out, "called `my_mod::call_public_function_in_my_mod()`, that")?;
+        nested::public_function_in_my_mod(out)?;
+        write!(out, "> ")?;
+        nested::public_function_in_super_mod(out)
+    }
+
+    // `pub(crate)` makes functions visible only within the current crate
+    pub(crate) fn public_function_in_crate(out: &mut impl Write) -> io::Result<()> {
+        writeln!(out, "called `my_mod::public_function_in_crate()`")
+    }
+
+    // Nested modules follow the same rules for visibility
+    mod private_nested {
+        use std::io::{self, Write};
+
+        #[allow(dead_code)]
+        pub fn function(out: &mut impl Write) -> io::Result<()> {
+            writeln!(out, "called `my_mod::private_nested::function()`")
+        }
+    }
+}
+
+fn function(out: &mut impl Write) -> io::Result<()> {
+    writeln!(out, "called `function()`")
+}
+
+// STRUCT VISIBILITY
+// struct visibility is private by default and can be overwritten by the pub modifier
+// this only matters when a struct is accessible from outside the module where it is defined and has the goal of hiding information
+mod my {
+    pub struct OpenBox<T> { // a public struct with a public field of generic type
+        pub contents: T,
+    }
+
+    #[allow(dead_code)]
+    pub struct ClosedBox<T> { // a public struct with a private field of generic type T
+        contents: T,
+    }
+
+    impl<T> ClosedBox<T> {
+        pub fn new(contents: T) -> ClosedBox<T> {
+            ClosedBox {
+                contents,
+            }
+        }
+    }
+}
+
+
+pub fn run(out: &mut impl Write) -> io::Result<()> {
+    function(out)?;
+    my_mod::function(out)?;
+
+    // public modules can be accessed from outside the parent module
+    my_mod::indirect_access(out)?;
+    my_mod::nested::function(out)?;
+    my_mod::call_public_function_in_my_mod(out)?;
+
+    // pub(crate) items can be called from anywhere in the same crate
+    my_mod::public_function_in_crate(out)?;
+
+    // The following cannot be called
+    // my_mod::nested::public_function_in_my_mod() // pub(in path) can only be called from the mode specified
+    // my_mod::private_function() // cannot be called outside the mod at all
+    // my_mod::nested::private() // also cannot be called as it is private
+    // my_mod::private_nested::function() // cannot be called as the module is private
+
+
+    // STRUCT VISIBILITY
+    // public structs with public fields can be constructed as usual
+    let open_box = my::OpenBox { contents: "public information" };
+    writeln!(out, "The open box contains {}", open_box.contents)?; // the fields are accesses as usual
+
+    // public structs with private fields cannot be constructed using field names
+    // let closed_box = my::ClosedBox { contents: "classified information" }
+    // however, structs with private field names can be created using public constructors
+    let _closed_box = my::ClosedBox::new("classified information");
+
+    // the private fields of a public struct cannot be accessed
+
+    Ok(())
+}
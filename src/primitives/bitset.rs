@@ -0,0 +1,596 @@
+// BITSETS
+// The bitwise operators on a u32 make a set of up to 32 flags; a bitset is the same idea at any width. BitSet<W>
+// holds W u64 words inline, 64 * W bits that are all there from the start, and BitVec holds its words on the heap
+// and grows when a bit past its end is inserted. Both have the operators `&`, `|`, `^`, `!`, `<<` and `>>` with
+// their assign forms, and everything else comes from the Bits trait.
+//
+// Bit i is bit i % 64 of word i / 64, and any bits of the last word past the width are always zero.
+
+use std::fmt;
+use std::iter::FromIterator;
+use std::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Not, Shl, ShlAssign, Shr, ShrAssign};
+
+const BITS: usize = 64;
+
+pub trait Bits {
+    fn words(&self) -> &[u64];
+    // the number of bits, set or not
+    fn width(&self) -> usize;
+
+    fn contains(&self, i: usize) -> bool {
+        i < self.width() && self.words()[i / BITS] & (1 << (i % BITS)) != 0
+    }
+
+    fn is_empty(&self) -> bool {
+        self.words().iter().all(|&word| word == 0)
+    }
+
+    // popcount
+    fn count_ones(&self) -> usize {
+        self.words().iter().map(|word| word.count_ones() as usize).sum()
+    }
+
+    fn count_zeros(&self) -> usize {
+        self.width() - self.count_ones()
+    }
+
+    // the unset bits above the highest set bit; the width when none is set
+    fn leading_zeros(&self) -> usize {
+        match self.ones().last() {
+            Some(highest) => self.width() - 1 - highest,
+            None => self.width(),
+        }
+    }
+
+    // the unset bits below the lowest set bit; the width when none is set
+    fn trailing_zeros(&self) -> usize {
+        self.ones().next().unwrap_or_else(|| self.width())
+    }
+
+    // the indices of the set bits, lowest first
+    fn ones(&self) -> Ones<'_> {
+        Ones { words: self.words(), index: 0, word: self.words().first().cloned().unwrap_or(0) }
+    }
+
+    // how many bits below i are set
+    fn rank(&self, i: usize) -> usize {
+        let i = i.min(self.width());
+        let whole: usize = self.words()[..i / BITS].iter().map(|word| word.count_ones() as usize).sum();
+        match i % BITS {
+            0 => whole,
+            partial => whole + (self.words()[i / BITS] & ((1 << partial) - 1)).count_ones() as usize,
+        }
+    }
+
+    // the index of the set bit with rank n, so the lowest is `select(0)`; the inverse of rank
+    fn select(&self, mut n: usize) -> Option<usize> {
+        for (index, &word) in self.words().iter().enumerate() {
+            let ones = word.count_ones() as usize;
+            if n < ones {
+                // drop the n lowest set bits and take the next one
+                let mut word = word;
+                for _ in 0..n {
+                    word &= word - 1;
+                }
+                return Some(index * BITS + word.trailing_zeros() as usize);
+            }
+            n -= ones;
+        }
+        None
+    }
+}
+
+pub struct Ones<'a> {
+    words: &'a [u64],
+    index: usize,
+    // what's left of words[index]
+    word: u64,
+}
+
+impl<'a> Iterator for Ones<'a> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        while self.word == 0 {
+            self.index += 1;
+            self.word = *self.words.get(self.index)?;
+        }
+        let bit = self.word.trailing_zeros() as usize;
+        // clear the lowest set bit
+        self.word &= self.word - 1;
+        Some(self.index * BITS + bit)
+    }
+}
+
+fn shift_left(words: &mut [u64], n: usize) {
+    let (skip, bits) = (n / BITS, n % BITS);
+    // from the top down, so every word is read before it is overwritten
+    for i in (0..words.len()).rev() {
+        words[i] = if i < skip {
+            0
+        } else if bits == 0 || i == skip {
+            words[i - skip] << bits
+        } else {
+            words[i - skip] << bits | words[i - skip - 1] >> (BITS - bits)
+        };
+    }
+}
+
+fn shift_right(words: &mut [u64], n: usize) {
+    let (skip, bits) = (n / BITS, n % BITS);
+    let len = words.len();
+    for i in 0..len {
+        words[i] = if i + skip >= len {
+            0
+        } else if bits == 0 || i + skip + 1 == len {
+            words[i + skip] >> bits
+        } else {
+            words[i + skip] >> bits | words[i + skip + 1] << (BITS - bits)
+        };
+    }
+}
+
+// Binary, highest bit first, with a `_` between nibbles: `0000_0101`. `{:#}` adds a `0b` prefix.
+fn binary(bits: &dyn Bits, f: &mut fmt::Formatter) -> fmt::Result {
+    if f.alternate() {
+        write!(f, "0b")?;
+    }
+    for i in (0..bits.width()).rev() {
+        write!(f, "{}", if bits.contains(i) { '1' } else { '0' })?;
+        if i % 4 == 0 && i > 0 {
+            write!(f, "_")?;
+        }
+    }
+    Ok(())
+}
+
+// FIXED WIDTH
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BitSet<const WORDS: usize> {
+    pub words: [u64; WORDS],
+}
+
+impl<const WORDS: usize> BitSet<WORDS> {
+    pub const WIDTH: usize = WORDS * BITS;
+
+    pub fn new() -> BitSet<WORDS> {
+        BitSet { words: [0; WORDS] }
+    }
+
+    // every bit set
+    pub fn full() -> BitSet<WORDS> {
+        BitSet { words: [!0; WORDS] }
+    }
+
+    // returns whether the bit was newly set; panics when i is past the width, like indexing an array
+    pub fn insert(&mut self, i: usize) -> bool {
+        assert!(i < Self::WIDTH, "bit {} is out of range for a BitSet of {} bits", i, Self::WIDTH);
+        let was = self.contains(i);
+        self.words[i / BITS] |= 1 << (i % BITS);
+        !was
+    }
+
+    // returns whether the bit was set
+    pub fn remove(&mut self, i: usize) -> bool {
+        let was = self.contains(i);
+        if was {
+            self.words[i / BITS] &= !(1 << (i % BITS));
+        }
+        was
+    }
+
+    pub fn toggle(&mut self, i: usize) {
+        if !self.remove(i) {
+            self.insert(i);
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.words = [0; WORDS];
+    }
+}
+
+impl<const WORDS: usize> Default for BitSet<WORDS> {
+    fn default() -> BitSet<WORDS> {
+        BitSet::new()
+    }
+}
+
+impl<const WORDS: usize> Bits for BitSet<WORDS> {
+    fn words(&self) -> &[u64] { &self.words }
+    fn width(&self) -> usize { Self::WIDTH }
+}
+
+// a single word: `BitSet::from(0b0101)`
+impl From<u64> for BitSet<1> {
+    fn from(word: u64) -> BitSet<1> {
+        BitSet { words: [word] }
+    }
+}
+
+impl<const WORDS: usize> From<[u64; WORDS]> for BitSet<WORDS> {
+    fn from(words: [u64; WORDS]) -> BitSet<WORDS> {
+        BitSet { words }
+    }
+}
+
+impl<const WORDS: usize> FromIterator<usize> for BitSet<WORDS> {
+    fn from_iter<I: IntoIterator<Item = usize>>(iter: I) -> BitSet<WORDS> {
+        let mut set = BitSet::new();
+        for i in iter {
+            set.insert(i);
+        }
+        set
+    }
+}
+
+impl<'a, const WORDS: usize> IntoIterator for &'a BitSet<WORDS> {
+    type Item = usize;
+    type IntoIter = Ones<'a>;
+
+    fn into_iter(self) -> Ones<'a> {
+        self.ones()
+    }
+}
+
+macro_rules! bitset_op {
+    ($($op:ident $method:ident $assign:ident $assign_method:ident $symbol:tt),*) => {
+        $(
+            impl<const WORDS: usize> $assign for BitSet<WORDS> {
+                fn $assign_method(&mut self, other: BitSet<WORDS>) {
+                    for (word, other) in self.words.iter_mut().zip(&other.words) {
+                        *word = *word $symbol *other;
+                    }
+                }
+            }
+
+            impl<const WORDS: usize> $op for BitSet<WORDS> {
+                type Output = BitSet<WORDS>;
+
+                fn $method(mut self, other: BitSet<WORDS>) -> BitSet<WORDS> {
+                    self.$assign_method(other);
+                    self
+                }
+            }
+        )*
+    };
+}
+
+bitset_op!(BitAnd bitand BitAndAssign bitand_assign &,
+           BitOr bitor BitOrAssign bitor_assign |,
+           BitXor bitxor BitXorAssign bitxor_assign ^);
+
+impl<const WORDS: usize> Not for BitSet<WORDS> {
+    type Output = BitSet<WORDS>;
+
+    fn not(mut self) -> BitSet<WORDS> {
+        for word in self.words.iter_mut() {
+            *word = !*word;
+        }
+        self
+    }
+}
+
+// bits shifted past either end are dropped, as with the integer shifts (but shifting by the width or more is fine)
+impl<const WORDS: usize> ShlAssign<usize> for BitSet<WORDS> {
+    fn shl_assign(&mut self, n: usize) {
+        shift_left(&mut self.words, n);
+    }
+}
+
+impl<const WORDS: usize> ShrAssign<usize> for BitSet<WORDS> {
+    fn shr_assign(&mut self, n: usize) {
+        shift_right(&mut self.words, n);
+    }
+}
+
+impl<const WORDS: usize> Shl<usize> for BitSet<WORDS> {
+    type Output = BitSet<WORDS>;
+
+    fn shl(mut self, n: usize) -> BitSet<WORDS> {
+        self <<= n;
+        self
+    }
+}
+
+impl<const WORDS: usize> Shr<usize> for BitSet<WORDS> {
+    type Output = BitSet<WORDS>;
+
+    fn shr(mut self, n: usize) -> BitSet<WORDS> {
+        self >>= n;
+        self
+    }
+}
+
+impl<const WORDS: usize> fmt::Display for BitSet<WORDS> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        binary(self, f)
+    }
+}
+
+// GROWABLE
+// A BitVec has a width of `len` bits, which grows to fit any bit inserted past it. Operators between BitVecs of
+// different widths treat the missing bits of the narrower one as unset and give a result as wide as the wider one.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct BitVec {
+    words: Vec<u64>,
+    len: usize,
+}
+
+impl BitVec {
+    pub fn new() -> BitVec {
+        BitVec::default()
+    }
+
+    // len unset bits
+    pub fn with_len(len: usize) -> BitVec {
+        BitVec { words: vec![0; len.div_ceil(BITS)], len }
+    }
+
+    // grows or truncates to len bits, with any new bits unset
+    pub fn resize(&mut self, len: usize) {
+        self.words.resize(len.div_ceil(BITS), 0);
+        self.len = len;
+        self.trim();
+    }
+
+    // returns whether the bit was newly set
+    pub fn insert(&mut self, i: usize) -> bool {
+        if i >= self.len {
+            self.resize(i + 1);
+        }
+        let was = self.contains(i);
+        self.words[i / BITS] |= 1 << (i % BITS);
+        !was
+    }
+
+    // returns whether the bit was set; the width stays the same
+    pub fn remove(&mut self, i: usize) -> bool {
+        let was = self.contains(i);
+        if was {
+            self.words[i / BITS] &= !(1 << (i % BITS));
+        }
+        was
+    }
+
+    pub fn toggle(&mut self, i: usize) {
+        if !self.remove(i) {
+            self.insert(i);
+        }
+    }
+
+    pub fn clear(&mut self) {
+        for word in self.words.iter_mut() {
+            *word = 0;
+        }
+    }
+
+    // unset the bits of the last word past len, which `!` and `<<` would otherwise leave set
+    fn trim(&mut self) {
+        if !self.len.is_multiple_of(BITS) {
+            if let Some(last) = self.words.last_mut() {
+                *last &= (1 << (self.len % BITS)) - 1;
+            }
+        }
+    }
+}
+
+impl Bits for BitVec {
+    fn words(&self) -> &[u64] { &self.words }
+    fn width(&self) -> usize { self.len }
+}
+
+// the fixed-width set's bits, as wide
+impl<const WORDS: usize> From<BitSet<WORDS>> for BitVec {
+    fn from(set: BitSet<WORDS>) -> BitVec {
+        BitVec { words: set.words.to_vec(), len: BitSet::<WORDS>::WIDTH }
+    }
+}
+
+// just wide enough for the highest bit
+impl FromIterator<usize> for BitVec {
+    fn from_iter<I: IntoIterator<Item = usize>>(iter: I) -> BitVec {
+        let mut set = BitVec::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl Extend<usize> for BitVec {
+    fn extend<I: IntoIterator<Item = usize>>(&mut self, iter: I) {
+        for i in iter {
+            self.insert(i);
+        }
+    }
+}
+
+impl<'a> IntoIterator for &'a BitVec {
+    type Item = usize;
+    type IntoIter = Ones<'a>;
+
+    fn into_iter(self) -> Ones<'a> {
+        self.ones()
+    }
+}
+
+macro_rules! bitvec_op {
+    ($($op:ident $method:ident $assign:ident $assign_method:ident $symbol:tt),*) => {
+        $(
+            impl<'a> $assign<&'a BitVec> for BitVec {
+                fn $assign_method(&mut self, other: &'a BitVec) {
+                    if other.len > self.len {
+                        self.resize(other.len);
+                    }
+                    for (i, word) in self.words.iter_mut().enumerate() {
+                        *word = *word $symbol other.words.get(i).cloned().unwrap_or(0);
+                    }
+                }
+            }
+
+            impl $assign for BitVec {
+                fn $assign_method(&mut self, other: BitVec) {
+                    self.$assign_method(&other);
+                }
+            }
+
+            impl<'a> $op<&'a BitVec> for &'a BitVec {
+                type Output = BitVec;
+
+                fn $method(self, other: &'a BitVec) -> BitVec {
+                    let mut result = self.clone();
+                    result.$assign_method(other);
+                    result
+                }
+            }
+
+            impl $op for BitVec {
+                type Output = BitVec;
+
+                fn $method(mut self, other: BitVec) -> BitVec {
+                    self.$assign_method(&other);
+                    self
+                }
+            }
+        )*
+    };
+}
+
+bitvec_op!(BitAnd bitand BitAndAssign bitand_assign &,
+           BitOr bitor BitOrAssign bitor_assign |,
+           BitXor bitxor BitXorAssign bitxor_assign ^);
+
+// flips the bits within the width
+impl Not for BitVec {
+    type Output = BitVec;
+
+    fn not(mut self) -> BitVec {
+        for word in self.words.iter_mut() {
+            *word = !*word;
+        }
+        self.trim();
+        self
+    }
+}
+
+impl Not for &BitVec {
+    type Output = BitVec;
+
+    fn not(self) -> BitVec {
+        !self.clone()
+    }
+}
+
+// shifts keep the width, so bits shifted past either end are dropped as with BitSet
+impl ShlAssign<usize> for BitVec {
+    fn shl_assign(&mut self, n: usize) {
+        shift_left(&mut self.words, n);
+        self.trim();
+    }
+}
+
+impl ShrAssign<usize> for BitVec {
+    fn shr_assign(&mut self, n: usize) {
+        shift_right(&mut self.words, n);
+    }
+}
+
+impl Shl<usize> for BitVec {
+    type Output = BitVec;
+
+    fn shl(mut self, n: usize) -> BitVec {
+        self <<= n;
+        self
+    }
+}
+
+impl Shr<usize> for BitVec {
+    type Output = BitVec;
+
+    fn shr(mut self, n: usize) -> BitVec {
+        self >>= n;
+        self
+    }
+}
+
+impl fmt::Display for BitVec {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        binary(self, f)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn operators_match_the_integer_ones() {
+        let (a, b) = (0b0011u64, 0b0101u64);
+        let (x, y) = (BitSet::from(a), BitSet::from(b));
+        assert_eq!(x & y, BitSet::from(a & b));
+        assert_eq!(x | y, BitSet::from(a | b));
+        assert_eq!(x ^ y, BitSet::from(a ^ b));
+        assert_eq!(!x, BitSet::from(!a));
+        assert_eq!(BitSet::from(1) << 5, BitSet::from(1 << 5));
+        assert_eq!(BitSet::from(0x80) >> 2, BitSet::from(0x80 >> 2));
+
+        let mut z = x;
+        z |= y;
+        z <<= 1;
+        assert_eq!(z, BitSet::from((a | b) << 1));
+    }
+
+    #[test]
+    fn shifts_cross_words() {
+        let mut set = BitSet::<3>::new();
+        set.insert(63);
+        assert_eq!((set << 1).ones().collect::<Vec<_>>(), vec![64]);
+        assert_eq!((set << 65).ones().collect::<Vec<_>>(), vec![128]);
+        assert_eq!((set << 129), BitSet::new());
+        assert_eq!((BitSet::<3>::from_iter(vec![130, 64]) >> 66).ones().collect::<Vec<_>>(), vec![64]);
+        assert_eq!(BitSet::<3>::full() >> 192, BitSet::new());
+    }
+
+    #[test]
+    fn queries() {
+        let set: BitSet<2> = vec![3, 64, 70, 100].into_iter().collect();
+        assert_eq!(set.count_ones(), 4);
+        assert_eq!(set.count_zeros(), 124);
+        assert_eq!(set.trailing_zeros(), 3);
+        assert_eq!(set.leading_zeros(), 27);
+        assert_eq!(BitSet::<2>::new().leading_zeros(), 128);
+        assert_eq!((&set).into_iter().collect::<Vec<_>>(), vec![3, 64, 70, 100]);
+        assert_eq!((set.rank(0), set.rank(4), set.rank(64), set.rank(65), set.rank(500)), (0, 1, 1, 2, 4));
+        assert_eq!((set.select(0), set.select(2), set.select(3), set.select(4)), (Some(3), Some(70), Some(100), None));
+        for n in 0..4 {
+            assert_eq!(set.rank(set.select(n).unwrap()), n);
+        }
+    }
+
+    #[test]
+    fn bitvec_grows() {
+        let mut set = BitVec::new();
+        assert!(set.insert(5));
+        assert!(!set.insert(5));
+        assert_eq!(set.width(), 6);
+        set.insert(130);
+        assert_eq!(set.width(), 131);
+        assert_eq!(set.ones().collect::<Vec<_>>(), vec![5, 130]);
+        assert_eq!((!&set).count_ones(), 129);
+        assert_eq!((set.clone() << 1).ones().collect::<Vec<_>>(), vec![6]);
+        assert_eq!((set.clone() >> 5).ones().collect::<Vec<_>>(), vec![0, 125]);
+
+        let small: BitVec = vec![0, 5].into_iter().collect();
+        assert_eq!((&set & &small).ones().collect::<Vec<_>>(), vec![5]);
+        assert_eq!((&small | &set).width(), 131);
+        assert_eq!((small ^ set).ones().collect::<Vec<_>>(), vec![0, 130]);
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(BitSet::from(0b1_0110).to_string().rsplit('_').take(2).collect::<Vec<_>>(), vec!["0110", "0001"]);
+        let set: BitVec = vec![0, 2, 5].into_iter().collect();
+        assert_eq!(set.to_string(), "10_0101");
+        assert_eq!(format!("{:#}", BitVec::with_len(4)), "0b0000");
+        assert_eq!(BitVec::new().to_string(), "");
+    }
+}
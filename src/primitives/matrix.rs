@@ -0,0 +1,365 @@
+// MATRIX
+// An R x C matrix stored as an array of rows. The dimensions are const generic parameters, so they are part of
+// the type: adding a 2x3 matrix to a 3x2 one, or multiplying a 2x3 matrix by another 2x3, doesn't compile
+// rather than failing when it runs:
+//   let a: Matrix<f64, 2, 3> = Matrix::zeros();
+//   let b: Matrix<f64, 3, 2> = Matrix::zeros();
+//   let c = a + b;     // error: expected `Matrix<f64, 2, 3>`, found `Matrix<f64, 3, 2>`
+//   let d = a * b;     // fine, a Matrix<f64, 2, 2>
+// Identity, determinant and inverse only exist for square matrices, i.e. Matrix<T, N, N>. Determinant and inverse
+// also need negative numbers, so a Matrix of u32 doesn't have them: eliminating rows subtracts, and would overflow.
+
+use std::array;
+use std::fmt;
+use std::ops::{Add, Div, Index, IndexMut, Mul, Neg, Sub};
+
+// The element types: the integer and float primitives
+pub trait Scalar: Copy + PartialEq + fmt::Debug +
+    Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self> + Div<Output = Self> {
+    fn zero() -> Self;
+    fn one() -> Self;
+}
+
+// Scalars that can be negative, which the determinant needs
+pub trait Signed: Scalar + Neg<Output = Self> {}
+
+// Scalars where division is exact enough to invert a matrix
+pub trait Float: Signed + PartialOrd {
+    fn abs(self) -> Self;
+}
+
+macro_rules! scalar {
+    ($zero:expr, $one:expr; $($t:ty),*) => {
+        $(
+            impl Scalar for $t {
+                fn zero() -> $t { $zero }
+                fn one() -> $t { $one }
+            }
+        )*
+    };
+}
+
+scalar!(0, 1; i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+scalar!(0.0, 1.0; f32, f64);
+
+macro_rules! signed {
+    ($($t:ty),*) => {
+        $(impl Signed for $t {})*
+    };
+}
+
+signed!(i8, i16, i32, i64, i128, isize, f32, f64);
+
+impl Float for f32 {
+    fn abs(self) -> f32 { f32::abs(self) }
+}
+
+impl Float for f64 {
+    fn abs(self) -> f64 { f64::abs(self) }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Matrix<T, const R: usize, const C: usize>(pub [[T; C]; R]);
+
+impl<T: Copy, const R: usize, const C: usize> Matrix<T, R, C> {
+    pub fn new(rows: [[T; C]; R]) -> Matrix<T, R, C> {
+        Matrix(rows)
+    }
+
+    // the element at (row, column) is f(row, column)
+    pub fn from_fn<F: FnMut(usize, usize) -> T>(mut f: F) -> Matrix<T, R, C> {
+        Matrix(array::from_fn(|row| array::from_fn(|column| f(row, column))))
+    }
+
+    pub fn rows_count(&self) -> usize { R }
+    pub fn columns_count(&self) -> usize { C }
+
+    pub fn get(&self, row: usize, column: usize) -> Option<T> {
+        self.0.get(row).and_then(|row| row.get(column)).cloned()
+    }
+
+    pub fn row(&self, row: usize) -> Option<&[T; C]> {
+        self.0.get(row)
+    }
+
+    pub fn rows(&self) -> ::std::slice::Iter<'_, [T; C]> {
+        self.0.iter()
+    }
+
+    // the elements of a column from top to bottom, or None if there is no such column
+    pub fn column(&self, column: usize) -> Option<Column<'_, T, R, C>> {
+        if column < C { Some(Column { matrix: self, column, row: 0 }) } else { None }
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = Column<'_, T, R, C>> {
+        (0..C).map(move |column| Column { matrix: self, column, row: 0 })
+    }
+
+    pub fn transpose(&self) -> Matrix<T, C, R> {
+        Matrix::from_fn(|row, column| self.0[column][row])
+    }
+
+    pub fn map<U: Copy, F: FnMut(T) -> U>(&self, mut f: F) -> Matrix<U, R, C> {
+        Matrix::from_fn(|row, column| f(self.0[row][column]))
+    }
+}
+
+impl<T: Scalar, const R: usize, const C: usize> Matrix<T, R, C> {
+    pub fn zeros() -> Matrix<T, R, C> {
+        Matrix([[T::zero(); C]; R])
+    }
+
+    pub fn scale(&self, k: T) -> Matrix<T, R, C> {
+        self.map(|x| x * k)
+    }
+}
+
+impl<T: Scalar, const N: usize> Matrix<T, N, N> {
+    pub fn identity() -> Matrix<T, N, N> {
+        Matrix::from_fn(|row, column| if row == column { T::one() } else { T::zero() })
+    }
+}
+
+impl<T: Signed, const N: usize> Matrix<T, N, N> {
+    // Bareiss' fraction-free elimination: every division is exact, so integer matrices get an exact result
+    pub fn determinant(&self) -> T {
+        if N == 0 {
+            return T::one();
+        }
+        let mut m = self.0;
+        let mut sign = T::one();
+        let mut previous = T::one();
+        for k in 0..N - 1 {
+            if m[k][k] == T::zero() {
+                match (k + 1..N).find(|&row| m[row][k] != T::zero()) {
+                    Some(row) => {
+                        m.swap(k, row);
+                        sign = -sign;
+                    },
+                    None => return T::zero(),
+                }
+            }
+            for i in k + 1..N {
+                for j in k + 1..N {
+                    m[i][j] = (m[i][j] * m[k][k] - m[i][k] * m[k][j]) / previous;
+                }
+            }
+            previous = m[k][k];
+        }
+        sign * m[N - 1][N - 1]
+    }
+}
+
+impl<T: Float, const N: usize> Matrix<T, N, N> {
+    // Gauss-Jordan elimination with partial pivoting; None if the matrix is singular
+    pub fn inverse(&self) -> Option<Matrix<T, N, N>> {
+        let mut m = self.0;
+        let mut inverse = Matrix::<T, N, N>::identity().0;
+        for k in 0..N {
+            // the largest pivot keeps the rounding errors small
+            let pivot = (k..N).fold(k, |best, row| if m[row][k].abs() > m[best][k].abs() { row } else { best });
+            if m[pivot][k] == T::zero() {
+                return None;
+            }
+            m.swap(k, pivot);
+            inverse.swap(k, pivot);
+            let p = m[k][k];
+            for j in 0..N {
+                m[k][j] = m[k][j] / p;
+                inverse[k][j] = inverse[k][j] / p;
+            }
+            for i in (0..N).filter(|&i| i != k) {
+                let factor = m[i][k];
+                for j in 0..N {
+                    m[i][j] = m[i][j] - factor * m[k][j];
+                    inverse[i][j] = inverse[i][j] - factor * inverse[k][j];
+                }
+            }
+        }
+        Some(Matrix(inverse))
+    }
+}
+
+// COLUMNS
+pub struct Column<'a, T: 'a, const R: usize, const C: usize> {
+    matrix: &'a Matrix<T, R, C>,
+    column: usize,
+    row: usize,
+}
+
+impl<'a, T: Copy, const R: usize, const C: usize> Iterator for Column<'a, T, R, C> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        let value = self.matrix.get(self.row, self.column)?;
+        self.row += 1;
+        Some(value)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (R - self.row, Some(R - self.row))
+    }
+}
+
+impl<'a, T: Copy, const R: usize, const C: usize> ExactSizeIterator for Column<'a, T, R, C> {}
+
+// OPERATORS
+// Both sides of + and - have the same type, so their dimensions must match
+impl<T: Scalar, const R: usize, const C: usize> Add for Matrix<T, R, C> {
+    type Output = Matrix<T, R, C>;
+
+    fn add(self, rhs: Matrix<T, R, C>) -> Matrix<T, R, C> {
+        Matrix::from_fn(|row, column| self.0[row][column] + rhs.0[row][column])
+    }
+}
+
+impl<T: Scalar, const R: usize, const C: usize> Sub for Matrix<T, R, C> {
+    type Output = Matrix<T, R, C>;
+
+    fn sub(self, rhs: Matrix<T, R, C>) -> Matrix<T, R, C> {
+        Matrix::from_fn(|row, column| self.0[row][column] - rhs.0[row][column])
+    }
+}
+
+// An R x C matrix times a C x K one is R x K; the shared C is what makes a mismatch a type error
+impl<T: Scalar, const R: usize, const C: usize, const K: usize> Mul<Matrix<T, C, K>> for Matrix<T, R, C> {
+    type Output = Matrix<T, R, K>;
+
+    fn mul(self, rhs: Matrix<T, C, K>) -> Matrix<T, R, K> {
+        Matrix::from_fn(|row, column| (0..C).fold(T::zero(), |sum, i| sum + self.0[row][i] * rhs.0[i][column]))
+    }
+}
+
+impl<T, const R: usize, const C: usize> Index<(usize, usize)> for Matrix<T, R, C> {
+    type Output = T;
+
+    fn index(&self, (row, column): (usize, usize)) -> &T {
+        &self.0[row][column]
+    }
+}
+
+impl<T, const R: usize, const C: usize> IndexMut<(usize, usize)> for Matrix<T, R, C> {
+    fn index_mut(&mut self, (row, column): (usize, usize)) -> &mut T {
+        &mut self.0[row][column]
+    }
+}
+
+// DISPLAY
+// One row per line in parentheses, with the columns lined up:
+//   ( 1.1 1.2 )
+//   ( 2.1 2.2 )
+// A precision, e.g. `{:.2}`, is applied to every element.
+impl<T: fmt::Display, const R: usize, const C: usize> fmt::Display for Matrix<T, R, C> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let cells: Vec<Vec<String>> = self.0.iter()
+            .map(|row| row.iter()
+                .map(|x| match f.precision() {
+                    Some(precision) => format!("{:.*}", precision, x),
+                    None => x.to_string(),
+                })
+                .collect())
+            .collect();
+        let widths: Vec<usize> = (0..C)
+            .map(|column| cells.iter().map(|row| row[column].chars().count()).max().unwrap_or(0))
+            .collect();
+        for (i, row) in cells.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "(")?;
+            for (cell, &width) in row.iter().zip(&widths) {
+                write!(f, " {:>width$}", cell, width = width)?;
+            }
+            write!(f, " )")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn display_and_transpose() {
+        let matrix = Matrix::new([[1.1, 1.2, 1.3], [2.1, 2.2, 2.3]]);
+        assert_eq!(matrix.to_string(), "( 1.1 1.2 1.3 )\n( 2.1 2.2 2.3 )");
+        assert_eq!(matrix.transpose().to_string(), "( 1.1 2.1 )\n( 1.2 2.2 )\n( 1.3 2.3 )");
+        assert_eq!(format!("{:.2}", Matrix::new([[1.0, -10.0]])), "( 1.00 -10.00 )");
+        assert_eq!(Matrix::new([[1, 200], [30, 4]]).to_string(), "(  1 200 )\n( 30   4 )");
+    }
+
+    #[test]
+    fn arithmetic() {
+        let a = Matrix::new([[1, 2, 3], [4, 5, 6]]);
+        let b = Matrix::new([[7, 8], [9, 10], [11, 12]]);
+        assert_eq!(a * b, Matrix::new([[58, 64], [139, 154]]));
+        assert_eq!(a + a, a.scale(2));
+        assert_eq!(a - a, Matrix::zeros());
+        assert_eq!(a * Matrix::identity(), a);
+        assert_eq!(Matrix::<i32, 3, 3>::identity() * b, b);
+    }
+
+    #[test]
+    fn determinant() {
+        assert_eq!(Matrix::new([[3, 8], [4, 6]]).determinant(), -14);
+        assert_eq!(Matrix::new([[6, 1, 1], [4, -2, 5], [2, 8, 7]]).determinant(), -306);
+        // a zero on the diagonal needs a row swap
+        assert_eq!(Matrix::new([[0, 1, 2], [1, 0, 3], [4, -3, 8]]).determinant(), -2);
+        assert_eq!(Matrix::new([[1, 2], [2, 4]]).determinant(), 0);
+        assert_eq!(Matrix::<i64, 4, 4>::identity().determinant(), 1);
+        assert_eq!(Matrix::<i64, 0, 0>::identity().determinant(), 1);
+        assert!((Matrix::new([[2.0, 0.5], [1.0, 3.0]]).determinant() - 5.5f64).abs() < 1e-12);
+        // the swap that used to overflow for unsigned types
+        assert_eq!(Matrix::new([[0i8, 1], [1, 0]]).determinant(), -1);
+    }
+
+    // Whether Matrix<T, N, N> has a determinant, i.e. T: Signed, found without calling it: `(&probe).signed()` picks
+    // the method of the impl on Probe<T> when T: Signed holds, and otherwise auto-references for the one on &Probe<T>
+    struct Probe<T>(::std::marker::PhantomData<T>);
+
+    trait HasDeterminant {
+        fn signed(&self) -> bool { true }
+    }
+
+    impl<T: Signed> HasDeterminant for Probe<T> {}
+
+    trait NoDeterminant {
+        fn signed(&self) -> bool { false }
+    }
+
+    impl<T> NoDeterminant for &Probe<T> {}
+
+    macro_rules! signed {
+        ($t:ty) => { (&Probe::<$t>(::std::marker::PhantomData)).signed() };
+    }
+
+    #[test]
+    fn only_signed_scalars_have_a_determinant() {
+        assert!(signed!(i8) && signed!(i64) && signed!(isize) && signed!(f32) && signed!(f64));
+        assert!(!signed!(u8) && !signed!(u32) && !signed!(u128) && !signed!(usize));
+    }
+
+    #[test]
+    fn inverse() {
+        let m = Matrix::new([[4.0, 7.0], [2.0, 6.0]]);
+        let inverse = m.inverse().unwrap();
+        let product = m * inverse;
+        for (row, column) in (0..2).flat_map(|row| (0..2).map(move |column| (row, column))) {
+            let expected = if row == column { 1.0 } else { 0.0 };
+            assert!((product[(row, column)] - expected).abs() < 1e-12);
+        }
+        assert_eq!(Matrix::new([[1.0, 2.0], [2.0, 4.0]]).inverse(), None);
+        assert_eq!(Matrix::new([[0.0f32, 2.0], [4.0, 0.0]]).inverse(), Some(Matrix::new([[0.0, 0.25], [0.5, 0.0]])));
+    }
+
+    #[test]
+    fn iterators() {
+        let m = Matrix::new([[1, 2, 3], [4, 5, 6]]);
+        assert_eq!(m.rows().map(|row| row.iter().sum::<i32>()).collect::<Vec<_>>(), vec![6, 15]);
+        assert_eq!(m.column(1).unwrap().collect::<Vec<_>>(), vec![2, 5]);
+        assert!(m.column(3).is_none());
+        assert_eq!(m.columns().map(|column| column.sum::<i32>()).collect::<Vec<_>>(), vec![5, 7, 9]);
+        assert_eq!(m.column(0).unwrap().len(), 2);
+    }
+}
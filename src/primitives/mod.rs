@@ -0,0 +1,145 @@
+// PRIMITIVES
+// Rust provides access to a wide variety of primitives: scalar types (integers, floats, char, bool and the unit type)
+// and compound types (arrays and tuples).
+
+// the boolean and literal expressions below are the point of the example
+#![allow(clippy::nonminimal_bool, clippy::eq_op)]
+
+pub mod bitset;
+pub mod matrix;
+pub mod stats;
+pub mod tuple;
+
+use std::io::{self, Write};
+
+use std::mem;
+
+use self::tuple::Reverse;
+
+pub use self::bitset::{BitSet, BitVec, Bits};
+pub use self::matrix::Matrix;
+pub use self::tuple::TupleDebug;
+
+pub fn run(out: &mut impl Write) -> io::Result<()> {
+    let _logical: bool = true;
+
+    let _a_float: f64 = 1.0; // regular annotation
+    let _an_integer = 5i32; // suffix annotation
+
+    let _default_float = 64.0; // f64
+    let _default_integer = 32; // i32
+
+    // while a mutable type's value can be changed, the type of the variable can't be
+    let _integer_new: i32 = 32;
+    // integer_new: i64 = 64;  // this will not compile
+
+    // variables can be overwritten by shadowing
+    let _integer_new: i64 = 64;
+
+    writeln!(out, "{}", 0.000001 == 0.000_001)?; // _ can be used in numeric literals to improve readability
+
+    writeln!(out, "1 - 2 = {}", 1i32 - 2)?; // a u32 here will not work
+
+    // short-circuiting boolean logic
+    writeln!(out, "true and false is {}", true && false)?;
+    writeln!(out, "true or false is {}", true || false)?;
+    writeln!(out, "not true is {}", !true)?;
+
+    // Bitwise operations
+    writeln!(out, "0011 and 0101 is {:04b}", 0b0011u32 & 0b0101)?;
+    writeln!(out, "0011 OR 0101 is {:04b}", 0b0011u32 | 0b0101)?;
+    writeln!(out, "0011 XOR 0101 is {:04b}", 0b0011u32 ^ 0b0101)?;
+    writeln!(out, "1 << 5 is {}", 1u32 << 5)?;
+    writeln!(out, "0x80 >> 2 is 0x{:x}", 0x80u32 >> 2)?;
+
+    // BITSETS: the same operators on a set of flags of any width; see primitives::bitset
+    let mut flags = BitVec::with_len(8);
+    flags.insert(0);
+    flags.insert(5);
+    writeln!(out, "flags {} have {} set, the highest at {:?}", flags, flags.count_ones(), flags.select(1))?;
+    writeln!(out, "flags with bit 9 set: {}", flags.clone() | [9].iter().cloned().collect())?;
+
+    // TUPLES: can hold multiple types/values and hold any number of values; can be used by functions to return multiple objects
+    let long_tuple = (1u8, 2u16, 3u32, 4u64,
+                      -1i8, -2i16, -3i32, -4i64,
+                      0.1f32, 0.2f64,
+                      'a', true);
+
+    writeln!(out, "long tuple first value is {}", long_tuple.0)?;
+    writeln!(out, "long tuple second value is {}", long_tuple.1)?;
+
+    let tuple_of_tuples = ((1u8, 2u16, 2u32), (4u64, -1i8), -2i16);
+    writeln!(out, "tuples inside a tuple {:?}", tuple_of_tuples)?; // remember that long_tuples cannot be printed
+
+    // std only implements Debug for tuples of up to 12 elements; primitives::tuple::TupleDebug goes up to 16
+    let longer_tuple = (1u8, 2u16, 3u32, 4u64, -1i8, -2i16, -3i32, -4i64, 0.1f32, 0.2f64, 'a', true, "thirteen");
+    writeln!(out, "a longer tuple {:?}", TupleDebug(&longer_tuple))?;
+
+    let pair = (1, false);
+    writeln!(out, "pair is {:?}", pair)?;
+
+    writeln!(out, "the reversed pair is {:?}", reverse(pair))?;
+    // primitives::tuple::Reverse does the same for any tuple
+    writeln!(out, "the reversed tuple is {:?}", (1, "hello", 4.5, true).reverse())?;
+
+    // (1i32) is not a tuple but an integer; (i32, ) is a 1 element tuple
+
+    // destructing a tuple
+    let tuple = (1, "hello", 4.5, true);
+    let (a, b, c, d) = tuple;
+    writeln!(out, "{:?}, {:?}, {:?}, {:?}", a, b, c, d)?;
+
+    // MATRIX
+    // the exercise of printing a matrix and its transpose; see primitives::matrix for the Matrix type
+    let matrix = Matrix::new([[1.1, 1.2], [2.1, 2.2]]);
+    writeln!(out, "Matrix:\n{}", matrix)?;
+    writeln!(out, "Transpose:\n{}", matrix.transpose())?;
+
+    // ARRAYS and SLICES: the signature of an array has 2 elements: type and length
+    // arrays are allocated on the stack
+    // slices are similar to arrays but their size is not known at compile time
+    // a slice is a 2 word object: first is a pointer to the data, second is the length of the slice
+    let xs: [i32; 5] = [0, 1, 2, 3, 4];
+    let ys: [i32; 500] = [0; 500]; // all elements can be initialized to the same value
+
+    writeln!(out, "first element of the array: {}", xs[0])?;
+    writeln!(out, "second element of the array: {}", xs[1])?;
+
+    writeln!(out, "array size: {}", xs.len())?;
+
+    writeln!(out, "array occupies {} bytes", mem::size_of_val(&xs))?; // arrays are stack allocated
+
+    writeln!(out, "borrow the whole array as a slice")?;
+    analyze_slice(out, &xs)?;
+
+    writeln!(out, "borrow a selection of array as a slice")?;
+    analyze_slice(out, &ys[1 .. 4])?;
+
+    writeln!(out, "borrow an empty selection of array as a slice")?;
+    analyze_slice(out, &xs[5 ..])?;
+
+    // STATISTICS
+    // primitives::stats summarizes a slice without panicking when it is empty
+    writeln!(out, "mean {:?}, median {:?}, variance {:?}", stats::mean(&xs), stats::median(&xs), stats::variance(&xs))?;
+    writeln!(out, "mean of no elements: {:?}", stats::mean(&xs[5 ..]))?;
+
+    Ok(())
+}
+
+pub fn reverse(pair: (i32, bool)) -> (bool, i32) {
+    // let can be used to bind the members of a tuple to variables
+
+    let (integer, boolean) = pair;
+
+    (boolean, integer)
+}
+
+pub fn analyze_slice(out: &mut impl Write, slice: &[i32]) -> io::Result<()> {
+    // `slice[0]` would panic on an empty slice
+    match stats::first(slice) {
+        Some(first) => writeln!(out, "the first element of the slice: {}", first)?,
+        None => writeln!(out, "the slice is empty")?,
+    }
+    writeln!(out, "the slice has {} elements", slice.len())?;
+    Ok(())
+}
@@ -0,0 +1,398 @@
+// STATISTICS
+// Summaries of a slice of numbers that never panic: indexing `slice[0]` panics on an empty slice, so instead
+// first/last/min/max return an Option, and everything that can fail returns a Result with a StatsError.
+// Running keeps a summary of values one at a time, for iterators too large to collect into a slice.
+
+use std::cmp::Ordering;
+use std::error;
+use std::fmt;
+
+use primitives::matrix::Scalar;
+
+// The integer and float primitives
+pub trait Sample: Scalar + PartialOrd {
+    // None when the sum doesn't fit in the type; for floats, when it overflows to infinity
+    fn checked_add(self, other: Self) -> Option<Self>;
+    fn to_f64(self) -> f64;
+}
+
+macro_rules! integer_sample {
+    ($($t:ty),*) => {
+        $(
+            impl Sample for $t {
+                fn checked_add(self, other: $t) -> Option<$t> { <$t>::checked_add(self, other) }
+                fn to_f64(self) -> f64 { self as f64 }
+            }
+        )*
+    };
+}
+
+integer_sample!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+macro_rules! float_sample {
+    ($($t:ty),*) => {
+        $(
+            impl Sample for $t {
+                fn checked_add(self, other: $t) -> Option<$t> {
+                    let sum = self + other;
+                    if sum.is_infinite() && self.is_finite() && other.is_finite() { None } else { Some(sum) }
+                }
+                fn to_f64(self) -> f64 { self as f64 }
+            }
+        )*
+    };
+}
+
+float_sample!(f32, f64);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StatsError {
+    Empty,
+    Overflow,
+    // a NaN can't be ordered, so there is no median, mode or percentile of a slice holding one
+    NotANumber,
+    // the sample variance needs at least two values
+    TooFew { needed: usize, found: usize },
+    // percentiles go from 0 to 100
+    PercentileOutOfRange(f64),
+    NoBins,
+}
+
+impl fmt::Display for StatsError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            StatsError::Empty => write!(f, "no values"),
+            StatsError::Overflow => write!(f, "the sum overflows"),
+            StatsError::NotANumber => write!(f, "a value is NaN"),
+            StatsError::TooFew { needed, found } => write!(f, "needs at least {} values, found {}", needed, found),
+            StatsError::PercentileOutOfRange(p) => write!(f, "percentile {} is not between 0 and 100", p),
+            StatsError::NoBins => write!(f, "a histogram needs at least one bin"),
+        }
+    }
+}
+
+impl error::Error for StatsError {}
+
+pub fn first<T: Copy>(xs: &[T]) -> Option<T> {
+    xs.first().cloned()
+}
+
+pub fn last<T: Copy>(xs: &[T]) -> Option<T> {
+    xs.last().cloned()
+}
+
+// NaN is never chosen over a number, as with f64::min
+fn extreme<T: Sample>(xs: &[T], wanted: Ordering) -> Option<T> {
+    xs.iter().cloned().fold(None, |best, x| match best {
+        None => Some(x),
+        Some(best) => match x.partial_cmp(&best) {
+            Some(ordering) if ordering == wanted => Some(x),
+            // best is NaN
+            None if best.partial_cmp(&best).is_none() => Some(x),
+            _ => Some(best),
+        },
+    })
+}
+
+pub fn min<T: Sample>(xs: &[T]) -> Option<T> {
+    extreme(xs, Ordering::Less)
+}
+
+pub fn max<T: Sample>(xs: &[T]) -> Option<T> {
+    extreme(xs, Ordering::Greater)
+}
+
+// The sum of no values is zero
+pub fn sum<T: Sample>(xs: &[T]) -> Result<T, StatsError> {
+    xs.iter().try_fold(T::zero(), |sum, &x| sum.checked_add(x).ok_or(StatsError::Overflow))
+}
+
+// The mean is computed in f64, so it doesn't overflow where `sum` would
+pub fn mean<T: Sample>(xs: &[T]) -> Result<f64, StatsError> {
+    if xs.is_empty() {
+        return Err(StatsError::Empty);
+    }
+    Ok(xs.iter().map(|x| x.to_f64()).sum::<f64>() / xs.len() as f64)
+}
+
+fn sorted<T: Sample>(xs: &[T]) -> Result<Vec<T>, StatsError> {
+    if xs.is_empty() {
+        return Err(StatsError::Empty);
+    }
+    if xs.iter().any(|x| x.partial_cmp(x).is_none()) {
+        return Err(StatsError::NotANumber);
+    }
+    let mut sorted = xs.to_vec();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+    Ok(sorted)
+}
+
+// For an even number of values, the mean of the middle two
+pub fn median<T: Sample>(xs: &[T]) -> Result<f64, StatsError> {
+    percentile(xs, 50.0)
+}
+
+// Every value that occurs most often, in ascending order
+pub fn mode<T: Sample>(xs: &[T]) -> Result<Vec<T>, StatsError> {
+    let sorted = sorted(xs)?;
+    let mut modes = Vec::new();
+    let mut best = 0;
+    let mut start = 0;
+    while start < sorted.len() {
+        let run = sorted[start..].iter().take_while(|&&x| x == sorted[start]).count();
+        if run > best {
+            best = run;
+            modes.clear();
+        }
+        if run == best {
+            modes.push(sorted[start]);
+        }
+        start += run;
+    }
+    Ok(modes)
+}
+
+// The population variance: the mean squared distance from the mean
+pub fn variance<T: Sample>(xs: &[T]) -> Result<f64, StatsError> {
+    let mean = mean(xs)?;
+    Ok(xs.iter().map(|x| (x.to_f64() - mean).powi(2)).sum::<f64>() / xs.len() as f64)
+}
+
+// The variance of the population the values are a sample of, dividing by n - 1
+pub fn sample_variance<T: Sample>(xs: &[T]) -> Result<f64, StatsError> {
+    if xs.len() < 2 {
+        return Err(StatsError::TooFew { needed: 2, found: xs.len() });
+    }
+    Ok(variance(xs)? * xs.len() as f64 / (xs.len() - 1) as f64)
+}
+
+pub fn std_dev<T: Sample>(xs: &[T]) -> Result<f64, StatsError> {
+    variance(xs).map(f64::sqrt)
+}
+
+// The value below which `p` percent of the values fall, interpolating linearly between the two closest ranks
+pub fn percentile<T: Sample>(xs: &[T], p: f64) -> Result<f64, StatsError> {
+    if !(0.0..=100.0).contains(&p) {
+        return Err(StatsError::PercentileOutOfRange(p));
+    }
+    let sorted = sorted(xs)?;
+    let rank = p / 100.0 * (sorted.len() - 1) as f64;
+    let (below, above) = (rank.floor() as usize, rank.ceil() as usize);
+    let (low, high) = (sorted[below].to_f64(), sorted[above].to_f64());
+    Ok(low + (high - low) * (rank - below as f64))
+}
+
+// HISTOGRAM
+// `counts[i]` is the number of values in the i-th of equally wide bins from `min` to `max`. Every bin includes
+// its lower bound; the last one also includes `max`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Histogram {
+    pub min: f64,
+    pub max: f64,
+    pub counts: Vec<usize>,
+}
+
+impl Histogram {
+    pub fn bin_width(&self) -> f64 {
+        (self.max - self.min) / self.counts.len() as f64
+    }
+
+    // the lower and upper bound of bin i
+    pub fn bounds(&self, i: usize) -> (f64, f64) {
+        (self.min + self.bin_width() * i as f64, self.min + self.bin_width() * (i + 1) as f64)
+    }
+}
+
+// one line per bin with a bar of `#`s
+impl fmt::Display for Histogram {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, &count) in self.counts.iter().enumerate() {
+            let (low, high) = self.bounds(i);
+            let close = if i + 1 == self.counts.len() { ']' } else { ')' };
+            if i > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "[{}, {}{} {} {}", low, high, close, "#".repeat(count), count)?;
+        }
+        Ok(())
+    }
+}
+
+pub fn histogram<T: Sample>(xs: &[T], bins: usize) -> Result<Histogram, StatsError> {
+    if bins == 0 {
+        return Err(StatsError::NoBins);
+    }
+    let sorted = sorted(xs)?;
+    let (min, max) = (sorted[0].to_f64(), sorted[sorted.len() - 1].to_f64());
+    let mut counts = vec![0; bins];
+    for x in sorted {
+        let bin = if max > min { ((x.to_f64() - min) / (max - min) * bins as f64) as usize } else { 0 };
+        counts[bin.min(bins - 1)] += 1;
+    }
+    Ok(Histogram { min, max, counts })
+}
+
+// RUNNING
+// A summary that takes one value at a time using Welford's method for the variance, so a whole stream can be
+// summarized in constant memory: `let summary: Running<u64> = huge_iterator.collect();`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Running<T> {
+    count: u64,
+    mean: f64,
+    // the sum of squared distances from the mean
+    m2: f64,
+    min: Option<T>,
+    max: Option<T>,
+    // None once the sum has overflowed
+    sum: Option<T>,
+}
+
+impl<T: Sample> Default for Running<T> {
+    fn default() -> Running<T> {
+        Running::new()
+    }
+}
+
+impl<T: Sample> Running<T> {
+    pub fn new() -> Running<T> {
+        Running { count: 0, mean: 0.0, m2: 0.0, min: None, max: None, sum: Some(T::zero()) }
+    }
+
+    pub fn push(&mut self, x: T) {
+        self.count += 1;
+        let value = x.to_f64();
+        let delta = value - self.mean;
+        self.mean += delta / self.count as f64;
+        self.m2 += delta * (value - self.mean);
+        self.min = min(&[self.min.unwrap_or(x), x]);
+        self.max = max(&[self.max.unwrap_or(x), x]);
+        self.sum = self.sum.and_then(|sum| sum.checked_add(x));
+    }
+
+    pub fn count(&self) -> u64 { self.count }
+    pub fn min(&self) -> Option<T> { self.min }
+    pub fn max(&self) -> Option<T> { self.max }
+
+    pub fn sum(&self) -> Result<T, StatsError> {
+        self.sum.ok_or(StatsError::Overflow)
+    }
+
+    pub fn mean(&self) -> Result<f64, StatsError> {
+        if self.count == 0 { Err(StatsError::Empty) } else { Ok(self.mean) }
+    }
+
+    pub fn variance(&self) -> Result<f64, StatsError> {
+        if self.count == 0 { Err(StatsError::Empty) } else { Ok(self.m2 / self.count as f64) }
+    }
+
+    pub fn sample_variance(&self) -> Result<f64, StatsError> {
+        if self.count < 2 {
+            return Err(StatsError::TooFew { needed: 2, found: self.count as usize });
+        }
+        Ok(self.m2 / (self.count - 1) as f64)
+    }
+
+    pub fn std_dev(&self) -> Result<f64, StatsError> {
+        self.variance().map(f64::sqrt)
+    }
+}
+
+impl<T: Sample> Extend<T> for Running<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for x in iter {
+            self.push(x);
+        }
+    }
+}
+
+impl<T: Sample> ::std::iter::FromIterator<T> for Running<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Running<T> {
+        let mut running = Running::new();
+        running.extend(iter);
+        running
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn close(a: f64, b: f64) -> bool {
+        (a - b).abs() < 1e-9
+    }
+
+    #[test]
+    fn empty_slices_do_not_panic() {
+        let empty: &[i32] = &[];
+        assert_eq!(first(empty), None);
+        assert_eq!(last(empty), None);
+        assert_eq!(min(empty), None);
+        assert_eq!(max(empty), None);
+        assert_eq!(sum(empty), Ok(0));
+        assert_eq!(mean(empty), Err(StatsError::Empty));
+        assert_eq!(median(empty), Err(StatsError::Empty));
+        assert_eq!(mode(empty), Err(StatsError::Empty));
+        assert_eq!(variance(empty), Err(StatsError::Empty));
+        assert_eq!(percentile(empty, 90.0), Err(StatsError::Empty));
+        assert_eq!(histogram(empty, 3), Err(StatsError::Empty));
+    }
+
+    #[test]
+    fn summaries() {
+        let xs = [4, 1, 3, 1, 5, 3];
+        assert_eq!((first(&xs), last(&xs), min(&xs), max(&xs)), (Some(4), Some(3), Some(1), Some(5)));
+        assert_eq!(sum(&xs), Ok(17));
+        assert!(close(mean(&xs).unwrap(), 17.0 / 6.0));
+        assert_eq!(median(&xs), Ok(3.0));
+        assert_eq!(median(&[3, 1, 2]), Ok(2.0));
+        assert_eq!(mode(&xs), Ok(vec![1, 3]));
+        assert_eq!(variance(&[2, 4, 4, 4, 5, 5, 7, 9]), Ok(4.0));
+        assert_eq!(std_dev(&[2, 4, 4, 4, 5, 5, 7, 9]), Ok(2.0));
+        assert!(close(sample_variance(&[1.0, 2.0, 3.0, 4.0]).unwrap(), 5.0 / 3.0));
+        assert_eq!(sample_variance(&[1]), Err(StatsError::TooFew { needed: 2, found: 1 }));
+    }
+
+    #[test]
+    fn overflow_and_nan() {
+        assert_eq!(sum(&[200u8, 100]), Err(StatsError::Overflow));
+        assert_eq!(sum(&[i64::MAX, 1]), Err(StatsError::Overflow));
+        assert_eq!(sum(&[f64::MAX, f64::MAX]), Err(StatsError::Overflow));
+        assert_eq!(mean(&[200u8, 100]), Ok(150.0));
+        assert_eq!(min(&[f64::NAN, 2.0, 1.0]), Some(1.0));
+        assert_eq!(max(&[2.0, f64::NAN, 3.0]), Some(3.0));
+        assert_eq!(median(&[1.0, f64::NAN]), Err(StatsError::NotANumber));
+    }
+
+    #[test]
+    fn percentiles_and_histograms() {
+        let xs: Vec<u32> = (1..=100).collect();
+        assert_eq!(percentile(&xs, 0.0), Ok(1.0));
+        assert_eq!(percentile(&xs, 100.0), Ok(100.0));
+        assert!(close(percentile(&xs, 90.0).unwrap(), 90.1));
+        assert_eq!(percentile(&xs, 101.0), Err(StatsError::PercentileOutOfRange(101.0)));
+
+        let histogram = histogram(&[0, 1, 2, 3, 4, 5, 6, 7, 8, 10], 2).unwrap();
+        assert_eq!(histogram, Histogram { min: 0.0, max: 10.0, counts: vec![5, 5] });
+        assert_eq!(histogram.to_string(), "[0, 5) ##### 5\n[5, 10] ##### 5");
+        assert_eq!(super::histogram(&[7, 7], 3).unwrap().counts, vec![2, 0, 0]);
+        assert_eq!(super::histogram(&[1], 0), Err(StatsError::NoBins));
+    }
+
+    #[test]
+    fn running_matches_the_slice_functions() {
+        let xs: Vec<i64> = (0..1000).map(|i| (i * 7919) % 1013 - 500).collect();
+        let running: Running<i64> = xs.iter().cloned().collect();
+        assert_eq!(running.count(), 1000);
+        assert_eq!((running.min(), running.max()), (min(&xs), max(&xs)));
+        assert_eq!(running.sum(), sum(&xs));
+        assert!(close(running.mean().unwrap(), mean(&xs).unwrap()));
+        assert!(close(running.variance().unwrap(), variance(&xs).unwrap()));
+        assert!(close(running.sample_variance().unwrap(), sample_variance(&xs).unwrap()));
+
+        let mut bytes = Running::new();
+        bytes.extend(vec![200u8, 100]);
+        assert_eq!(bytes.sum(), Err(StatsError::Overflow));
+        assert_eq!(bytes.mean(), Ok(150.0));
+        assert_eq!(Running::<f64>::new().mean(), Err(StatsError::Empty));
+    }
+}
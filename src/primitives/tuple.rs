@@ -0,0 +1,258 @@
+// TUPLES
+// A function like `reverse(pair: (i32, bool))` only works on one shape of tuple. The traits here work on tuples of
+// any types with up to 16 elements, with the impls for every length written by a macro:
+// - Reverse, First/Last and PushFront/PushBack rearrange a tuple
+// - Zip turns a tuple of iterators into an iterator of tuples, and unzip goes back to a tuple of Vecs
+// - Homogeneous maps a function over a tuple whose elements all have the same type
+// - TupleDebug prints tuples longer than 12, where std's Debug impls stop
+
+use std::fmt;
+
+pub trait Reverse {
+    type Output;
+    fn reverse(self) -> Self::Output;
+}
+
+pub trait First {
+    type First;
+    fn first(&self) -> &Self::First;
+}
+
+pub trait Last {
+    type Last;
+    fn last(&self) -> &Self::Last;
+}
+
+// `(1, 2).push_front(0) == (0, 1, 2)`
+pub trait PushFront<T> {
+    type Output;
+    fn push_front(self, value: T) -> Self::Output;
+}
+
+pub trait PushBack<T> {
+    type Output;
+    fn push_back(self, value: T) -> Self::Output;
+}
+
+// `(xs, ys).zip()` yields `(x, y)` pairs until the shortest iterator runs out
+pub trait Zip {
+    type Iter: Iterator;
+    fn zip(self) -> Self::Iter;
+}
+
+#[derive(Debug, Clone)]
+pub struct ZipIter<T>(T);
+
+// The tuple whose elements are Vecs of the element types. See `unzip`.
+pub trait Unzip: Sized {
+    type Output;
+    fn unzip<I: IntoIterator<Item = Self>>(iter: I) -> Self::Output;
+}
+
+// An iterator of tuples into a tuple of Vecs: `unzip(vec![(1, 'a'), (2, 'b')]) == (vec![1, 2], vec!['a', 'b'])`
+pub fn unzip<T: Unzip, I: IntoIterator<Item = T>>(iter: I) -> T::Output {
+    T::unzip(iter)
+}
+
+// A tuple of Ts
+pub trait Homogeneous<T> {
+    // the same length of tuple, of Us
+    type Mapped<U>;
+    fn map<U, F: FnMut(T) -> U>(self, func: F) -> Self::Mapped<U>;
+}
+
+// `{:?}` for any tuple up to 16 elements of Debug types: `TupleDebug(&long_tuple)`
+pub struct TupleDebug<'a, T: 'a>(pub &'a T);
+
+// stands for `$sub` once per repetition of `$_t`
+macro_rules! replace {
+    ($_t:tt $sub:ty) => { $sub };
+}
+
+macro_rules! tuple_impls {
+    // the unit tuple
+    ([] []) => {
+        impl Reverse for () {
+            type Output = ();
+            fn reverse(self) {}
+        }
+
+        impl Unzip for () {
+            type Output = ();
+            fn unzip<I: IntoIterator<Item = ()>>(_iter: I) {}
+        }
+
+        impl<T> Homogeneous<T> for () {
+            type Mapped<U> = ();
+            fn map<U, F: FnMut(T) -> U>(self, _func: F) {}
+        }
+
+        impl<'a> fmt::Debug for TupleDebug<'a, ()> {
+            fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("()")
+            }
+        }
+    };
+    // the element lists in order and in reverse, each element as a type parameter, a binding and an index
+    ([$F0:ident $f0:ident $i0:tt $($F:ident $f:ident $i:tt)*] [$R0:ident $r0:ident $j0:tt $($R:ident $r:ident $j:tt)*]) => {
+        impl<$F0, $($F),*> Reverse for ($F0, $($F,)*) {
+            type Output = ($R0, $($R,)*);
+
+            fn reverse(self) -> Self::Output {
+                let ($f0, $($f,)*) = self;
+                ($r0, $($r,)*)
+            }
+        }
+
+        impl<$F0, $($F),*> First for ($F0, $($F,)*) {
+            type First = $F0;
+
+            fn first(&self) -> &$F0 {
+                &self.$i0
+            }
+        }
+
+        impl<$F0, $($F),*> Last for ($F0, $($F,)*) {
+            type Last = $R0;
+
+            fn last(&self) -> &$R0 {
+                &self.$j0
+            }
+        }
+
+        impl<$F0: IntoIterator, $($F: IntoIterator),*> Zip for ($F0, $($F,)*) {
+            type Iter = ZipIter<($F0::IntoIter, $($F::IntoIter,)*)>;
+
+            fn zip(self) -> Self::Iter {
+                let ($f0, $($f,)*) = self;
+                ZipIter(($f0.into_iter(), $($f.into_iter(),)*))
+            }
+        }
+
+        impl<$F0: Iterator, $($F: Iterator),*> Iterator for ZipIter<($F0, $($F,)*)> {
+            type Item = ($F0::Item, $($F::Item,)*);
+
+            fn next(&mut self) -> Option<Self::Item> {
+                let ($f0, $($f,)*) = &mut self.0;
+                Some(($f0.next()?, $($f.next()?,)*))
+            }
+        }
+
+        impl<$F0, $($F),*> Unzip for ($F0, $($F,)*) {
+            type Output = (Vec<$F0>, $(Vec<$F>,)*);
+
+            fn unzip<I: IntoIterator<Item = Self>>(iter: I) -> Self::Output {
+                let mut vecs = (Vec::new(), $(Vec::<$F>::new(),)*);
+                for ($f0, $($f,)*) in iter {
+                    vecs.$i0.push($f0);
+                    $(vecs.$i.push($f);)*
+                }
+                vecs
+            }
+        }
+
+        impl<T> Homogeneous<T> for (replace!($F0 T), $(replace!($F T),)*) {
+            type Mapped<U> = (U, $(replace!($F U),)*);
+
+            fn map<U, F: FnMut(T) -> U>(self, mut func: F) -> Self::Mapped<U> {
+                let ($f0, $($f,)*) = self;
+                (func($f0), $(func($f),)*)
+            }
+        }
+
+        impl<'a, $F0: fmt::Debug, $($F: fmt::Debug),*> fmt::Debug for TupleDebug<'a, ($F0, $($F,)*)> {
+            fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                let ($f0, $($f,)*) = self.0;
+                // an unnamed debug_tuple is how std prints tuples, trailing comma of `(1,)` included
+                formatter.debug_tuple("").field($f0)$(.field($f))*.finish()
+            }
+        }
+    };
+}
+
+macro_rules! push_impls {
+    ([$($F:ident $f:ident $i:tt)*] $N:ident) => {
+        impl<$($F,)* $N> PushFront<$N> for ($($F,)*) {
+            type Output = ($N, $($F,)*);
+
+            fn push_front(self, value: $N) -> Self::Output {
+                let ($($f,)*) = self;
+                (value, $($f,)*)
+            }
+        }
+
+        impl<$($F,)* $N> PushBack<$N> for ($($F,)*) {
+            type Output = ($($F,)* $N,);
+
+            fn push_back(self, value: $N) -> Self::Output {
+                let ($($f,)*) = self;
+                ($($f,)* value,)
+            }
+        }
+    };
+}
+
+// Implements everything for the tuple of the first n elements, for every n, by moving one element at a time from
+// the remaining list onto the end of the forward list and the start of the reversed one.
+macro_rules! tuples {
+    ([$($F:ident $f:ident $i:tt)*] [$($R:ident $r:ident $j:tt)*]) => {
+        tuple_impls!([$($F $f $i)*] [$($R $r $j)*]);
+    };
+    ([$($F:ident $f:ident $i:tt)*] [$($R:ident $r:ident $j:tt)*] $N:ident $n:ident $k:tt $($rest:tt)*) => {
+        tuple_impls!([$($F $f $i)*] [$($R $r $j)*]);
+        push_impls!([$($F $f $i)*] $N);
+        tuples!([$($F $f $i)* $N $n $k] [$N $n $k $($R $r $j)*] $($rest)*);
+    };
+}
+
+tuples!([] [] A a 0 B b 1 C c 2 D d 3 E e 4 G g 5 H h 6 J j 7 K k 8 L l 9 M m 10 N n 11 O o 12 P p 13 Q q 14
+        R r 15);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn rearranging() {
+        assert_eq!((1, false).reverse(), (false, 1));
+        assert_eq!((1, "two", 3.0, '4').reverse(), ('4', 3.0, "two", 1));
+        assert_eq!(().reverse(), ());
+        assert_eq!((1,).reverse(), (1,));
+        assert_eq!((1, "two", 3.0).first(), &1);
+        assert_eq!((1, "two", 3.0).last(), &3.0);
+        assert_eq!((1, 2).push_front(0), (0, 1, 2));
+        assert_eq!((1, 2).push_back('3'), (1, 2, '3'));
+        assert_eq!(().push_back(1), (1,));
+
+        let sixteen = (0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14).push_back(15);
+        assert_eq!(*sixteen.last(), 15);
+        assert_eq!(*sixteen.reverse().last(), 0);
+    }
+
+    #[test]
+    fn zip_and_unzip() {
+        let zipped: Vec<_> = (vec![1, 2, 3], "ab".chars(), 10..).zip().collect();
+        assert_eq!(zipped, vec![(1, 'a', 10), (2, 'b', 11)]);
+        assert_eq!(unzip(zipped), (vec![1, 2], vec!['a', 'b'], vec![10, 11]));
+        assert_eq!(unzip(Vec::<(u8, bool)>::new()), (vec![], vec![]));
+    }
+
+    #[test]
+    fn map() {
+        assert_eq!((1, 2, 3).map(|x| x * 10), (10, 20, 30));
+        assert_eq!(("a", "bb").map(str::len), (1, 2));
+        let mut calls = Vec::new();
+        (1, 2, 3).map(|x| calls.push(x));
+        assert_eq!(calls, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn debug() {
+        let long = (1u8, 2u16, 3u32, 4u64, -1i8, -2i16, -3i32, -4i64, 0.1f32, 0.2f64, 'a', true, "thirteen");
+        assert_eq!(format!("{:?}", TupleDebug(&long)),
+                   "(1, 2, 3, 4, -1, -2, -3, -4, 0.1, 0.2, 'a', true, \"thirteen\")");
+        assert_eq!(format!("{:?}", TupleDebug(&(1, "a"))), format!("{:?}", (1, "a")));
+        assert_eq!(format!("{:#?}", TupleDebug(&(1,))), format!("{:#?}", (1,)));
+        assert_eq!(format!("{:?}", TupleDebug(&())), "()");
+    }
+}
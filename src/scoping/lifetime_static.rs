@@ -0,0 +1,63 @@
+// STATIC
+// A 'static lifetime is the longest possible lifetime and lasts for the lifetime of the program.
+// A 'static lifetime may also be coerced to a shorter lifetime. There are ways to make a variable with 'static lifetime,
+// and both are stored in the read only memory of of the binary
+// 1. Make a constant with the 'static declaration
+// 2. Make a string literal which has the type `&' static str`
+
+// the explicit lifetimes below are the point of the example
+#![allow(clippy::needless_lifetimes)]
+
+use std::io::{self, Write};
+
+pub static NUM: i32 = 18;
+
+pub fn coerce_static<'a>(_: &'a i32) -> &'a i32 {
+    &NUM // returns a reference to NUM where its 'static lifetime is coerced to that of the input argument
+}
+
+pub fn run(out: &mut impl Write) -> io::Result<()> {
+    {
+        // Make a string literal and print it
+        let static_string = "I'm in read only memory";
+        writeln!(out, "static_string: {}", static_string)?;
+        // When `static_string` goes out of scope, the reference can no longer be used but the data remains in the binary
+    }
+    {
+        // Make an integer to use for `coerce_static`
+        let lifetime_num = 9;
+        // Coerce 'NUM' to lifetime of `lifetime_num`
+        let coerced_static = coerce_static(&lifetime_num);
+        writeln!(out, "coerced_static: {}", coerced_static)?;
+    }
+    writeln!(out, "NUM: {} stays accessible", NUM)?;
+
+    // ELISION
+    let x = 3;
+    elided_input(out, &x)?;
+    annotated_input(out, &x)?;
+    writeln!(out, "elided_pass: {}", elided_pass(&x))?;
+    writeln!(out, "annotated_pass: {}", annotaed_pass(&x))?;
+
+    Ok(())
+}
+
+// ELISION
+// Some lifetime parameters are so common the borrow checker will implicitly add them to save typing and to improve readability
+// The process of implicit addition is called elision
+
+// `elided_input` and `annotated_input` essentially have identical signatures because the lifetime of
+// `elided_input` is elided by the compiler
+pub fn elided_input(out: &mut impl Write, x: &i32) -> io::Result<()> {
+    writeln!(out, "elided_input: {}", x)?;
+    Ok(())
+}
+
+pub fn annotated_input<'a>(out: &mut impl Write, x: &'a i32) -> io::Result<()> {
+    writeln!(out, "annotated_input: {}", x)?;
+    Ok(())
+}
+
+// Similarly, elided_pass and annotated_pass have identical signatures because the lifetime is added implicitly to elided_pass
+pub fn elided_pass(x: &i32) -> &i32 { x }
+pub fn annotaed_pass<'a>(x: &'a i32) -> &'a i32 { x }
@@ -0,0 +1,11 @@
+// SCOPING RULES
+// Scopes play an important part in ownership, borrowing, and lifetimes.
+
+pub mod ownership;
+pub mod borrowing;
+pub mod lifetimes;
+pub mod lifetime_structs;
+pub mod lifetime_static;
+
+pub use self::borrowing::Book;
+pub use self::ownership::ToDrop;
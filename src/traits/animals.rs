@@ -0,0 +1,71 @@
+// A trait is a collection of methods defined for an unkown type: Self. They can access other methods declared in the same trait
+// Traits can be implemented for any data type
+
+use std::io::{self, Write};
+
+pub struct Sheep { pub naked: bool, pub name: &'static str }
+
+pub trait Animal {
+    // static method signature: `Self` refers to the implementor type
+    fn new(name: &'static str) -> Self;
+
+    // Instance method signatures; these will return a string.
+    fn name(&self) -> &'static str;
+    fn noise(&self) -> &'static str;
+
+    // Traits can provide default method definitions
+    fn talk(&self, out: &mut impl Write) -> io::Result<()> {
+        writeln!(out, "{} says {}", self.name(), self.noise())
+    }
+}
+
+impl Sheep {
+    pub fn is_naked(&self) -> bool {
+        self.naked
+    }
+
+    pub fn shear(&mut self, out: &mut impl Write) -> io::Result<()> {
+        if self.is_naked() {
+            // Implementor methods can use implementor's trait methods
+            writeln!(out, "{} is already naked", self.name())?;
+        } else {
+            writeln!(out, "{} gets a haircut", self.name)?;
+            self.naked = true;
+        }
+
+        Ok(())
+    }
+}
+
+// Implement the animal trait for Sheep
+impl Animal for Sheep {
+    // `Self` is the implementor type: `Sheep`
+    fn new(name: &'static str) -> Sheep {
+        Sheep { name, naked: false }
+    }
+
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn noise(&self) -> &'static str {
+        if self.is_naked() {
+            "baaaaaah?"
+        } else {
+            "baaaaaah!"
+        }
+    }
+    // Default trait methods can be overwritten
+    fn talk(&self, out: &mut impl Write) -> io::Result<()> {
+        // for example, we can add some quiet comtemplation
+        writeln!(out, "{} pauses briefly... {}", self.name, self.noise())
+    }
+}
+
+pub fn run(out: &mut impl Write) -> io::Result<()> {
+    let mut dolly: Sheep = Animal::new("Dolly"); // type annotation is necessary in this case
+    dolly.talk(out)?;
+    dolly.shear(out)?;
+    dolly.talk(out)
+}
+
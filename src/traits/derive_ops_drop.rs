@@ -0,0 +1,123 @@
+// Derive
+// The compiler is capable of providing basic implementations for some traits via the #[derive] attribute.
+// These traits can still be manually implemented if more complex behaviour is required.
+// List of derivable traits
+// 1. comparison traits: Eq, ParitalEq, Ord, PartialOrd
+// 2. Clone, to create T from &T via a copy
+// 3. Copy, to give a type "copy semantics" instead of "move semantics"
+// 4. Hash, to compute a hash from &T
+// 5. Default, to create an empty instance of a data type.
+// 6. Debug, to format a value using the {:?} formatter
+
+use std::cell::RefCell;
+use std::io::{self, Write};
+
+#[derive(PartialEq, PartialOrd)]
+pub struct Centimeters(pub f64); // Centimeters, a tuple struct that can be compared
+
+#[derive(Debug)]
+pub struct Inches(pub i32); // A tuple struct that can be printed
+
+impl Inches {
+    pub fn to_centimeters(&self) -> Centimeters {
+        let &Inches(inches) = self; // this is destructuring a struct to create a variable `inches` that will be an i32
+
+        Centimeters(inches as f64 * 2.54)
+    }
+}
+
+pub struct Seconds(pub i32); // a tuple struct with no attributes
+
+pub fn run(out: &mut impl Write) -> io::Result<()> {
+    // the writer is shared with the types below, which print from inside `add` and `drop`
+    let out = RefCell::new(out);
+
+    let _one_second = Seconds(1); // can't be printed or compared
+    let foot = Inches(12);
+    writeln!(out.borrow_mut(), "One foot equals {:?}", foot)?;
+    let meter = Centimeters(100.0);
+    let cmp =
+        if foot.to_centimeters() < meter { // comparing 2 variables that are type Centimeters as they have PartialEq implemented
+            "smaller"
+        } else {
+            "bigger"
+        };
+    writeln!(out.borrow_mut(), "One foot is {} than one meter", cmp)?;
+
+    // OPERATOR OVERLOADING
+    // the sums are computed before writing as `add` needs to borrow the writer itself
+    let foo_bar = Foo(&out) + Bar(&out);
+    writeln!(out.borrow_mut(), "Foo + Bar =  {:?}", foo_bar)?;
+    let bar_foo = Bar(&out) + Foo(&out);
+    writeln!(out.borrow_mut(), "Bar + Foo =  {:?}", bar_foo)?;
+
+    // DROP
+    let _a = Dropable { name: "a", out: &out };
+    {
+        let _b = Dropable { name: "b", out: &out };
+        {
+            let _c = Dropable { name: "c", out: &out };
+            let _d = Dropable { name: "d", out: &out };
+            writeln!(out.borrow_mut(), "Exiting block B")?;
+        }
+        writeln!(out.borrow_mut(), "Just exited block B")?;
+        writeln!(out.borrow_mut(), "Exiting block A")?;
+    }
+    writeln!(out.borrow_mut(), "Just exited block A")?;
+
+    // drop(_a); // The variable can also be manually dropped; otherwise will get dropped at the end of the main function
+    writeln!(out.borrow_mut(), "End of main function")?;
+
+    Ok(())
+}
+
+// OPERATOR OVERLOADING
+// Many operators can be overloaded via traits. Some operators can be used to accomplish different tasks based on their input arguments.
+// This is possible because operators are syntactic sugar for method calls. The + operator in a + b calls the add method (a.add(b))
+// The add method is a part of the Add trait. Hence, the + operator can be used by any implementor of the Add trait.
+// A list of traits such as Add, that overload operators can be found in core::ops
+// `add` has a fixed signature, so Foo and Bar hold a shared handle to the writer they announce the call on
+use std::ops;
+pub struct Foo<'a, W: Write + 'a>(pub &'a RefCell<W>);
+pub struct Bar<'a, W: Write + 'a>(pub &'a RefCell<W>);
+
+#[derive(Debug)]
+pub struct FooBar;
+
+#[derive(Debug)]
+pub struct BarFoo;
+
+impl<'a, W: Write> ops::Add<Bar<'a, W>> for Foo<'a, W> { // std::ops::Add trait is used to specify the functionality of `+`
+    type Output = FooBar;
+    fn add(self, _rhs: Bar<'a, W>) -> FooBar {
+        // add can't return an error, so a failed write is ignored
+        let _ = writeln!(self.0.borrow_mut(), "> Foo.add(Bar) was called");
+        FooBar
+    }
+}
+
+impl<'a, W: Write> ops::Add<Foo<'a, W>> for Bar<'a, W> {
+    type Output = BarFoo;
+    fn add(self, _rhs: Foo<'a, W>) -> BarFoo {
+        let _ = writeln!(self.0.borrow_mut(), "> Bar.add(Foo) was called");
+        BarFoo
+    }
+}
+
+// DROP
+// The Drop trait has only one method: drop, which is called automatically when an object goes out of scope.
+// The main use of the Drop trait is to free the resource that the implementor instance owns.
+// Some examples of the types that implement the Drop trait are Box, Vec, String, File, and Process. It can also be manually
+// implemented for custom types. The example adds a print to console to the drop function to announce when it's called.
+
+pub struct Dropable<'a, W: Write + 'a> {
+    pub name: &'static str,
+    pub out: &'a RefCell<W>,
+}
+
+impl<'a, W: Write> Drop for Dropable<'a, W> {
+    fn drop(&mut self) {
+        // drop can't return an error, so a failed write is ignored
+        let _ = writeln!(self.out.borrow_mut(), ">Dropping {}", self.name);
+    }
+}
@@ -0,0 +1,90 @@
+// ITERATORS
+// The Iterator trait is used to implement iterators over collections such as arrays.
+// The trait requires only a method to be defined for the next element, which may be manually defined in an impl block
+// or automatically defined (as in arrays or ranges). For convenience, the for construct turns some collections
+// into iterators using the into_iterator() method.
+
+use std::io::{self, Write};
+
+pub struct Fibonacci {
+    pub curr: u32,
+    pub next: u32,
+}
+
+impl Iterator for Fibonacci {
+    type Item = u32;
+
+    fn next(&mut self) -> Option<u32> {
+        let new_next = self.curr + self.next;
+        self.curr = self.next;
+        self.next = new_next;
+
+        Some(self.curr)
+    }
+}
+
+pub fn fibonacci() -> Fibonacci { // returns a Fibonacci sequence generator
+    Fibonacci { curr: 1, next: 1 }
+}
+
+pub fn run(out: &mut impl Write) -> io::Result<()> {
+    let mut sequence = 0..3;
+    writeln!(out, "Four consecutive `next` calls on 0..3")?;
+    writeln!(out, "> {:?}", sequence.next())?;
+    writeln!(out, "> {:?}", sequence.next())?;
+    writeln!(out, "> {:?}", sequence.next())?;
+    writeln!(out, "> {:?}", sequence.next())?;
+
+    writeln!(out, "Iterate through 0..3  using `for`")?; // for works through an iterator till it returns `None`
+    for i in 0..3 {
+        writeln!(out, "> {}", i)?;
+    }
+    // The take(n) method reduces an Iterator to its first n terms
+    writeln!(out, "The first 4 terms of the Fibonacci sequence are: ")?;
+    for i in fibonacci().take(4) {
+        writeln!(out, "> {}", i)?;
+    }
+    // The skip method shortens an `Iterator` by dropping its first n terms
+    writeln!(out, "The next 4 terms of a Fibonacci sequence are: ")?;
+    for i in fibonacci().skip(4).take(4) {
+        writeln!(out, "> {}", i)?;
+    }
+
+    let array = [1u32, 3, 3, 7];
+    writeln!(out, "Iterate the following array {:?}", &array)?;
+    for i in array.iter() { // the iter method produces an Iterator over an array/slice
+        writeln!(out, "> {}", i)?;
+    }
+
+    // CLONE
+    let nil = Nil; // Instantiate Nil
+    let copied_nil = nil; // copy nil; there are no resources to move
+    // Both Nil's can be used independently
+    writeln!(out, "original: {:?}", nil)?;
+    writeln!(out, "copy: {:?}", copied_nil)?;
+
+    let pair = Pair(Box::new(1), Box::new(2)); // instantiate Pair
+    writeln!(out, "original: {:?}", pair)?;
+
+    let moved_pair = pair; // Copy pair into moved_pair; moves resources. pair has lost its resources
+    writeln!(out, "copy: {:?}", moved_pair)?;
+
+    let cloned_pair = moved_pair.clone();
+    drop(moved_pair); // drop the original pair
+    writeln!(out, "clone: {:?}", cloned_pair)?;
+
+    Ok(())
+}
+
+// CLONE
+// When dealing with resources, the default behaviour is to transfer them during assignments or function calls.
+// However, sometimes we need to make a copy of the resource as well.
+// The clone trait helps us do that.
+
+// Nil is the unit struct without resources from custom_types; it derives Debug, Clone and Copy
+use custom_types::Nil;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Pair(pub Box<i32>, pub Box<i32>); // a tuple struct with resources that implements the Clone trait
+
+
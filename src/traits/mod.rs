@@ -0,0 +1,9 @@
+// TRAITS
+// A trait is a collection of methods defined for an unknown type: Self.
+
+pub mod animals;
+pub mod derive_ops_drop;
+pub mod iter_clone;
+
+pub use self::animals::{Animal, Sheep};
+pub use self::iter_clone::{fibonacci, Fibonacci, Pair};
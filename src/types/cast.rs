@@ -0,0 +1,439 @@
+// CASTS
+// `as` never fails: `1000 as u8` wraps to 232, `-1i8 as u8` to 255, `128 as i8` to -128, and `65.4321_f32 as u8`
+// drops the fraction. The Cast trait makes the choice explicit, between any two integer or float primitives:
+// - checked_cast returns a CastError saying what was wrong instead of changing the value
+// - saturating_cast clamps to the target's MIN or MAX
+// - wrapping_cast keeps the low bits, like `as` between integers
+// - rounding_cast rounds a float the given way first, then is checked
+//
+// A float cast to an integer is checked for NaN, infinity, range and a fractional part. An integer cast to a float
+// fails when the float can't hold it exactly, e.g. 2^24 + 1 as f32. Between floats, NaN and the infinities are
+// kept as they are, and a finite f64 fails when it is out of f32's range or loses precision.
+
+use std::error;
+use std::fmt;
+
+// Every primitive's value, as the sign and magnitude of an integer or a float
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Wide {
+    Int { negative: bool, magnitude: u128 },
+    Float(f64),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Reason {
+    // greater than the target's MAX
+    Overflow,
+    // less than the target's MIN, like any negative value for an unsigned type
+    Underflow,
+    NaN,
+    Infinite,
+    // a float with a fractional part cast to an integer
+    Fractional,
+    // a value the float type can only get close to
+    Inexact,
+}
+
+impl fmt::Display for Reason {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Reason::Overflow => write!(f, "it is above the maximum"),
+            Reason::Underflow => write!(f, "it is below the minimum"),
+            Reason::NaN => write!(f, "it is NaN"),
+            Reason::Infinite => write!(f, "it is infinite"),
+            Reason::Fractional => write!(f, "it has a fractional part"),
+            Reason::Inexact => write!(f, "it can't be represented exactly"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CastError {
+    // the value being cast, as its Display shows it
+    pub value: String,
+    pub from: &'static str,
+    pub to: &'static str,
+    pub reason: Reason,
+}
+
+impl fmt::Display for CastError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "cannot cast {}_{} to {}: {}", self.value, self.from, self.to, self.reason)
+    }
+}
+
+impl error::Error for CastError {}
+
+// How rounding_cast turns a float into a whole number
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rounding {
+    // what `as` does: 2.7 -> 2, -2.7 -> -2
+    TowardZero,
+    // halfway cases away from zero, as f64::round: 2.5 -> 3, -2.5 -> -3
+    Nearest,
+    // halfway cases to the even neighbour: 2.5 -> 2, 3.5 -> 4
+    NearestEven,
+    // floor: -2.5 -> -3
+    Down,
+    // ceil: -2.5 -> -2
+    Up,
+}
+
+impl Rounding {
+    pub fn apply(self, x: f64) -> f64 {
+        match self {
+            Rounding::TowardZero => x.trunc(),
+            Rounding::Nearest => x.round(),
+            Rounding::NearestEven => x.round_ties_even(),
+            Rounding::Down => x.floor(),
+            Rounding::Up => x.ceil(),
+        }
+    }
+}
+
+// The integer and float primitives, converted to and from Wide. Cast has the methods to use.
+pub trait Primitive: Copy + fmt::Display {
+    const NAME: &'static str;
+    fn to_wide(self) -> Wide;
+    fn checked_from(value: Wide) -> Result<Self, Reason>;
+    fn saturating_from(value: Wide) -> Self;
+    fn wrapping_from(value: Wide) -> Self;
+}
+
+macro_rules! int_primitive {
+    ($($t:ident $signed:expr),*) => {
+        $(
+            impl Primitive for $t {
+                const NAME: &'static str = stringify!($t);
+
+                fn to_wide(self) -> Wide {
+                    // every signed integer fits in an i128
+                    if $signed && (self as i128) < 0 {
+                        Wide::Int { negative: true, magnitude: (self as i128).unsigned_abs() }
+                    } else {
+                        Wide::Int { negative: false, magnitude: self as u128 }
+                    }
+                }
+
+                fn checked_from(value: Wide) -> Result<$t, Reason> {
+                    // the magnitude of MIN: 2^(BITS - 1) when signed, 0 otherwise
+                    let min = if $signed { 1u128 << ($t::BITS - 1) } else { 0 };
+                    match value {
+                        Wide::Int { negative: false, magnitude } if magnitude > $t::MAX as u128 => Err(Reason::Overflow),
+                        Wide::Int { negative: true, magnitude } if magnitude > min => Err(Reason::Underflow),
+                        Wide::Int { .. } => Ok($t::wrapping_from(value)),
+                        Wide::Float(x) if x.is_nan() => Err(Reason::NaN),
+                        Wide::Float(x) if x.is_infinite() => Err(Reason::Infinite),
+                        // MAX + 1 is a power of two, so unlike MAX it converts to a float exactly
+                        Wide::Float(x) if x >= 2f64.powi(($t::BITS - $signed as u32) as i32) => Err(Reason::Overflow),
+                        // -1.0 is an underflow for an unsigned type but -0.0 and -0.5 are not
+                        Wide::Float(x) if x.trunc() < -(min as f64) => Err(Reason::Underflow),
+                        Wide::Float(x) if x.fract() != 0.0 => Err(Reason::Fractional),
+                        Wide::Float(x) => Ok(x as $t),
+                    }
+                }
+
+                fn saturating_from(value: Wide) -> $t {
+                    match value {
+                        Wide::Int { .. } => $t::checked_from(value).unwrap_or_else(|reason| match reason {
+                            Reason::Underflow => $t::MIN,
+                            _ => $t::MAX,
+                        }),
+                        // float to integer `as` saturates, and makes NaN 0
+                        Wide::Float(x) => x as $t,
+                    }
+                }
+
+                fn wrapping_from(value: Wide) -> $t {
+                    match value {
+                        // two's complement: the low bits of the magnitude, negated
+                        Wide::Int { negative, magnitude } =>
+                            (if negative { magnitude.wrapping_neg() } else { magnitude }) as $t,
+                        Wide::Float(x) if !x.is_finite() => 0,
+                        // the whole part modulo 2^BITS, which is exact in floating point
+                        Wide::Float(x) => {
+                            let low = (x.abs().trunc() % 2f64.powi($t::BITS as i32)) as u128;
+                            (if x < 0.0 { low.wrapping_neg() } else { low }) as $t
+                        },
+                    }
+                }
+            }
+        )*
+    };
+}
+
+int_primitive!(i8 true, i16 true, i32 true, i64 true, i128 true, isize true,
+               u8 false, u16 false, u32 false, u64 false, u128 false, usize false);
+
+macro_rules! float_primitive {
+    ($($t:ident),*) => {
+        $(
+            impl Primitive for $t {
+                const NAME: &'static str = stringify!($t);
+
+                fn to_wide(self) -> Wide {
+                    Wide::Float(self as f64)
+                }
+
+                fn checked_from(value: Wide) -> Result<$t, Reason> {
+                    let result = $t::wrapping_from(value);
+                    let (finite, negative) = match value {
+                        Wide::Int { negative, .. } => (true, negative),
+                        Wide::Float(x) => (x.is_finite(), x < 0.0),
+                    };
+                    if finite && result.is_infinite() {
+                        return Err(if negative { Reason::Underflow } else { Reason::Overflow });
+                    }
+                    match value {
+                        Wide::Int { magnitude, .. } if significant_bits(magnitude) > $t::MANTISSA_DIGITS =>
+                            Err(Reason::Inexact),
+                        Wide::Float(x) if x.is_finite() && result as f64 != x => Err(Reason::Inexact),
+                        _ => Ok(result),
+                    }
+                }
+
+                fn saturating_from(value: Wide) -> $t {
+                    let result = $t::wrapping_from(value);
+                    match $t::checked_from(value) {
+                        Err(Reason::Overflow) => $t::MAX,
+                        Err(Reason::Underflow) => $t::MIN,
+                        _ => result,
+                    }
+                }
+
+                // what `as` does: round to the nearest float, which may be infinite
+                fn wrapping_from(value: Wide) -> $t {
+                    match value {
+                        Wide::Int { negative, magnitude } => {
+                            let result = magnitude as $t;
+                            if negative { -result } else { result }
+                        },
+                        Wide::Float(x) => x as $t,
+                    }
+                }
+            }
+        )*
+    };
+}
+
+float_primitive!(f32, f64);
+
+// the bits from the highest set one to the lowest, which is what a float's mantissa has to hold
+fn significant_bits(magnitude: u128) -> u32 {
+    if magnitude == 0 { 0 } else { 128 - magnitude.leading_zeros() - magnitude.trailing_zeros() }
+}
+
+pub trait Cast: Primitive {
+    fn checked_cast<T: Primitive>(self) -> Result<T, CastError> {
+        T::checked_from(self.to_wide()).map_err(|reason| self.cast_error::<T>(reason))
+    }
+
+    fn saturating_cast<T: Primitive>(self) -> T {
+        T::saturating_from(self.to_wide())
+    }
+
+    fn wrapping_cast<T: Primitive>(self) -> T {
+        T::wrapping_from(self.to_wide())
+    }
+
+    // For an integer the rounding changes nothing, so this is checked_cast
+    fn rounding_cast<T: Primitive>(self, rounding: Rounding) -> Result<T, CastError> {
+        let value = match self.to_wide() {
+            Wide::Float(x) => Wide::Float(rounding.apply(x)),
+            value => value,
+        };
+        T::checked_from(value).map_err(|reason| self.cast_error::<T>(reason))
+    }
+
+    fn cast_error<T: Primitive>(self, reason: Reason) -> CastError {
+        CastError { value: self.to_string(), from: Self::NAME, to: T::NAME, reason }
+    }
+}
+
+impl<P: Primitive> Cast for P {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::convert::TryFrom;
+
+    // every power of two, one less and one more, and their negations, that fit in the type
+    macro_rules! boundaries {
+        ($t:ty) => {{
+            let mut values: Vec<$t> = vec![<$t>::MIN, <$t>::MAX, 0];
+            for k in 0..128 {
+                let power = 1u128 << k;
+                for &candidate in &[power - 1, power, power + 1] {
+                    values.extend(<$t>::try_from(candidate).ok());
+                    values.extend(i128::try_from(candidate).ok().and_then(|c| <$t>::try_from(-c).ok()));
+                }
+            }
+            values
+        }};
+    }
+
+    // checked is TryFrom, wrapping is `as` and saturating clamps, for every pair of integer types
+    macro_rules! int_to_int {
+        ($values:expr, $s:ty => $($t:ty),*) => {{
+            for &v in $values.iter() {
+                let v: $s = v;
+                $(
+                    let expected = <$t>::try_from(v).ok();
+                    assert_eq!(v.checked_cast::<$t>().ok(), expected, "{} as {}", v, stringify!($t));
+                    assert_eq!(v.wrapping_cast::<$t>(), v as $t, "{} wrapping as {}", v, stringify!($t));
+                    let clamped = expected.unwrap_or(if v.to_string().starts_with('-') { <$t>::MIN } else { <$t>::MAX });
+                    assert_eq!(v.saturating_cast::<$t>(), clamped, "{} saturating as {}", v, stringify!($t));
+                )*
+            }
+        }};
+    }
+
+    macro_rules! int_to_all_ints {
+        ($($s:ty),*) => {
+            $(int_to_int!(boundaries!($s), $s => i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);)*
+        };
+    }
+
+    #[test]
+    fn integers_to_integers() {
+        int_to_all_ints!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+        // every value of the small types
+        int_to_int!((i8::MIN..=i8::MAX).collect::<Vec<_>>(), i8 => u8, u16, i16);
+        int_to_int!((u8::MIN..=u8::MAX).collect::<Vec<_>>(), u8 => i8);
+        int_to_int!((i16::MIN..=i16::MAX).collect::<Vec<_>>(), i16 => i8, u8, u16);
+        int_to_int!((u16::MIN..=u16::MAX).collect::<Vec<_>>(), u16 => i8, u8, i16);
+    }
+
+    #[test]
+    fn the_as_pitfalls() {
+        assert_eq!(1000i32.checked_cast::<u8>().unwrap_err().to_string(), "cannot cast 1000_i32 to u8: it is above the maximum");
+        assert_eq!((-1i8).checked_cast::<u8>().unwrap_err().reason, Reason::Underflow);
+        assert_eq!(128i32.checked_cast::<i8>().unwrap_err().reason, Reason::Overflow);
+        assert_eq!(65.4321_f32.checked_cast::<u8>().unwrap_err(), CastError {
+            value: "65.4321".to_owned(), from: "f32", to: "u8", reason: Reason::Fractional,
+        });
+        assert_eq!((1000i32.saturating_cast::<u8>(), (-1i8).saturating_cast::<u8>(), 128i32.saturating_cast::<i8>()),
+                   (255, 0, 127));
+        assert_eq!((1000i32.wrapping_cast::<u8>(), (-1i8).wrapping_cast::<u8>(), 128i32.wrapping_cast::<i8>()),
+                   (232, 255, -128));
+        assert_eq!(65.4321_f32.rounding_cast::<u8>(Rounding::TowardZero), Ok(65));
+    }
+
+    macro_rules! float_to_all_ints {
+        ($x:expr => $($t:ty),*) => {{
+            let x = $x;
+            $(
+                // float to integer `as` is saturating
+                assert_eq!(x.saturating_cast::<$t>(), x as $t, "{:?} saturating as {}", x, stringify!($t));
+                match x.checked_cast::<$t>() {
+                    Ok(n) => assert!(n as f64 == x as f64 && x as $t == n, "{:?} as {}", x, stringify!($t)),
+                    Err(e) => match e.reason {
+                        Reason::NaN => assert!(x.is_nan()),
+                        Reason::Infinite => assert!(x.is_infinite()),
+                        Reason::Fractional => assert!(x.fract() != 0.0),
+                        Reason::Overflow => assert!(x as $t == <$t>::MAX && (x as f64) >= <$t>::MAX as f64),
+                        Reason::Underflow => assert!(x as $t == <$t>::MIN && x <= -1.0),
+                        Reason::Inexact => panic!("{:?} as {} is inexact", x, stringify!($t)),
+                    },
+                }
+            )*
+        }};
+    }
+
+    #[test]
+    fn floats_to_integers() {
+        let mut values = vec![0.0, -0.0, 0.5, -0.5, 1.0, -1.0, 1.5, f64::NAN, f64::INFINITY, f64::NEG_INFINITY, f64::MAX,
+                              f64::MIN, f64::MIN_POSITIVE];
+        for k in 0..130 {
+            let power = 2f64.powi(k);
+            values.extend(&[power, power.next_up(), power.next_down(), power - 1.0, power + 0.5]);
+        }
+        values.extend(values.clone().iter().map(|x| -x));
+        for &x in &values {
+            float_to_all_ints!(x => i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+            float_to_all_ints!(x as f32 => i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+        }
+
+        assert_eq!(255.0f64.checked_cast::<u8>(), Ok(255));
+        assert_eq!(256.0f64.checked_cast::<u8>().unwrap_err().reason, Reason::Overflow);
+        assert_eq!((-0.0f64).checked_cast::<u8>(), Ok(0));
+        assert_eq!((-0.5f64).checked_cast::<u8>().unwrap_err().reason, Reason::Fractional);
+        assert_eq!(9223372036854775807.0f64.checked_cast::<i64>().unwrap_err().reason, Reason::Overflow);
+        assert_eq!((-9223372036854775808.0f64).checked_cast::<i64>(), Ok(i64::MIN));
+        assert_eq!(2f32.powi(127).checked_cast::<u128>(), Ok(1 << 127));
+        assert_eq!(f32::MAX.checked_cast::<u128>(), Ok(f32::MAX as u128));
+        assert_eq!(f32::MAX.checked_cast::<i128>().unwrap_err().reason, Reason::Overflow);
+        assert_eq!(f64::NAN.checked_cast::<i32>().unwrap_err().reason, Reason::NaN);
+        assert_eq!(f64::NEG_INFINITY.checked_cast::<i32>().unwrap_err().reason, Reason::Infinite);
+        assert_eq!((f64::NAN.saturating_cast::<i32>(), f64::INFINITY.saturating_cast::<u8>()), (0, 255));
+    }
+
+    #[test]
+    fn wrapping_floats() {
+        assert_eq!(256.0f64.wrapping_cast::<u8>(), 0);
+        assert_eq!((-1.0f64).wrapping_cast::<u8>(), 255);
+        assert_eq!(300.7f64.wrapping_cast::<u8>(), 44);
+        assert_eq!((-129.0f64).wrapping_cast::<i8>(), 127);
+        assert_eq!((2f64.powi(64) + 4096.0).wrapping_cast::<u64>(), 4096);
+        assert_eq!((-3.0f64).wrapping_cast::<u128>(), u128::MAX - 2);
+        assert_eq!(2f64.powi(127).wrapping_cast::<i128>(), i128::MIN);
+        assert_eq!((f64::NAN.wrapping_cast::<i32>(), f64::INFINITY.wrapping_cast::<i32>()), (0, 0));
+        for x in -70000i32..70000 {
+            assert_eq!((x as f64 + 0.25 * x.signum() as f64).wrapping_cast::<i16>(), x as i16);
+            assert_eq!((x as f32).wrapping_cast::<u16>(), x as u16);
+        }
+    }
+
+    #[test]
+    fn rounding() {
+        let cases = [
+            (2.5f64, [2, 3, 2, 2, 3]),
+            (-2.5, [-2, -3, -2, -3, -2]),
+            (3.5, [3, 4, 4, 3, 4]),
+            (-2.7, [-2, -3, -3, -3, -2]),
+            (2.2, [2, 2, 2, 2, 3]),
+        ];
+        let modes = [Rounding::TowardZero, Rounding::Nearest, Rounding::NearestEven, Rounding::Down, Rounding::Up];
+        for &(x, expected) in &cases {
+            for (&mode, &n) in modes.iter().zip(&expected) {
+                assert_eq!(x.rounding_cast::<i32>(mode), Ok(n), "{} rounded {:?}", x, mode);
+            }
+        }
+        assert_eq!(255.5f64.rounding_cast::<u8>(Rounding::Nearest).unwrap_err().reason, Reason::Overflow);
+        assert_eq!(255.5f64.rounding_cast::<u8>(Rounding::Down), Ok(255));
+        assert_eq!((-0.4f64).rounding_cast::<u8>(Rounding::Nearest), Ok(0));
+        assert_eq!(f32::NAN.rounding_cast::<u8>(Rounding::Nearest).unwrap_err().reason, Reason::NaN);
+        assert_eq!(7u8.rounding_cast::<i8>(Rounding::Up), Ok(7));
+    }
+
+    #[test]
+    fn to_floats() {
+        assert_eq!(16777216i32.checked_cast::<f32>(), Ok(16777216.0));
+        assert_eq!(16777217i32.checked_cast::<f32>().unwrap_err().reason, Reason::Inexact);
+        assert_eq!(16777217i32.checked_cast::<f64>(), Ok(16777217.0));
+        assert_eq!((1u128 << 127).checked_cast::<f32>(), Ok(2f32.powi(127)));
+        assert_eq!(u128::MAX.checked_cast::<f32>().unwrap_err().reason, Reason::Overflow);
+        assert_eq!(u128::MAX.saturating_cast::<f32>(), f32::MAX);
+        assert_eq!(u128::MAX.wrapping_cast::<f32>(), u128::MAX as f32);
+        assert_eq!(i64::MIN.checked_cast::<f64>(), Ok(-9223372036854775808.0));
+        assert_eq!((i64::MIN + 1).checked_cast::<f64>().unwrap_err().reason, Reason::Inexact);
+
+        assert_eq!(0.1f64.checked_cast::<f32>().unwrap_err().reason, Reason::Inexact);
+        assert_eq!(0.5f64.checked_cast::<f32>(), Ok(0.5));
+        assert_eq!(1e300f64.checked_cast::<f32>().unwrap_err().reason, Reason::Overflow);
+        assert_eq!((-1e300f64).saturating_cast::<f32>(), f32::MIN);
+        assert_eq!(1e300f64.wrapping_cast::<f32>(), f32::INFINITY);
+        assert_eq!(f64::NEG_INFINITY.checked_cast::<f32>(), Ok(f32::NEG_INFINITY));
+        assert!(f64::NAN.checked_cast::<f32>().unwrap().is_nan());
+        assert_eq!(0.1f32.checked_cast::<f64>(), Ok(0.1f32 as f64));
+
+        for &v in boundaries!(i64).iter() {
+            assert_eq!(v.wrapping_cast::<f64>(), v as f64);
+            assert_eq!(v.wrapping_cast::<f32>(), v as f32);
+        }
+        for &v in boundaries!(u128).iter() {
+            assert_eq!(v.wrapping_cast::<f64>(), v as f64);
+            assert_eq!(v.checked_cast::<f64>().is_ok(), v.count_ones() == 0 || 128 - v.leading_zeros() - v.trailing_zeros() <= 53);
+        }
+    }
+}
@@ -0,0 +1,321 @@
+// LITERALS
+// `parse_literal` reads a number written the way Rust source writes it, so a config value or a command line
+// argument can be `0x80u32`, `1_000`, `65.4321_f32` or `1e-3`:
+// - a `0x`, `0o` or `0b` prefix for hexadecimal, octal and binary integers
+// - `_` anywhere after the first digit
+// - a fraction and an `e` exponent, for decimal floats
+// - a type suffix, checked against the value: `256u8` and `-1u32` are errors
+// A leading `-` is accepted too, although in Rust source it is an operator rather than part of the literal.
+//
+// Without a suffix, rustc picks the type from the context; here an integer is read as an i128 and a float as an
+// f64, and Display writes them back without a suffix.
+
+use std::error;
+use std::fmt;
+use std::str::FromStr;
+
+use types::cast::{Primitive, Wide};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Literal {
+    I8(i8),
+    I16(i16),
+    I32(i32),
+    I64(i64),
+    I128(i128),
+    Isize(isize),
+    U8(u8),
+    U16(u16),
+    U32(u32),
+    U64(u64),
+    U128(u128),
+    Usize(usize),
+    F32(f32),
+    F64(f64),
+    // no suffix
+    Int(i128),
+    Float(f64),
+}
+
+impl Literal {
+    // the suffix, or what rustc calls a literal whose type isn't known yet
+    pub fn ty(&self) -> &'static str {
+        match *self {
+            Literal::I8(_) => "i8",
+            Literal::I16(_) => "i16",
+            Literal::I32(_) => "i32",
+            Literal::I64(_) => "i64",
+            Literal::I128(_) => "i128",
+            Literal::Isize(_) => "isize",
+            Literal::U8(_) => "u8",
+            Literal::U16(_) => "u16",
+            Literal::U32(_) => "u32",
+            Literal::U64(_) => "u64",
+            Literal::U128(_) => "u128",
+            Literal::Usize(_) => "usize",
+            Literal::F32(_) => "f32",
+            Literal::F64(_) => "f64",
+            Literal::Int(_) => "{integer}",
+            Literal::Float(_) => "{float}",
+        }
+    }
+
+    pub fn is_float(&self) -> bool {
+        matches!(*self, Literal::F32(_) | Literal::F64(_) | Literal::Float(_))
+    }
+}
+
+// Decimal, with the suffix: `parse_literal` reads it back as the same Literal
+impl fmt::Display for Literal {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Literal::I8(n) => write!(f, "{}i8", n),
+            Literal::I16(n) => write!(f, "{}i16", n),
+            Literal::I32(n) => write!(f, "{}i32", n),
+            Literal::I64(n) => write!(f, "{}i64", n),
+            Literal::I128(n) => write!(f, "{}i128", n),
+            Literal::Isize(n) => write!(f, "{}isize", n),
+            Literal::U8(n) => write!(f, "{}u8", n),
+            Literal::U16(n) => write!(f, "{}u16", n),
+            Literal::U32(n) => write!(f, "{}u32", n),
+            Literal::U64(n) => write!(f, "{}u64", n),
+            Literal::U128(n) => write!(f, "{}u128", n),
+            Literal::Usize(n) => write!(f, "{}usize", n),
+            // `{:?}` always has a `.` or an exponent, which keeps an unsuffixed float from reading back as an integer
+            Literal::F32(x) => write!(f, "{:?}f32", x),
+            Literal::F64(x) => write!(f, "{:?}f64", x),
+            Literal::Int(n) => write!(f, "{}", n),
+            Literal::Float(x) => write!(f, "{:?}", x),
+        }
+    }
+}
+
+// Each position is a byte offset into the text
+#[derive(Debug, Clone, PartialEq)]
+pub enum LiteralError {
+    // nothing where a digit should be, as in "", "-", "0x", "_1" or "1e"
+    NoDigits { position: usize },
+    // e.g. a 2 in a binary literal
+    InvalidDigit { position: usize, digit: char, radix: u32 },
+    // not a type, or a type the literal can't have, like the f32 of `0b1f32` or the u8 of `1.5u8`
+    InvalidSuffix { position: usize, suffix: String },
+    OutOfRange { literal: String, ty: &'static str },
+}
+
+impl fmt::Display for LiteralError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            LiteralError::NoDigits { position } => write!(f, "expected a digit at {}", position),
+            LiteralError::InvalidDigit { position, digit, radix } =>
+                write!(f, "invalid digit `{}` at {} for a base {} literal", digit, position, radix),
+            LiteralError::InvalidSuffix { position, ref suffix } => write!(f, "invalid suffix `{}` at {}", suffix, position),
+            LiteralError::OutOfRange { ref literal, ty } => write!(f, "`{}` is out of range for {}", literal, ty),
+        }
+    }
+}
+
+impl error::Error for LiteralError {}
+
+impl FromStr for Literal {
+    type Err = LiteralError;
+
+    fn from_str(text: &str) -> Result<Literal, LiteralError> {
+        parse_literal(text)
+    }
+}
+
+// the length of the longest prefix of `text` whose chars match
+fn scan<F: Fn(char) -> bool>(text: &str, matches: F) -> usize {
+    text.find(|c: char| !matches(c)).unwrap_or(text.len())
+}
+
+pub fn parse_literal(text: &str) -> Result<Literal, LiteralError> {
+    let negative = text.starts_with('-');
+    let start = if negative { 1 } else { 0 };
+    let (radix, digits) = match text.get(start..start + 2) {
+        Some("0x") => (16, start + 2),
+        Some("0o") => (8, start + 2),
+        Some("0b") => (2, start + 2),
+        _ => (10, start),
+    };
+
+    // binary and octal literals take every decimal digit, so that a wrong one is reported rather than read as a
+    // suffix
+    let end = digits + if radix == 16 {
+        scan(&text[digits..], |c| c.is_ascii_hexdigit() || c == '_')
+    } else {
+        scan(&text[digits..], |c| c.is_ascii_digit() || c == '_')
+    };
+    // `0x_1` is fine but `_1` is an identifier
+    if !text[digits..end].contains(|c: char| c != '_') || (radix == 10 && text[digits..].starts_with('_')) {
+        return Err(LiteralError::NoDigits { position: digits });
+    }
+    if let Some((i, digit)) = text[digits..end].char_indices().find(|&(_, c)| c != '_' && c.to_digit(10) >= Some(radix)) {
+        return Err(LiteralError::InvalidDigit { position: digits + i, digit, radix });
+    }
+
+    let mut end = end;
+    let mut float = false;
+    if radix == 10 {
+        // `1.5` and `1.`, but not `1..2` or the method call of `1.max(2)`
+        let rest = &text[end..];
+        if rest.starts_with('.') && !rest[1..].starts_with(|c: char| c == '.' || c == '_' || c.is_alphabetic()) {
+            float = true;
+            end += 1 + scan(&text[end + 1..], |c| c.is_ascii_digit() || c == '_');
+        }
+        if text[end..].starts_with(['e', 'E']) {
+            float = true;
+            end += 1;
+            if text[end..].starts_with(['+', '-']) {
+                end += 1;
+            }
+            let exponent = scan(&text[end..], |c| c.is_ascii_digit() || c == '_');
+            if !text[end..end + exponent].contains(|c: char| c.is_ascii_digit()) {
+                return Err(LiteralError::NoDigits { position: end + exponent });
+            }
+            end += exponent;
+        }
+    }
+
+    let suffix = &text[end..];
+    let invalid_suffix = || LiteralError::InvalidSuffix { position: end, suffix: suffix.to_owned() };
+    let out_of_range = |ty| LiteralError::OutOfRange { literal: text.to_owned(), ty };
+    let number: String = text[..end].chars().filter(|&c| c != '_').collect();
+
+    match suffix {
+        "f32" | "f64" | "" if float || suffix.starts_with('f') => {
+            if radix != 10 {
+                return Err(invalid_suffix());
+            }
+            if suffix == "f32" {
+                let x: f32 = number.parse().map_err(|_| invalid_suffix())?;
+                if x.is_infinite() { Err(out_of_range("f32")) } else { Ok(Literal::F32(x)) }
+            } else {
+                let x: f64 = number.parse().map_err(|_| invalid_suffix())?;
+                let literal = if suffix.is_empty() { Literal::Float(x) } else { Literal::F64(x) };
+                if x.is_infinite() { Err(out_of_range("f64")) } else { Ok(literal) }
+            }
+        },
+        _ if float => Err(invalid_suffix()),
+        _ => {
+            let ty = match suffix {
+                "" => "i128",
+                _ => *INT_SUFFIXES.iter().find(|&&ty| ty == suffix).ok_or_else(invalid_suffix)?,
+            };
+            let mut magnitude = 0u128;
+            for c in text[digits..end].chars().filter(|&c| c != '_') {
+                magnitude = magnitude.checked_mul(radix as u128)
+                    .and_then(|m| m.checked_add(c.to_digit(radix).unwrap_or(0) as u128))
+                    .ok_or_else(|| out_of_range(ty))?;
+            }
+            let value = Wide::Int { negative, magnitude };
+            let literal = match suffix {
+                "i8" => typed(value, Literal::I8),
+                "i16" => typed(value, Literal::I16),
+                "i32" => typed(value, Literal::I32),
+                "i64" => typed(value, Literal::I64),
+                "i128" => typed(value, Literal::I128),
+                "isize" => typed(value, Literal::Isize),
+                "u8" => typed(value, Literal::U8),
+                "u16" => typed(value, Literal::U16),
+                "u32" => typed(value, Literal::U32),
+                "u64" => typed(value, Literal::U64),
+                "u128" => typed(value, Literal::U128),
+                "usize" => typed(value, Literal::Usize),
+                _ => typed(value, Literal::Int),
+            };
+            literal.ok_or_else(|| out_of_range(ty))
+        },
+    }
+}
+
+const INT_SUFFIXES: [&str; 12] = ["i8", "i16", "i32", "i64", "i128", "isize", "u8", "u16", "u32", "u64", "u128", "usize"];
+
+// the value as a T, if it fits, in the Literal variant for T
+fn typed<T: Primitive>(value: Wide, variant: fn(T) -> Literal) -> Option<Literal> {
+    T::checked_from(value).ok().map(variant)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn the_literals_of_the_examples() {
+        assert_eq!(parse_literal("5i32"), Ok(Literal::I32(5)));
+        assert_eq!(parse_literal("0b0011u32"), Ok(Literal::U32(3)));
+        assert_eq!(parse_literal("0x80u32"), Ok(Literal::U32(128)));
+        assert_eq!(parse_literal("1_000"), Ok(Literal::Int(1000)));
+        assert_eq!(parse_literal("65.4321_f32"), Ok(Literal::F32(65.4321)));
+        assert_eq!(parse_literal("1000u16"), Ok(Literal::U16(1000)));
+        assert_eq!(parse_literal("0.000_001"), Ok(Literal::Float(0.000001)));
+        assert_eq!(parse_literal("-1i8"), Ok(Literal::I8(-1)));
+        assert_eq!(parse_literal("0o777"), Ok(Literal::Int(511)));
+    }
+
+    #[test]
+    fn floats() {
+        assert_eq!(parse_literal("1e3"), Ok(Literal::Float(1000.0)));
+        assert_eq!(parse_literal("2.5E-3_f64"), Ok(Literal::F64(0.0025)));
+        assert_eq!(parse_literal("1."), Ok(Literal::Float(1.0)));
+        assert_eq!(parse_literal("1f32"), Ok(Literal::F32(1.0)));
+        assert_eq!(parse_literal("-1_0.5e+1"), Ok(Literal::Float(-105.0)));
+        assert!(parse_literal("1.5").unwrap().is_float());
+        // the f32 is hex digits
+        assert_eq!(parse_literal("0x1f32"), Ok(Literal::Int(0x1f32)));
+    }
+
+    #[test]
+    fn ranges() {
+        assert_eq!(parse_literal("255u8"), Ok(Literal::U8(255)));
+        assert_eq!(parse_literal("256u8"), Err(LiteralError::OutOfRange { literal: "256u8".to_owned(), ty: "u8" }));
+        assert_eq!(parse_literal("-128i8"), Ok(Literal::I8(-128)));
+        assert!(parse_literal("-129i8").is_err());
+        assert!(parse_literal("128i8").is_err());
+        assert_eq!(parse_literal("-0u8"), Ok(Literal::U8(0)));
+        assert!(parse_literal("-1u32").is_err());
+        assert_eq!(parse_literal("0xffff_ffff_ffff_ffff_ffff_ffff_ffff_ffffu128"), Ok(Literal::U128(u128::MAX)));
+        assert_eq!(parse_literal("340282366920938463463374607431768211456u128").unwrap_err().to_string(),
+                   "`340282366920938463463374607431768211456u128` is out of range for u128");
+        assert_eq!(parse_literal("-170141183460469231731687303715884105728"), Ok(Literal::Int(i128::MIN)));
+        assert!(parse_literal("170141183460469231731687303715884105728").is_err());
+        assert_eq!(parse_literal("1e39f32").unwrap_err(), LiteralError::OutOfRange { literal: "1e39f32".to_owned(), ty: "f32" });
+        assert!(parse_literal("1e309").is_err());
+        assert_eq!(parse_literal("1_000_000_000_000_000_000_000_000_000_000_000_000_000u8").unwrap_err().to_string(),
+                   "`1_000_000_000_000_000_000_000_000_000_000_000_000_000u8` is out of range for u8");
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(parse_literal(""), Err(LiteralError::NoDigits { position: 0 }));
+        assert_eq!(parse_literal("-"), Err(LiteralError::NoDigits { position: 1 }));
+        assert_eq!(parse_literal("0x"), Err(LiteralError::NoDigits { position: 2 }));
+        assert_eq!(parse_literal("0x_"), Err(LiteralError::NoDigits { position: 2 }));
+        assert_eq!(parse_literal("_1"), Err(LiteralError::NoDigits { position: 0 }));
+        assert_eq!(parse_literal("1e"), Err(LiteralError::NoDigits { position: 2 }));
+        assert_eq!(parse_literal("0b102"), Err(LiteralError::InvalidDigit { position: 4, digit: '2', radix: 2 }));
+        assert_eq!(parse_literal("0o8"), Err(LiteralError::InvalidDigit { position: 2, digit: '8', radix: 8 }));
+        let suffix = |text: &str| match parse_literal(text) {
+            Err(LiteralError::InvalidSuffix { position, suffix }) => (position, suffix),
+            other => panic!("{}: {:?}", text, other),
+        };
+        assert_eq!(suffix("1.5u8"), (3, "u8".to_owned()));
+        assert_eq!(suffix("0b1f32"), (3, "f32".to_owned()));
+        assert_eq!(suffix("12abc"), (2, "abc".to_owned()));
+        assert_eq!(suffix("0X10"), (1, "X10".to_owned()));
+        assert_eq!(suffix("1..2"), (1, "..2".to_owned()));
+        assert_eq!(parse_literal("0b102").unwrap_err().to_string(), "invalid digit `2` at 4 for a base 2 literal");
+    }
+
+    #[test]
+    fn display_reads_back() {
+        let literals = ["5i32", "-1i8", "0x80u32", "1_000", "65.4321_f32", "1e300", "-0.5", "1.", "7usize", "2f64"];
+        for text in literals.iter() {
+            let literal = parse_literal(text).unwrap();
+            assert_eq!(literal.to_string().parse::<Literal>(), Ok(literal), "{}", text);
+        }
+        assert_eq!(Literal::Float(1.0).to_string(), "1.0");
+        assert_eq!(Literal::U32(128).to_string(), "128u32");
+        assert_eq!((Literal::Int(1).ty(), Literal::Float(1.0).ty(), Literal::U8(1).ty()), ("{integer}", "{float}", "u8"));
+    }
+}
@@ -0,0 +1,81 @@
+// CASTING
+// Rust provides no implicit conversiion but explicit conversion can be used using as
+
+// INFERENCING
+// The type inferencing system looks at the value as well as how the variable is used in the code to infer the type.
+
+// the casts below are the point of the example
+#![allow(clippy::unnecessary_cast, clippy::vec_init_then_push)]
+
+pub mod cast;
+pub mod literal;
+
+use std::fmt::Display;
+use std::io::{self, Write};
+
+use self::cast::{Cast, CastError, Rounding};
+use self::literal::parse_literal;
+
+// ALIASING
+// the type statement can be used to give a new name to an existing type
+// the main use of aliasing is avoiding boilerplate, e.g. the IoResult<T> type is an alias for the Result<T, IoError> type
+
+#[allow(non_camel_case_types)]
+pub type u64_t = u64;
+
+
+#[allow(overflowing_literals)]
+pub fn run(out: &mut impl Write) -> io::Result<()> {
+    let decimal = 65.4321_f32;
+
+    // explicit conversion as allowed by rust
+    let integer: u8 = decimal as u8;
+    let character = integer as char;
+
+    writeln!(out, "Casting: {} -> {} -> {}", decimal, integer, character)?;
+
+    // when casting any value to an unsigned type T, std::T::MAX + 1 is added or subtracted till the value fits into the new type
+    // mostly, this means that don't cast like an idiot!
+
+    // have to use `as` with a variable binding - numeric literals can be annotated by using suffixes
+    // if a literal is unsuffixed, the type depends on how it's used.
+    writeln!(out, "1000 as u16 is {}", 1000u16)?;
+    writeln!(out, "1000 as u8 is {}", 1000u8)?;
+    writeln!(out, "-1 as u8 is {}", (-1i8) as u8)?;
+
+    writeln!(out, "128 as i16 is {}", 128 as i16)?;
+    writeln!(out, "128 as i8 is {}", 128 as i8)?;
+
+    // CHECKED CASTS: types::cast has casts that say when a value doesn't fit instead of changing it
+    writeln!(out, "1000 checked as u8: {}", describe(1000i32.checked_cast::<u8>()))?;
+    writeln!(out, "-1 checked as u8: {}", describe((-1i8).checked_cast::<u8>()))?;
+    writeln!(out, "128 checked as i8: {}", describe(128i32.checked_cast::<i8>()))?;
+    writeln!(out, "{} checked as u8: {}", decimal, describe(decimal.checked_cast::<u8>()))?;
+    writeln!(out, "200 checked as u8: {}", describe(200i32.checked_cast::<u8>()))?;
+    writeln!(out, "1000 saturating as u8 is {}, wrapping is {}", 1000.saturating_cast::<u8>(), 1000.wrapping_cast::<u8>())?;
+    writeln!(out, "{} rounded to the nearest u8 is {:?}", decimal, decimal.rounding_cast::<u8>(Rounding::Nearest))?;
+
+    // LITERALS: types::literal reads numbers written the way these literals are, e.g. from a config file
+    for text in &["0x80u32", "65.4321_f32", "1_000", "1000u8", "0b102"] {
+        match parse_literal(text) {
+            Ok(literal) => writeln!(out, "{} is {} of type {}", text, literal, literal.ty())?,
+            Err(e) => writeln!(out, "{}: {}", text, e)?,
+        }
+    }
+
+    // INFERENCING
+    let elem = 5u8; // the compiler knows the type as we have annotated it
+
+    let mut vec = Vec::new(); // the compiler does not know the type of the vector yet
+    vec.push(elem); // it now knows the vector is u8
+    writeln!(out, "{:?}", vec)?;
+
+    Ok(())
+}
+
+fn describe<T: Display>(result: Result<T, CastError>) -> String {
+    match result {
+        Ok(value) => value.to_string(),
+        Err(e) => e.to_string(),
+    }
+}